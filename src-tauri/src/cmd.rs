@@ -1,4 +1,11 @@
 pub mod config;
+pub mod filter;
+pub mod inspector;
+pub mod map;
+pub mod monitor;
+pub mod pcap;
+pub mod recording;
+pub mod replay;
 pub mod transmit;
 
 pub use config::{get_config, save_config};
@@ -3,12 +3,14 @@ use std::sync::RwLock;
 use tauri::{AppHandle, State};
 use tauri_plugin_dialog::DialogExt;
 
-use crate::config::{self, AppConfig, ScenarioConfig, SharedConfig};
+use crate::config::{self, AppConfig, DisplayConfig, ScenarioConfig};
+use crate::core::{AppState, EntityIdentifier};
 
 #[tauri::command]
 pub async fn load_scenario_config(
     app: tauri::AppHandle,
     config: State<'_, RwLock<AppConfig>>,
+    state: State<'_, AppState>,
 ) -> Result<ScenarioConfig, String> {
     let file = app
         .dialog()
@@ -18,9 +20,15 @@ pub async fn load_scenario_config(
 
     if let Some(path) = file {
         let path = path.into_path().map_err(|_| "Invalid file path")?;
-        let contents = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+        let contents = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
         if let Ok(cfg) = toml::from_str::<ScenarioConfig>(&contents) {
-            config.write().unwrap().scenario_config = cfg.clone();
+            {
+                // Filters saved with this scenario file before are kept under its path
+                let mut config = config.write().map_err(|_| "AppConfig lock poisoned")?;
+                config.scenario_config = cfg.clone();
+                config.scenario_file = Some(path);
+            }
+            state.apply_scenario(&cfg).await?;
             return Ok(cfg);
         } else {
             Err("Unable to parse file".to_string())
@@ -30,28 +38,105 @@ pub async fn load_scenario_config(
     }
 }
 
+/// Returns the application configuration.
+///
+/// # Errors
+/// - Returns an error if the configuration lock is poisoned
 #[tauri::command]
-pub async fn get_config(config_state: State<'_, SharedConfig>) -> Result<AppConfig, String> {
-    Ok(config_state.read().await.clone())
+pub async fn get_config(config: State<'_, RwLock<AppConfig>>) -> Result<AppConfig, String> {
+    Ok(config
+        .read()
+        .map_err(|_| "AppConfig lock poisoned")?
+        .clone())
 }
 
+/// Replaces the application configuration, writes it to the config file and applies its
+/// scenario, rebinding the network service if its settings changed.
+///
+/// # Errors
+/// - Returns an error if the simulation address is not a valid DIS address, the
+///   configuration lock is poisoned or the file cannot be written
+/// - Returns an error if the network service cannot be bound with the new settings
 #[tauri::command]
 pub async fn save_config(
     config: AppConfig,
-    app_handle: AppHandle,
-    config_state: State<'_, SharedConfig>,
+    app: AppHandle,
+    current: State<'_, RwLock<AppConfig>>,
+    state: State<'_, AppState>,
 ) -> Result<(), String> {
-    config::save_config(&app_handle, &config)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to save config file: {e}");
-            e.to_string()
-        })?;
-
-    {
-        let mut config_guard = config_state.write().await;
-        *config_guard = config.clone();
+    let originator = config.simulation_address.originating_id()?;
+
+    let scenario = config.scenario_config.clone();
+    update_config(&app, &current, |current| *current = config).await?;
+
+    if let Ok(mut conflicts) = state.conflicts.lock() {
+        conflicts.set_originator(Some(originator));
     }
 
+    // The new settings are kept even if they cannot be bound, so they can be corrected
+    state.apply_scenario(&scenario).await?;
+
     Ok(())
 }
+
+/// Returns the entity identifier Centurion uses as the originator of its SIMAN PDUs.
+///
+/// # Errors
+/// - Returns an error if the configured simulation address is not a valid DIS address
+#[tauri::command]
+pub async fn get_simulation_address(
+    config: State<'_, RwLock<AppConfig>>,
+) -> Result<EntityIdentifier, String> {
+    config
+        .read()
+        .map_err(|_| "AppConfig lock poisoned")?
+        .simulation_address
+        .originating_id()
+}
+
+/// Returns the user's display preferences.
+///
+/// # Errors
+/// - Returns an error if the configuration lock is poisoned
+#[tauri::command]
+pub async fn get_display_config(
+    config: State<'_, RwLock<AppConfig>>,
+) -> Result<DisplayConfig, String> {
+    Ok(config
+        .read()
+        .map_err(|_| "AppConfig lock poisoned")?
+        .display)
+}
+
+/// Stores the user's display preferences and writes them to the config file.
+///
+/// # Errors
+/// - Returns an error if the configuration lock is poisoned or the file cannot be written
+#[tauri::command]
+pub async fn save_display_config(
+    display: DisplayConfig,
+    app: AppHandle,
+    config: State<'_, RwLock<AppConfig>>,
+) -> Result<(), String> {
+    update_config(&app, &config, |config| config.display = display).await
+}
+
+/// Applies `change` to the shared configuration and writes the result to the config file.
+///
+/// # Errors
+/// - Returns an error if the configuration lock is poisoned or the file cannot be written
+pub async fn update_config(
+    app: &AppHandle,
+    config: &RwLock<AppConfig>,
+    change: impl FnOnce(&mut AppConfig) + Send,
+) -> Result<(), String> {
+    let updated = {
+        let mut config = config.write().map_err(|_| "AppConfig lock poisoned")?;
+        change(&mut config);
+        config.clone()
+    };
+
+    config::save_config(app, &updated)
+        .await
+        .map_err(|e| format!("Unable to save the configuration: {e}"))
+}
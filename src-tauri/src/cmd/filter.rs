@@ -0,0 +1,89 @@
+use std::sync::RwLock;
+
+use tauri::{AppHandle, State};
+
+use crate::cmd::config::update_config;
+use crate::config::{AppConfig, SavedFilter};
+use crate::core::Filter;
+
+/// Checks a filter expression and returns it as it will be applied.
+///
+/// # Errors
+/// - Returns an error naming the column of the first problem if the expression is not
+///   a valid filter
+#[tauri::command]
+pub async fn check_filter(filter: String) -> Result<String, String> {
+    Ok(Filter::parse(&filter)?.text().to_string())
+}
+
+/// Returns the filters saved with the current scenario.
+///
+/// # Errors
+/// - Returns an error if the configuration lock is poisoned
+#[tauri::command]
+pub async fn list_filters(
+    config: State<'_, RwLock<AppConfig>>,
+) -> Result<Vec<SavedFilter>, String> {
+    Ok(config
+        .read()
+        .map_err(|_| "AppConfig lock poisoned")?
+        .saved_filters()
+        .to_vec())
+}
+
+/// Saves a filter with the current scenario, replacing any filter of the same name, and returns
+/// the saved filters.
+///
+/// # Errors
+/// - Returns an error if the name is blank, the expression is not a valid filter or the
+///   config file cannot be written
+#[tauri::command]
+pub async fn save_filter(
+    name: String,
+    expression: String,
+    app: AppHandle,
+    config: State<'_, RwLock<AppConfig>>,
+) -> Result<Vec<SavedFilter>, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Filters need a name".into());
+    }
+
+    let saved = SavedFilter {
+        name,
+        expression: Filter::parse(&expression)?.text().to_string(),
+    };
+
+    update_config(&app, &config, |config| {
+        let filters = config.saved_filters_mut();
+
+        if let Some(existing) = filters.iter_mut().find(|filter| filter.name == saved.name) {
+            *existing = saved;
+        } else {
+            filters.push(saved);
+        }
+    })
+    .await?;
+
+    list_filters(config).await
+}
+
+/// Removes a saved filter and returns the filters left.
+///
+/// # Errors
+/// - Returns an error if the configuration lock is poisoned or the file cannot be written
+#[tauri::command]
+pub async fn delete_filter(
+    name: String,
+    app: AppHandle,
+    config: State<'_, RwLock<AppConfig>>,
+) -> Result<Vec<SavedFilter>, String> {
+    update_config(&app, &config, |config| {
+        config
+            .saved_filters_mut()
+            .retain(|filter| filter.name != name);
+    })
+    .await?;
+
+    list_filters(config).await
+}
@@ -0,0 +1,188 @@
+use std::path::PathBuf;
+
+use tauri::{AppHandle, State};
+use tauri_plugin_dialog::DialogExt;
+
+use crate::core::{
+    AppState, CAPTURE_EXTENSION, CaptureBrowser, CapturePage, CaptureReader, Filter, Inspection,
+    Monitor, PduRow, inspect,
+};
+
+/// Most records returned in one page of a capture
+const MAX_PAGE_ROWS: usize = 500;
+
+/// Returns the latest PDUs received and sent, newest first.
+///
+/// # Errors
+/// - Returns an error if the PDU log lock is poisoned
+#[tauri::command]
+pub async fn get_pdu_log(state: State<'_, AppState>) -> Result<Vec<PduRow>, String> {
+    Ok(state
+        .pdu_log
+        .lock()
+        .map_err(|_| "PDU log lock poisoned")?
+        .snapshot())
+}
+
+/// Limits the live log to the PDUs matching `filter`, or lists every PDU if it is blank,
+/// and returns the matching PDUs, newest first.
+///
+/// # Errors
+/// - Returns an error if the filter is not a valid expression
+#[tauri::command]
+pub async fn set_live_filter(
+    filter: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<PduRow>, String> {
+    let filter = Filter::parse_optional(filter.as_deref())?;
+
+    let mut log = state.pdu_log.lock().map_err(|_| "PDU log lock poisoned")?;
+    log.set_filter(filter);
+    let rows = log.snapshot();
+    drop(log);
+
+    Ok(rows)
+}
+
+/// Decodes a PDU from the live log.
+///
+/// # Errors
+/// - Returns an error if the PDU has been dropped from the log
+#[tauri::command]
+pub async fn inspect_logged_pdu(id: u64, state: State<'_, AppState>) -> Result<Inspection, String> {
+    let data = state
+        .pdu_log
+        .lock()
+        .map_err(|_| "PDU log lock poisoned")?
+        .data(id)
+        .ok_or_else(|| format!("PDU {id} is no longer in the log"))?;
+
+    Ok(inspect(&data))
+}
+
+/// Opens a capture file in the inspector, asking for the file unless a `path` is given,
+/// and returns its first page of records matching `filter`.
+///
+/// # Errors
+/// - Returns an error if no file is selected or the file is not a readable capture
+#[tauri::command]
+pub async fn open_capture_browser(
+    app: AppHandle,
+    path: Option<PathBuf>,
+    filter: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<CapturePage, String> {
+    let filter = Filter::parse_optional(filter.as_deref())?;
+
+    let path = match path {
+        Some(path) => path,
+        None => app
+            .dialog()
+            .file()
+            .add_filter("Capture", &[CAPTURE_EXTENSION])
+            .blocking_pick_file()
+            .ok_or("No file selected")?
+            .into_path()
+            .map_err(|_| "Invalid file path")?,
+    };
+
+    // Captures without an index are scanned, as are all records when filtering, which may
+    // take a while for large files
+    let (browser, page) = tokio::task::spawn_blocking(move || {
+        let mut browser = CaptureBrowser::new(CaptureReader::open(&path)?);
+        browser.set_filter(filter)?;
+        let page = browser.page(0, MAX_PAGE_ROWS)?;
+        Ok::<_, String>((browser, page))
+    })
+    .await
+    .map_err(|e| format!("Unable to open capture: {e}"))??;
+
+    *state.browser.lock().map_err(|_| "Browser lock poisoned")? = Some(browser);
+
+    Ok(page)
+}
+
+/// Limits the open capture's records to those matching `filter`, or lists every record if
+/// it is blank, and returns the first page of matching records.
+///
+/// # Errors
+/// - Returns an error if the filter is not valid, no capture is open or its records
+///   cannot be read
+#[tauri::command]
+pub async fn set_capture_filter(
+    filter: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<CapturePage, String> {
+    let filter = Filter::parse_optional(filter.as_deref())?;
+
+    // Filtering reads every record, so the capture is taken out of the browser meanwhile
+    let mut browser = state
+        .browser
+        .lock()
+        .map_err(|_| "Browser lock poisoned")?
+        .take()
+        .ok_or("No capture is open")?;
+
+    let (browser, page) = tokio::task::spawn_blocking(move || {
+        let page = browser
+            .set_filter(filter)
+            .and_then(|()| browser.page(0, MAX_PAGE_ROWS));
+        (browser, page)
+    })
+    .await
+    .map_err(|e| format!("Unable to filter capture: {e}"))?;
+
+    *state.browser.lock().map_err(|_| "Browser lock poisoned")? = Some(browser);
+
+    page
+}
+
+/// Returns up to `count` of the open capture's matching records from position `start`.
+///
+/// # Errors
+/// - Returns an error if no capture is open or its records cannot be read
+#[tauri::command]
+pub async fn get_capture_rows(
+    start: usize,
+    count: usize,
+    state: State<'_, AppState>,
+) -> Result<CapturePage, String> {
+    let mut browser = state.browser.lock().map_err(|_| "Browser lock poisoned")?;
+    let page = browser
+        .as_mut()
+        .ok_or("No capture is open")?
+        .page(start, count.min(MAX_PAGE_ROWS));
+    drop(browser);
+
+    page
+}
+
+/// Decodes a record of the open capture.
+///
+/// # Errors
+/// - Returns an error if no capture is open or the record cannot be read
+#[tauri::command]
+pub async fn inspect_capture_pdu(
+    record: usize,
+    state: State<'_, AppState>,
+) -> Result<Inspection, String> {
+    let datagram = state
+        .browser
+        .lock()
+        .map_err(|_| "Browser lock poisoned")?
+        .as_mut()
+        .ok_or("No capture is open")?
+        .read(record)?;
+
+    Ok(inspect(&datagram.data))
+}
+
+/// Closes the capture open in the inspector.
+///
+/// # Errors
+/// - Returns an error if the browser lock is poisoned
+#[tauri::command]
+pub async fn close_capture_browser(state: State<'_, AppState>) -> Result<(), String> {
+    *state.browser.lock().map_err(|_| "Browser lock poisoned")? = None;
+    Ok(())
+}
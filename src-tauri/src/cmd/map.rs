@@ -0,0 +1,65 @@
+use std::sync::RwLock;
+
+use tauri::{AppHandle, State};
+use tauri_plugin_dialog::DialogExt;
+
+use crate::cmd::config::update_config;
+use crate::config::AppConfig;
+use crate::core::MapBackground;
+
+/// Asks for a `GeoJSON` file to draw behind the tactical map and remembers it for later
+/// sessions.
+///
+/// # Errors
+/// - Returns an error if no file is selected, the file is not `GeoJSON` or the choice cannot
+///   be saved
+#[tauri::command]
+pub async fn load_map_background(
+    app: AppHandle,
+    config: State<'_, RwLock<AppConfig>>,
+) -> Result<MapBackground, String> {
+    let path = app
+        .dialog()
+        .file()
+        .add_filter("GeoJSON", &["geojson", "json"])
+        .blocking_pick_file()
+        .ok_or("No file selected")?
+        .into_path()
+        .map_err(|_| "Invalid file path")?;
+
+    let background = MapBackground::load(&path)?;
+
+    update_config(&app, &config, |config| config.map.background = Some(path)).await?;
+
+    Ok(background)
+}
+
+/// Returns the background chosen in an earlier session, if any.
+///
+/// # Errors
+/// - Returns an error if the remembered file can no longer be read
+#[tauri::command]
+pub async fn get_map_background(
+    config: State<'_, RwLock<AppConfig>>,
+) -> Result<Option<MapBackground>, String> {
+    let path = config
+        .read()
+        .map_err(|_| "AppConfig lock poisoned")?
+        .map
+        .background
+        .clone();
+
+    path.map(|path| MapBackground::load(&path)).transpose()
+}
+
+/// Removes the map background.
+///
+/// # Errors
+/// - Returns an error if the choice cannot be saved
+#[tauri::command]
+pub async fn clear_map_background(
+    app: AppHandle,
+    config: State<'_, RwLock<AppConfig>>,
+) -> Result<(), String> {
+    update_config(&app, &config, |config| config.map.background = None).await
+}
@@ -0,0 +1,89 @@
+use tauri::{AppHandle, State};
+use tauri_plugin_dialog::DialogExt;
+
+use crate::core::{AppState, EntityRecord, Participant, TrafficSnapshot};
+
+/// Returns every simulation application heard on the exercise network so far.
+///
+/// # Errors
+/// - Returns an error if the roster lock is poisoned
+#[tauri::command]
+pub async fn get_roster(state: State<'_, AppState>) -> Result<Vec<Participant>, String> {
+    Ok(state
+        .roster
+        .lock()
+        .map_err(|_| "Roster lock poisoned")?
+        .participants())
+}
+
+/// Returns the latest reported state of every active entity.
+///
+/// # Errors
+/// - Returns an error if the entity registry lock is poisoned
+#[tauri::command]
+pub async fn get_entities(state: State<'_, AppState>) -> Result<Vec<EntityRecord>, String> {
+    Ok(state
+        .entities
+        .lock()
+        .map_err(|_| "Entity registry lock poisoned")?
+        .entities())
+}
+
+/// Returns the traffic counters accumulated since the last reset.
+///
+/// # Errors
+/// - Returns an error if the traffic statistics lock is poisoned
+#[tauri::command]
+pub async fn get_traffic_statistics(state: State<'_, AppState>) -> Result<TrafficSnapshot, String> {
+    Ok(state
+        .traffic
+        .lock()
+        .map_err(|_| "Traffic statistics lock poisoned")?
+        .statistics())
+}
+
+/// Clears the traffic counters.
+///
+/// # Errors
+/// - Returns an error if the traffic statistics lock is poisoned
+#[tauri::command]
+pub async fn reset_traffic_statistics(state: State<'_, AppState>) -> Result<(), String> {
+    state
+        .traffic
+        .lock()
+        .map_err(|_| "Traffic statistics lock poisoned")?
+        .reset();
+
+    Ok(())
+}
+
+/// Asks where to save the traffic counters and writes them there as CSV.
+///
+/// # Errors
+/// - Returns an error if no file is chosen or the file cannot be written
+#[tauri::command]
+pub async fn export_traffic_statistics(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let csv = state
+        .traffic
+        .lock()
+        .map_err(|_| "Traffic statistics lock poisoned")?
+        .statistics()
+        .to_csv();
+
+    let path = app
+        .dialog()
+        .file()
+        .add_filter("CSV", &["csv"])
+        .set_file_name("traffic.csv")
+        .blocking_save_file()
+        .ok_or("No file selected")?
+        .into_path()
+        .map_err(|_| "Invalid file path")?;
+
+    tokio::fs::write(&path, csv)
+        .await
+        .map_err(|e| format!("Unable to write {}: {e}", path.display()))
+}
@@ -0,0 +1,129 @@
+use std::net::{IpAddr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use tauri::{AppHandle, State};
+use tauri_plugin_dialog::DialogExt;
+
+use crate::config::AppConfig;
+use crate::core::{
+    CAPTURE_EXTENSION, ImportSummary, PCAP_EXTENSIONS, PCAPNG_EXTENSION, export_pcapng, import_pcap,
+};
+
+/// Names a file after `path` with its extension replaced.
+fn file_name(path: &Path, extension: &str) -> String {
+    let stem = path
+        .file_stem()
+        .map_or_else(|| "capture".into(), |stem| stem.to_string_lossy());
+    format!("{stem}.{extension}")
+}
+
+fn pick_file(app: &AppHandle, name: &str, extensions: &[&str]) -> Result<PathBuf, String> {
+    app.dialog()
+        .file()
+        .add_filter(name, extensions)
+        .blocking_pick_file()
+        .ok_or("No file selected")?
+        .into_path()
+        .map_err(|_| "Invalid file path".into())
+}
+
+fn save_file(
+    app: &AppHandle,
+    name: &str,
+    extension: &str,
+    file_name: String,
+) -> Result<PathBuf, String> {
+    app.dialog()
+        .file()
+        .add_filter(name, &[extension])
+        .set_file_name(file_name)
+        .blocking_save_file()
+        .ok_or("No file selected")?
+        .into_path()
+        .map_err(|_| "Invalid file path".into())
+}
+
+/// Asks for a capture file and where to save it, then exports it as pcapng for Wireshark.
+/// Returns the number of packets written.
+///
+/// Each datagram is addressed from its recorded source to the scenario's destination
+/// address, on the port it was received on.
+///
+/// # Errors
+/// - Returns an error if no file is chosen, the destination address is invalid, the
+///   capture cannot be read or the pcapng file cannot be written
+#[tauri::command]
+pub async fn export_capture_pcapng(
+    app: AppHandle,
+    config: State<'_, RwLock<AppConfig>>,
+) -> Result<usize, String> {
+    let network = config
+        .read()
+        .map_err(|_| "AppConfig lock poisoned")?
+        .scenario_config
+        .network
+        .clone();
+    let ip: IpAddr = network
+        .destination_ip
+        .parse()
+        .map_err(|e| format!("Invalid destination {}: {e}", network.destination_ip))?;
+    let destination = SocketAddr::new(ip, network.interface_port);
+
+    let capture = pick_file(&app, "Capture", &[CAPTURE_EXTENSION])?;
+    let output = save_file(
+        &app,
+        "pcapng",
+        PCAPNG_EXTENSION,
+        file_name(&capture, PCAPNG_EXTENSION),
+    )?;
+
+    let packets =
+        tokio::task::spawn_blocking(move || export_pcapng(&capture, &output, destination))
+            .await
+            .map_err(|e| format!("Unable to export capture: {e}"))??;
+
+    tracing::info!("Exported {packets} packets to pcapng");
+
+    Ok(packets)
+}
+
+/// Asks for a pcap or pcapng file and where to save the capture, then extracts the UDP
+/// datagrams sent to the scenario's interface or destination port.
+///
+/// # Errors
+/// - Returns an error if no file is chosen, the pcap file cannot be read or the capture
+///   cannot be written
+#[tauri::command]
+pub async fn import_capture_pcap(
+    app: AppHandle,
+    config: State<'_, RwLock<AppConfig>>,
+) -> Result<ImportSummary, String> {
+    let network = config
+        .read()
+        .map_err(|_| "AppConfig lock poisoned")?
+        .scenario_config
+        .network
+        .clone();
+    let mut ports = vec![network.interface_port, network.destination_port];
+    ports.dedup();
+
+    let input = pick_file(&app, "pcap", &PCAP_EXTENSIONS)?;
+    let output = save_file(
+        &app,
+        "Capture",
+        CAPTURE_EXTENSION,
+        file_name(&input, CAPTURE_EXTENSION),
+    )?;
+
+    let summary = import_pcap(&input, &output, ports).await?;
+
+    tracing::info!(
+        "Imported {} of {} packets from {}",
+        summary.datagrams,
+        summary.packets,
+        input.display()
+    );
+
+    Ok(summary)
+}
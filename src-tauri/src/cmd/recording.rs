@@ -0,0 +1,56 @@
+use std::sync::RwLock;
+
+use tauri::{AppHandle, Manager, State};
+
+use crate::config::AppConfig;
+use crate::core::{AppState, RecordingStatus};
+
+/// Starts recording every PDU received to a capture file in the configured directory.
+///
+/// # Errors
+/// - Returns an error if a recording is already running or the capture file cannot be
+///   created
+#[tauri::command]
+pub async fn start_recording(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    config: State<'_, RwLock<AppConfig>>,
+) -> Result<RecordingStatus, String> {
+    let settings = config
+        .read()
+        .map_err(|_| "AppConfig lock poisoned")?
+        .recording
+        .clone();
+
+    let directory = match settings.directory.clone() {
+        Some(directory) => directory,
+        None => app
+            .path()
+            .app_data_dir()
+            .map_err(|e| format!("Unable to find the app data directory: {e}"))?
+            .join("recordings"),
+    };
+
+    state
+        .recorder
+        .start(app.clone(), &state.network, directory, settings)
+        .await
+}
+
+/// Stops the running recording and closes its capture file.
+///
+/// # Errors
+/// - Returns an error if nothing is being recorded
+#[tauri::command]
+pub async fn stop_recording(state: State<'_, AppState>) -> Result<RecordingStatus, String> {
+    state.recorder.stop().await
+}
+
+/// Returns the state of the current or last recording.
+///
+/// # Errors
+/// - Never fails; async commands borrowing state must return a `Result`
+#[tauri::command]
+pub async fn get_recording_status(state: State<'_, AppState>) -> Result<RecordingStatus, String> {
+    Ok(state.recorder.status())
+}
@@ -0,0 +1,115 @@
+use std::path::PathBuf;
+
+use tauri::{AppHandle, State};
+use tauri_plugin_dialog::DialogExt;
+
+use crate::core::{
+    AppState, CAPTURE_EXTENSION, CaptureReader, Control, Filter, ReplayOptions, ReplayStatus,
+};
+
+/// Opens a capture file for replay, paused at its first record, asking for the file unless
+/// a `path` is given.
+///
+/// # Errors
+/// - Returns an error if no file is selected or the file is not a readable capture
+#[tauri::command]
+pub async fn open_replay(
+    app: AppHandle,
+    path: Option<PathBuf>,
+    state: State<'_, AppState>,
+) -> Result<ReplayStatus, String> {
+    let path = match path {
+        Some(path) => path,
+        None => app
+            .dialog()
+            .file()
+            .add_filter("Capture", &[CAPTURE_EXTENSION])
+            .blocking_pick_file()
+            .ok_or("No file selected")?
+            .into_path()
+            .map_err(|_| "Invalid file path")?,
+    };
+
+    // Captures without an index are scanned, which may take a while for large files
+    let reader = tokio::task::spawn_blocking(move || CaptureReader::open(&path))
+        .await
+        .map_err(|e| format!("Unable to open capture: {e}"))??;
+
+    state.player.open(app.clone(), reader).await
+}
+
+/// Starts or resumes replay, starting over if the capture had finished.
+///
+/// # Errors
+/// - Returns an error if no capture is open
+#[tauri::command]
+pub async fn play_replay(state: State<'_, AppState>) -> Result<(), String> {
+    state.player.control(Control::Play).await
+}
+
+/// Pauses replay at the next record.
+///
+/// # Errors
+/// - Returns an error if no capture is open
+#[tauri::command]
+pub async fn pause_replay(state: State<'_, AppState>) -> Result<(), String> {
+    state.player.control(Control::Pause).await
+}
+
+/// Moves replay to `position` milliseconds into the capture.
+///
+/// # Errors
+/// - Returns an error if no capture is open
+#[tauri::command]
+pub async fn seek_replay(position: u64, state: State<'_, AppState>) -> Result<(), String> {
+    state.player.control(Control::Seek(position)).await
+}
+
+/// Changes the speed, looping and rewriting of the replay.
+///
+/// # Errors
+/// - Returns an error if the speed is outside 0.1x to 10x
+#[tauri::command]
+pub async fn set_replay_options(
+    app: AppHandle,
+    options: ReplayOptions,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    options.validate()?;
+    state.player.set_options(&app, options).await;
+    Ok(())
+}
+
+/// Limits replay to the records matching `filter`, or replays every record if it is blank.
+///
+/// # Errors
+/// - Returns an error if the filter is not a valid expression
+#[tauri::command]
+pub async fn set_replay_filter(
+    app: AppHandle,
+    filter: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let filter = Filter::parse_optional(filter.as_deref())?;
+    state.player.set_filter(&app, filter).await;
+    Ok(())
+}
+
+/// Stops replay and closes the capture.
+///
+/// # Errors
+/// - Never fails; async commands borrowing state must return a `Result`
+#[tauri::command]
+pub async fn close_replay(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    state.player.close(&app).await;
+    Ok(())
+}
+
+/// Returns the state of the replay.
+///
+/// # Errors
+/// - Never fails; async commands borrowing state must return a `Result`
+#[tauri::command]
+pub async fn get_replay_status(state: State<'_, AppState>) -> Result<ReplayStatus, String> {
+    Ok(state.player.status())
+}
@@ -1,304 +1,1247 @@
 use open_dis_rust::common::data_types::EntityId;
-use tauri::State;
+use tauri::{AppHandle, State};
 
-use std::io;
-use std::net::UdpSocket;
-use std::sync::{MutexGuard, RwLock};
+use std::sync::RwLock;
+use std::time::Duration;
 
 use anyhow::Result;
 
 use bytes::BytesMut;
-use open_dis_rust::common::enums::{ActionRequestActionID, PduType, Reason};
+use open_dis_rust::common::constants::{NO_ENTITY, PDU_HEADER_SIZE};
+use open_dis_rust::common::enums::{
+    AcknowledgeFlag, ActionRequestActionID, PduType, RequiredReliabilityService,
+};
 use open_dis_rust::common::{GenericHeader, Pdu, PduHeader};
 use open_dis_rust::simulation_management::{
-    AcknowledgePdu, ActionRequestPdu, ActionResponsePdu, StartResumePdu, StopFreezePdu,
+    AcknowledgePdu, ActionRequestPdu, ActionResponsePdu, CreateEntityPdu, DataPdu, DataQueryPdu,
+    RemoveEntityPdu, SetDataPdu, StartResumePdu, StopFreezePdu,
+};
+use open_dis_rust::simulation_management_with_reliability::{
+    ActionRequestReliablePdu, CreateEntityReliablePdu, DataQueryReliablePdu, DataReliablePdu,
+    RemoveEntityReliablePdu, SetDataReliablePdu, StartResumeReliablePdu, StopFreezeReliablePdu,
 };
+use serde::Serialize;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::time::{Instant, timeout_at};
 
-use crate::config::AppConfig;
-use crate::core::RequestIds;
+use crate::config::{AppConfig, ReliabilityService, Siman, SimanMode};
 use crate::core::app_state::AppState;
+use crate::core::roster::ACKNOWLEDGE_BODY_LEN;
+use crate::core::{
+    ActionOption, ApplicationAddress, CommandTarget, Datagram, DatumIds, DatumSet,
+    EntityIdentifier, ExerciseCommand, GLOBAL_COMMANDS, NetworkService, Notice, Outcome,
+    RequestIds, Schedule, SimulationStatus, StopFreezeOptions, action_catalogue, action_name,
+    advance, clock_time, is_siman, notify, originating_entity,
+};
 
-fn handle_res(socket: &UdpSocket) -> Result<(), String> {
-    // Wait for response
-    socket
-        .set_read_timeout(Some(std::time::Duration::from_secs(2)))
-        .map_err(|e| e.to_string())?;
+/// The decoded contents of a Data PDU received in reply to a Set Data or Data Query PDU.
+#[derive(Serialize, Debug, Clone)]
+pub struct DataReply {
+    pub originating_entity: EntityIdentifier,
+    pub request_id: u32,
+    pub datums: DatumSet,
+}
+
+/// The outcome of a global SIMAN command sent to a target.
+#[derive(Serialize, Debug, Clone)]
+pub struct Acknowledgement {
+    pub target: CommandTarget,
+    pub command: String,
+    pub request_id: u32,
+    /// The participants that replied, or `None` when the request was sent unacknowledged
+    pub participants: Option<Vec<ParticipantReply>>,
+}
 
-    let mut buf = [0u8; 1024];
-    let (len, _) = socket.recv_from(&mut buf).map_err(|e| e.to_string())?;
+/// A single participant's reply to a global SIMAN command.
+#[derive(Serialize, Debug, Clone)]
+pub struct ParticipantReply {
+    pub entity: EntityIdentifier,
+    pub outcome: Outcome,
+    /// The Acknowledge response flag or Action Response request status as received
+    pub status: String,
+    /// Datum records returned with an Action Response
+    pub datums: DatumSet,
+    /// Time from the first transmission until the reply arrived
+    pub elapsed_ms: u64,
+}
 
-    tracing::debug!("Received a response with length {len}");
+/// Everything needed to exchange SIMAN PDUs on behalf of a single command.
+struct Link<'a> {
+    app: AppHandle,
+    network: &'a NetworkService,
+    siman: Siman,
+    /// The configured simulation address Centurion sends from
+    originator: EntityIdentifier,
+}
 
-    let mut bytes = BytesMut::from(&buf[..len]);
-    let pdu_header = PduHeader::deserialize(&mut bytes);
+/// Entity IDs, request ID, status and datum counts preceding the datums of an Action
+/// Response, and the entity IDs, request ID, padding or reliability and datum counts of a
+/// Data PDU
+const ACTION_RESPONSE_BODY_LEN: usize = 28;
+const DATA_BODY_LEN: usize = 28;
 
-    if pdu_header.pdu_type != PduType::ActionResponse {
-        return Err("unexpected PDU type received in response".into());
+const fn reliability_service(siman: &Siman) -> RequiredReliabilityService {
+    match siman.required_reliability {
+        ReliabilityService::Acknowledged => RequiredReliabilityService::Acknowledged,
+        ReliabilityService::Unacknowledged => RequiredReliabilityService::Unacknowledged,
     }
+}
 
-    ActionResponsePdu::deserialize_without_header(&mut bytes, pdu_header)
-        .map_err(|e| format!("ActionResponsePdu deserialization error: {e}"))?;
+/// Identifies the replies that answer a request.
+#[derive(Clone, Copy)]
+struct Expected {
+    request_id: u32,
+    /// Replies from simulations outside the target are ignored
+    target: CommandTarget,
+    /// Stops collecting at the first matching reply instead of waiting out the window
+    first_only: bool,
+}
 
-    Ok(())
+/// An Action Response PDU together with the datum records it carries.
+struct ActionResponse {
+    pdu: ActionResponsePdu,
+    datums: DatumSet,
 }
 
-fn handle_ack(socket: &UdpSocket) -> Result<(), String> {
-    // Wait for response
-    socket
-        .set_read_timeout(Some(std::time::Duration::from_secs(2)))
-        .map_err(|e| e.to_string())?;
+/// The request ID and sender that correlate a reply with its request, and the outcome
+/// it reports.
+trait Reply {
+    fn request_id(&self) -> u32;
+    fn originator(&self) -> EntityIdentifier;
 
-    let mut buf = [0u8; 1024];
-    let (len, _) = socket.recv_from(&mut buf).map_err(|e| e.to_string())?;
+    /// Returns the typed outcome along with the name of the raw flag or status it came from.
+    fn outcome(&self) -> (Outcome, String);
 
-    let mut bytes = BytesMut::from(&buf[..len]);
-    let pdu_header = PduHeader::deserialize(&mut bytes);
+    fn datums(&self) -> DatumSet {
+        DatumSet::default()
+    }
+}
 
-    if pdu_header.pdu_type != PduType::Acknowledge {
-        return Err("unexpected PDU type received in response".into());
+impl Reply for AcknowledgePdu {
+    fn request_id(&self) -> u32 {
+        self.request_id
     }
 
-    AcknowledgePdu::deserialize_without_header(&mut bytes, pdu_header)
-        .map_err(|e| format!("AcknowledgePdu deserialization error: {e}"))?;
+    fn originator(&self) -> EntityIdentifier {
+        self.originating_entity_id.into()
+    }
 
-    Ok(())
+    fn outcome(&self) -> (Outcome, String) {
+        (
+            self.response_flag.into(),
+            format!("{:?}", self.response_flag),
+        )
+    }
 }
 
-fn initialize_cmd(
-    socket: UdpSocket,
-    mut ids: MutexGuard<'_, RequestIds>,
-    dest_addr: String,
-) -> Result<(), String> {
-    let mut bytes = BytesMut::new();
-    let mut pdu = ActionRequestPdu::new();
+impl Reply for ActionResponse {
+    fn request_id(&self) -> u32 {
+        self.pdu.request_id
+    }
 
-    let site_id: u16 = 1;
-    let app_id: u16 = 50;
-    let entity_id: u16 = 1;
+    fn originator(&self) -> EntityIdentifier {
+        self.pdu.originating_entity_id.into()
+    }
 
-    let centurion_id = EntityId::new(site_id, app_id, entity_id);
-    let receive_all: EntityId = EntityId::new(0xFFFF, 0xFFFF, 0xFFFF);
+    fn outcome(&self) -> (Outcome, String) {
+        (
+            self.pdu.request_status.into(),
+            format!("{:?}", self.pdu.request_status),
+        )
+    }
 
-    pdu.originating_entity_id = centurion_id;
-    pdu.receiving_entity_id = receive_all;
-    pdu.action_id = ActionRequestActionID::InitializeInternalParameters as u32;
+    fn datums(&self) -> DatumSet {
+        self.datums.clone()
+    }
+}
 
-    pdu.request_id = ids.action_request;
+impl Reply for DataReply {
+    fn request_id(&self) -> u32 {
+        self.request_id
+    }
 
-    ids.action_request += 1;
+    fn originator(&self) -> EntityIdentifier {
+        self.originating_entity
+    }
 
-    let _ = pdu
-        .serialize(&mut bytes)
-        .map_err(|_| io::ErrorKind::InvalidData);
+    // Data PDUs carry no status, so receiving one means the request was serviced
+    fn outcome(&self) -> (Outcome, String) {
+        (Outcome::Complied, "Data".into())
+    }
+}
 
-    socket
-        .send_to(&bytes, dest_addr)
-        .map_err(|e| e.to_string())?;
+/// A reply together with the time it took to arrive after the first transmission.
+struct Received<T> {
+    reply: T,
+    elapsed: Duration,
+}
 
-    handle_res(&socket)?;
+/// Sends a request and collects the replies of every participant within the response
+/// window, retransmitting according to the scenario's SIMAN settings while none arrive.
+///
+/// Replies are returned in arrival order, one per participant. Returns `None` when the
+/// request was sent as unacknowledged SIMAN-R and no reply is expected.
+async fn transact<T: Reply + Send>(
+    link: &Link<'_>,
+    request: &[u8],
+    expected: Expected,
+    parse: impl Fn(PduHeader, BytesMut) -> Result<T, String> + Send + Sync,
+) -> Result<Option<Vec<Received<T>>>, String> {
+    let siman = &link.siman;
+
+    let attempts = match (siman.mode, siman.required_reliability) {
+        (SimanMode::Standard, _) => 1,
+        (SimanMode::Reliable, ReliabilityService::Acknowledged) => siman.retries.saturating_add(1),
+        (SimanMode::Reliable, ReliabilityService::Unacknowledged) => {
+            link.network.send(request).await?;
+            return Ok(None);
+        }
+    };
+
+    // Subscribe before sending so that no reply can slip past
+    let mut traffic = link.network.subscribe();
+
+    let window = Duration::from_millis(siman.timeout_ms.max(1));
+    let started = Instant::now();
+    let mut replies: Vec<Received<T>> = Vec::new();
+
+    for attempt in 1..=attempts {
+        link.network.send(request).await?;
+
+        let deadline = Instant::now() + window;
+
+        while let Some((header, bytes)) = receive(link, &mut traffic, request, deadline).await? {
+            let reply = match parse(header, bytes) {
+                Ok(reply) => reply,
+                Err(e) => {
+                    tracing::trace!("Ignoring PDU while awaiting replies: {e}");
+                    continue;
+                }
+            };
+
+            let originator = reply.originator();
+
+            if reply.request_id() != expected.request_id || !expected.target.contains(originator) {
+                tracing::debug!(
+                    "Ignoring reply to request {} from {originator}",
+                    reply.request_id()
+                );
+                continue;
+            }
+
+            // Retransmissions may be answered more than once by the same participant
+            if replies.iter().any(|r| r.reply.originator() == originator) {
+                continue;
+            }
+
+            replies.push(Received {
+                reply,
+                elapsed: started.elapsed(),
+            });
+
+            if expected.first_only {
+                break;
+            }
+        }
 
+        if !replies.is_empty() {
+            return Ok(Some(replies));
+        }
+
+        tracing::warn!("No replies to attempt {attempt} of {attempts}");
+    }
+
+    Err(format!(
+        "no replies to request {} received from {}",
+        expected.request_id, expected.target
+    ))
+}
+
+/// Waits until `deadline` for the next PDU that is neither the looped-back request nor a
+/// SIMAN PDU sent by another manager from Centurion's own simulation address.
+///
+/// Returns `None` once the deadline passes.
+async fn receive(
+    link: &Link<'_>,
+    traffic: &mut broadcast::Receiver<Datagram>,
+    request: &[u8],
+    deadline: Instant,
+) -> Result<Option<(PduHeader, BytesMut)>, String> {
+    loop {
+        let datagram = match timeout_at(deadline, traffic.recv()).await {
+            Err(_) => return Ok(None),
+            Ok(Ok(datagram)) => datagram,
+            Ok(Err(RecvError::Lagged(missed))) => {
+                tracing::warn!("Missed {missed} datagrams while awaiting replies");
+                continue;
+            }
+            Ok(Err(RecvError::Closed)) => return Err("the network service has stopped".into()),
+        };
+
+        tracing::debug!("Received a response with length {}", datagram.data.len());
+
+        // Broadcast and multicast requests are delivered back to the receive socket
+        if datagram.data == request {
+            continue;
+        }
+
+        // Stray datagrams too short for a PDU header would panic the decoder
+        if datagram.data.len() < PDU_HEADER_SIZE {
+            tracing::trace!("Ignoring a {} byte datagram", datagram.data.len());
+            continue;
+        }
+
+        let mut bytes = BytesMut::from(&datagram.data[..]);
+        let pdu_header = PduHeader::deserialize(&mut bytes);
+
+        if is_siman(pdu_header.protocol_family)
+            && originating_entity(&bytes) == Some(link.originator)
+        {
+            // Reported by the address conflict monitor
+            continue;
+        }
+
+        return Ok(Some((pdu_header, bytes)));
+    }
+}
+
+/// Refuses a reply too short for the fixed part of its body, which the decoders would
+/// otherwise panic on.
+fn ensure_body(body: &[u8], len: usize, name: &str) -> Result<(), String> {
+    if body.len() < len {
+        return Err(format!(
+            "{name} PDU body is {} bytes, shorter than the {len} bytes expected",
+            body.len()
+        ));
+    }
     Ok(())
 }
 
-fn startup_cmd(
-    socket: UdpSocket,
-    mut ids: MutexGuard<'_, RequestIds>,
-    dest_addr: String,
-) -> Result<(), String> {
-    let mut bytes = BytesMut::new();
-    let mut pdu = StartResumePdu::new();
+fn handle_res(pdu_header: PduHeader, mut bytes: BytesMut) -> Result<ActionResponse, String> {
+    // Action Response-R shares the body layout of the plain Action Response PDU
+    if !matches!(
+        pdu_header.pdu_type,
+        PduType::ActionResponse | PduType::ActionResponseReliable
+    ) {
+        return Err("unexpected PDU type received in response".into());
+    }
 
-    let site_id: u16 = 1;
-    let app_id: u16 = 50;
-    let entity_id: u16 = 1;
+    ensure_body(&bytes, ACTION_RESPONSE_BODY_LEN, "Action Response")?;
+    let pdu = ActionResponsePdu::deserialize_without_header(&mut bytes, pdu_header)
+        .map_err(|e| format!("ActionResponsePdu deserialization error: {e}"))?;
 
-    let centurion_id = EntityId::new(site_id, app_id, entity_id);
-    let receive_all: EntityId = EntityId::new(0xFFFF, 0xFFFF, 0xFFFF);
+    let datums = DatumSet::read_from(
+        &mut bytes,
+        pdu.number_of_fixed_datum_records,
+        pdu.number_of_variable_datum_records,
+    )?;
 
-    pdu.originating_entity_id = centurion_id;
-    pdu.receiving_entity_id = receive_all;
+    Ok(ActionResponse { pdu, datums })
+}
 
-    pdu.request_id = ids.start_resume;
+fn handle_ack(pdu_header: PduHeader, mut bytes: BytesMut) -> Result<AcknowledgePdu, String> {
+    // Acknowledge-R shares the body layout of the plain Acknowledge PDU
+    if !matches!(
+        pdu_header.pdu_type,
+        PduType::Acknowledge | PduType::AcknowledgeReliable
+    ) {
+        return Err("unexpected PDU type received in response".into());
+    }
 
-    ids.start_resume += 1;
+    ensure_body(&bytes, ACKNOWLEDGE_BODY_LEN, "Acknowledge")?;
+    AcknowledgePdu::deserialize_without_header(&mut bytes, pdu_header)
+        .map_err(|e| format!("AcknowledgePdu deserialization error: {e}"))
+}
 
-    let _ = pdu
-        .serialize(&mut bytes)
-        .map_err(|_| io::ErrorKind::InvalidData);
+/// Parses Acknowledge PDUs that answer the given kind of request.
+fn handle_ack_for(
+    flag: AcknowledgeFlag,
+) -> impl Fn(PduHeader, BytesMut) -> Result<AcknowledgePdu, String> {
+    move |pdu_header, bytes| {
+        let ack = handle_ack(pdu_header, bytes)?;
+        if ack.acknowledge_flag != flag {
+            return Err(format!(
+                "unexpected acknowledge flag received in response: {:?}",
+                ack.acknowledge_flag
+            ));
+        }
+        Ok(ack)
+    }
+}
 
-    socket
-        .send_to(&bytes, dest_addr)
-        .map_err(|e| e.to_string())?;
+fn handle_data(pdu_header: PduHeader, mut bytes: BytesMut) -> Result<DataReply, String> {
+    // Data-R shares the fixed body length of the plain Data PDU
+    if matches!(pdu_header.pdu_type, PduType::Data | PduType::DataReliable) {
+        ensure_body(&bytes, DATA_BODY_LEN, "Data")?;
+    }
 
-    handle_ack(&socket)?;
+    let (originating_entity_id, request_id, fixed, variable) = match pdu_header.pdu_type {
+        PduType::Data => {
+            let pdu = DataPdu::deserialize_without_header(&mut bytes, pdu_header)
+                .map_err(|e| format!("DataPdu deserialization error: {e}"))?;
+            (
+                pdu.originating_entity_id,
+                pdu.request_id,
+                pdu.number_of_fixed_datum_records,
+                pdu.number_of_variable_datum_records,
+            )
+        }
+        PduType::DataReliable => {
+            let pdu = DataReliablePdu::deserialize_without_header(&mut bytes, pdu_header)
+                .map_err(|e| format!("DataReliablePdu deserialization error: {e}"))?;
+            (
+                pdu.originating_entity_id,
+                pdu.request_id,
+                pdu.number_of_fixed_datum_records,
+                pdu.number_of_variable_datum_records,
+            )
+        }
+        _ => return Err("unexpected PDU type received in response".into()),
+    };
 
-    Ok(())
+    let datums = DatumSet::read_from(&mut bytes, fixed, variable)?;
+
+    Ok(DataReply {
+        originating_entity: originating_entity_id.into(),
+        request_id,
+        datums,
+    })
 }
 
-fn terminate_cmd(
-    socket: UdpSocket,
-    mut ids: MutexGuard<'_, RequestIds>,
-    dest_addr: String,
-) -> Result<(), String> {
+async fn action_request_cmd(
+    link: &Link<'_>,
+    request_id: u32,
+    target: CommandTarget,
+    action_id: u32,
+    datums: &DatumSet,
+) -> Result<Option<Vec<ParticipantReply>>, String> {
     let mut bytes = BytesMut::new();
-    let mut pdu = StopFreezePdu::new();
 
-    let site_id: u16 = 1;
-    let app_id: u16 = 50;
-    let entity_id: u16 = 1;
+    let centurion_id: EntityId = link.originator.into();
+    let receiving_id = target.receiving_id();
+
+    let (fixed, variable) = datums.counts()?;
+
+    match link.siman.mode {
+        SimanMode::Standard => {
+            let mut pdu = ActionRequestPdu::new();
+            pdu.originating_entity_id = centurion_id;
+            pdu.receiving_entity_id = receiving_id;
+            pdu.action_id = action_id;
+            pdu.request_id = request_id;
+            pdu.number_of_fixed_datum_records = fixed;
+            pdu.number_of_variable_datum_records = variable;
+            pdu.serialize(&mut bytes)
+        }
+        SimanMode::Reliable => {
+            let mut pdu = ActionRequestReliablePdu::new();
+            pdu.originating_entity_id = centurion_id;
+            pdu.receiving_entity_id = receiving_id;
+            pdu.required_reliability_service = reliability_service(&link.siman);
+            pdu.action_id = action_id;
+            pdu.request_id = request_id;
+            pdu.number_of_fixed_datum_records = fixed;
+            pdu.number_of_variable_datum_records = variable;
+            pdu.serialize(&mut bytes)
+        }
+    }
+    .map_err(|e| format!("Action Request serialization error: {e}"))?;
 
-    let centurion_id = EntityId::new(site_id, app_id, entity_id);
-    let receive_all: EntityId = EntityId::new(0xFFFF, 0xFFFF, 0xFFFF);
+    datums.append_to(&mut bytes)?;
 
-    pdu.originating_entity_id = centurion_id;
-    pdu.receiving_entity_id = receive_all;
+    let expected = Expected {
+        request_id,
+        target,
+        first_only: false,
+    };
 
-    pdu.reason = Reason::Termination;
+    let responses = transact(link, &bytes, expected, handle_res).await?;
 
-    pdu.request_id = ids.stop_freeze;
+    Ok(responses.as_deref().map(participants))
+}
 
-    ids.stop_freeze += 1;
+async fn startup_cmd(
+    link: &Link<'_>,
+    request_id: u32,
+    target: CommandTarget,
+    (real_world_ms, simulation_ms): (u64, u64),
+) -> Result<Option<Vec<ParticipantReply>>, String> {
+    let mut bytes = BytesMut::new();
+
+    let centurion_id: EntityId = link.originator.into();
+    let receiving_id = target.receiving_id();
+
+    match link.siman.mode {
+        SimanMode::Standard => {
+            let mut pdu = StartResumePdu::new();
+            pdu.originating_entity_id = centurion_id;
+            pdu.receiving_entity_id = receiving_id;
+            pdu.real_world_time = clock_time(real_world_ms);
+            pdu.simulation_time = clock_time(simulation_ms);
+            pdu.request_id = request_id;
+            pdu.serialize(&mut bytes)
+        }
+        SimanMode::Reliable => {
+            let mut pdu = StartResumeReliablePdu::new();
+            pdu.originating_entity_id = centurion_id;
+            pdu.receiving_entity_id = receiving_id;
+            pdu.real_world_time = clock_time(real_world_ms);
+            pdu.simulation_time = clock_time(simulation_ms);
+            pdu.required_reliability_service = reliability_service(&link.siman);
+            pdu.request_id = request_id;
+            pdu.serialize(&mut bytes)
+        }
+    }
+    .map_err(|e| format!("Start/Resume serialization error: {e}"))?;
+
+    let expected = Expected {
+        request_id,
+        target,
+        first_only: false,
+    };
+    let acks = transact(
+        link,
+        &bytes,
+        expected,
+        handle_ack_for(AcknowledgeFlag::StartResume),
+    )
+    .await?;
+
+    Ok(acks.as_deref().map(participants))
+}
 
-    let _ = pdu
-        .serialize(&mut bytes)
-        .map_err(|_| io::ErrorKind::InvalidData);
+async fn stop_freeze_cmd(
+    link: &Link<'_>,
+    request_id: u32,
+    target: CommandTarget,
+    options: StopFreezeOptions,
+    real_world_ms: u64,
+) -> Result<Option<Vec<ParticipantReply>>, String> {
+    let mut bytes = BytesMut::new();
 
-    socket
-        .send_to(&bytes, dest_addr)
-        .map_err(|e| e.to_string())?;
+    let centurion_id: EntityId = link.originator.into();
+    let receiving_id = target.receiving_id();
+
+    match link.siman.mode {
+        SimanMode::Standard => {
+            let mut pdu = StopFreezePdu::new();
+            pdu.originating_entity_id = centurion_id;
+            pdu.receiving_entity_id = receiving_id;
+            pdu.real_world_time = clock_time(real_world_ms);
+            pdu.reason = options.reason.into();
+            pdu.frozen_behavior = options.frozen_behavior();
+            pdu.request_id = request_id;
+            pdu.serialize(&mut bytes)
+        }
+        SimanMode::Reliable => {
+            let mut pdu = StopFreezeReliablePdu::new();
+            pdu.originating_entity_id = centurion_id;
+            pdu.receiving_entity_id = receiving_id;
+            pdu.real_world_time = clock_time(real_world_ms);
+            pdu.reason = options.reason.into();
+            pdu.frozen_behavior = options.frozen_behavior();
+            pdu.required_reliability_service = reliability_service(&link.siman) as u8;
+            pdu.request_id = request_id;
+            pdu.serialize(&mut bytes)
+        }
+    }
+    .map_err(|e| format!("Stop/Freeze serialization error: {e}"))?;
+
+    let expected = Expected {
+        request_id,
+        target,
+        first_only: false,
+    };
+    let acks = transact(
+        link,
+        &bytes,
+        expected,
+        handle_ack_for(AcknowledgeFlag::StopFreeze),
+    )
+    .await?;
+
+    Ok(acks.as_deref().map(participants))
+}
 
-    handle_ack(&socket)?;
+/// Summarises the replies to a global SIMAN command per participant.
+fn participants<T: Reply>(replies: &[Received<T>]) -> Vec<ParticipantReply> {
+    replies
+        .iter()
+        .map(|received| {
+            let (outcome, status) = received.reply.outcome();
+            ParticipantReply {
+                entity: received.reply.originator(),
+                outcome,
+                status,
+                datums: received.reply.datums(),
+                elapsed_ms: u64::try_from(received.elapsed.as_millis()).unwrap_or(u64::MAX),
+            }
+        })
+        .collect()
+}
 
-    Ok(())
+/// Returns the only expected reply, failing if the participant refused the request.
+fn first_reply<T: Reply>(replies: Option<Vec<Received<T>>>) -> Result<Option<T>, String> {
+    let Some(reply) = replies
+        .and_then(|replies| replies.into_iter().next())
+        .map(|received| received.reply)
+    else {
+        return Ok(None);
+    };
+
+    match reply.outcome() {
+        (Outcome::Refused, status) => Err(format!(
+            "{} refused the request: {status}",
+            reply.originator()
+        )),
+        (Outcome::Complied, _) => Ok(Some(reply)),
+        (outcome, status) => {
+            tracing::warn!("{} answered with {outcome}: {status}", reply.originator());
+            Ok(Some(reply))
+        }
+    }
 }
 
-fn standby_cmd(
-    socket: UdpSocket,
-    mut ids: MutexGuard<'_, RequestIds>,
-    dest_addr: String,
-) -> Result<(), String> {
+async fn create_entity_cmd(
+    link: &Link<'_>,
+    request_id: u32,
+    application: ApplicationAddress,
+) -> Result<Option<EntityIdentifier>, String> {
+    let mut bytes = BytesMut::new();
+
+    let centurion_id: EntityId = link.originator.into();
+
+    // The receiving entity is the simulation application that will own the new entity
+    let receiving_app = EntityId::new(application.site_id, application.application_id, NO_ENTITY);
+
+    match link.siman.mode {
+        SimanMode::Standard => {
+            let mut pdu = CreateEntityPdu::new();
+            pdu.originating_entity_id = centurion_id;
+            pdu.receiving_entity_id = receiving_app;
+            pdu.request_id = request_id;
+            pdu.serialize(&mut bytes)
+        }
+        SimanMode::Reliable => {
+            let mut pdu = CreateEntityReliablePdu::new();
+            pdu.originating_entity_id = centurion_id;
+            pdu.receiving_entity_id = receiving_app;
+            pdu.required_reliability_service = reliability_service(&link.siman);
+            pdu.request_id = request_id;
+            pdu.serialize(&mut bytes)
+        }
+    }
+    .map_err(|e| format!("Create Entity serialization error: {e}"))?;
+
+    let expected = Expected {
+        request_id,
+        target: CommandTarget::Application(application),
+        first_only: true,
+    };
+    let acks = transact(
+        link,
+        &bytes,
+        expected,
+        handle_ack_for(AcknowledgeFlag::CreateEntity),
+    )
+    .await?;
+
+    // The acknowledgement is sent on behalf of the newly created entity
+    Ok(first_reply(acks)?.map(|ack| ack.originator()))
+}
+
+async fn remove_entity_cmd(
+    link: &Link<'_>,
+    request_id: u32,
+    entity: EntityIdentifier,
+) -> Result<Option<EntityIdentifier>, String> {
     let mut bytes = BytesMut::new();
-    let mut pdu = StopFreezePdu::new();
 
-    let site_id: u16 = 1;
-    let app_id: u16 = 50;
-    let entity_id: u16 = 1;
+    let centurion_id: EntityId = link.originator.into();
 
-    let centurion_id = EntityId::new(site_id, app_id, entity_id);
-    let receive_all: EntityId = EntityId::new(0xFFFF, 0xFFFF, 0xFFFF);
+    match link.siman.mode {
+        SimanMode::Standard => {
+            let mut pdu = RemoveEntityPdu::new();
+            pdu.originating_entity_id = centurion_id;
+            pdu.receiving_entity_id = entity.into();
+            pdu.request_id = request_id;
+            pdu.serialize(&mut bytes)
+        }
+        SimanMode::Reliable => {
+            let mut pdu = RemoveEntityReliablePdu::new();
+            pdu.originating_entity_id = centurion_id;
+            pdu.receiving_entity_id = entity.into();
+            pdu.required_reliability_service = reliability_service(&link.siman);
+            pdu.request_id = request_id;
+            pdu.serialize(&mut bytes)
+        }
+    }
+    .map_err(|e| format!("Remove Entity serialization error: {e}"))?;
+
+    let expected = Expected {
+        request_id,
+        target: CommandTarget::Application(entity.application()),
+        first_only: true,
+    };
+    let acks = transact(
+        link,
+        &bytes,
+        expected,
+        handle_ack_for(AcknowledgeFlag::RemoveEntity),
+    )
+    .await?;
+
+    Ok(first_reply(acks)?.map(|ack| ack.originator()))
+}
 
-    pdu.originating_entity_id = centurion_id;
-    pdu.receiving_entity_id = receive_all;
-    pdu.reason = Reason::Recess;
+async fn set_data_cmd(
+    link: &Link<'_>,
+    request_id: u32,
+    entity: EntityIdentifier,
+    datums: &DatumSet,
+) -> Result<Option<DataReply>, String> {
+    let mut bytes = BytesMut::new();
 
-    pdu.request_id = ids.stop_freeze;
+    let centurion_id: EntityId = link.originator.into();
 
-    ids.stop_freeze += 1;
+    let (fixed, variable) = datums.counts()?;
 
-    let _ = pdu
-        .serialize(&mut bytes)
-        .map_err(|_| io::ErrorKind::InvalidData);
+    match link.siman.mode {
+        SimanMode::Standard => {
+            let mut pdu = SetDataPdu::new();
+            pdu.originating_entity_id = centurion_id;
+            pdu.receiving_entity_id = entity.into();
+            pdu.request_id = request_id;
+            pdu.number_of_fixed_datum_records = fixed;
+            pdu.number_of_variable_datum_records = variable;
+            pdu.serialize(&mut bytes)
+        }
+        SimanMode::Reliable => {
+            let mut pdu = SetDataReliablePdu::new();
+            pdu.originating_entity_id = centurion_id;
+            pdu.receiving_entity_id = entity.into();
+            pdu.required_reliability_service = reliability_service(&link.siman);
+            pdu.request_id = request_id;
+            pdu.number_of_fixed_datum_records = fixed;
+            pdu.number_of_variable_datum_records = variable;
+            pdu.serialize(&mut bytes)
+        }
+    }
+    .map_err(|e| format!("Set Data serialization error: {e}"))?;
 
-    socket
-        .send_to(&bytes, dest_addr)
-        .map_err(|e| e.to_string())?;
+    datums.append_to(&mut bytes)?;
 
-    handle_ack(&socket)?;
+    // The receiver answers a Set Data PDU with a Data PDU holding the values it applied
+    let expected = Expected {
+        request_id,
+        target: CommandTarget::Application(entity.application()),
+        first_only: true,
+    };
 
-    Ok(())
+    first_reply(transact(link, &bytes, expected, handle_data).await?)
 }
 
-fn reset_cmd(
-    socket: UdpSocket,
-    mut ids: MutexGuard<'_, RequestIds>,
-    dest_addr: String,
-) -> Result<(), String> {
+async fn query_data_cmd(
+    link: &Link<'_>,
+    request_id: u32,
+    entity: EntityIdentifier,
+    datums: &DatumIds,
+) -> Result<Option<DataReply>, String> {
     let mut bytes = BytesMut::new();
-    let mut pdu = StopFreezePdu::new();
 
-    let site_id: u16 = 1;
-    let app_id: u16 = 50;
-    let entity_id: u16 = 1;
+    let centurion_id: EntityId = link.originator.into();
+
+    let (fixed, variable) = datums.counts()?;
+
+    // A zero time interval requests a single Data PDU in reply
+    match link.siman.mode {
+        SimanMode::Standard => {
+            let mut pdu = DataQueryPdu::new();
+            pdu.originating_entity_id = centurion_id;
+            pdu.receiving_entity_id = entity.into();
+            pdu.request_id = request_id;
+            pdu.time_interval = 0;
+            pdu.number_of_fixed_datum_records = fixed;
+            pdu.number_of_variable_datum_records = variable;
+            pdu.serialize(&mut bytes)
+        }
+        SimanMode::Reliable => {
+            let mut pdu = DataQueryReliablePdu::new();
+            pdu.originating_entity_id = centurion_id;
+            pdu.receiving_entity_id = entity.into();
+            pdu.required_reliability_service = reliability_service(&link.siman);
+            pdu.request_id = request_id;
+            pdu.time_interval = 0;
+            pdu.number_of_fixed_datum_records = fixed;
+            pdu.number_of_variable_datum_records = variable;
+            pdu.serialize(&mut bytes)
+        }
+    }
+    .map_err(|e| format!("Data Query serialization error: {e}"))?;
 
-    let centurion_id = EntityId::new(site_id, app_id, entity_id);
-    let receive_all: EntityId = EntityId::new(0xFFFF, 0xFFFF, 0xFFFF);
+    datums.append_to(&mut bytes)?;
 
-    pdu.originating_entity_id = centurion_id;
-    pdu.receiving_entity_id = receive_all;
+    let expected = Expected {
+        request_id,
+        target: CommandTarget::Application(entity.application()),
+        first_only: true,
+    };
 
-    pdu.reason = Reason::StopForRestart;
+    first_reply(transact(link, &bytes, expected, handle_data).await?)
+}
 
-    pdu.request_id = ids.stop_freeze;
+/// Validates the configured simulation address and prepares to exchange SIMAN PDUs over
+/// the shared network service.
+fn open_link<'a>(
+    config: &AppConfig,
+    app: AppHandle,
+    network: &'a NetworkService,
+) -> Result<Link<'a>, String> {
+    let originator = config.simulation_address.originating_id()?;
+    tracing::trace!("originator={originator}");
+
+    Ok(Link {
+        app,
+        network,
+        siman: config.scenario_config.siman.clone(),
+        originator,
+    })
+}
 
-    ids.stop_freeze += 1;
+/// The global SIMAN commands offered by the dashboard's controls.
+#[derive(Clone, Copy)]
+enum GlobalCommand {
+    Initialize,
+    Startup,
+    StopFreeze(StopFreezeOptions),
+}
 
-    let _ = pdu
-        .serialize(&mut bytes)
-        .map_err(|_| io::ErrorKind::InvalidData);
+impl GlobalCommand {
+    /// Maps a frontend command name to the PDU it sends. `stop_freeze` carries
+    /// operator-chosen options; the shortcut commands halt everything with a fixed reason.
+    fn parse(command: &str, freeze: Option<StopFreezeOptions>) -> Option<Self> {
+        if command == "stop_freeze" {
+            return freeze.map(Self::StopFreeze);
+        }
 
-    socket
-        .send_to(&bytes, dest_addr)
-        .map_err(|e| e.to_string())?;
+        GLOBAL_COMMANDS
+            .iter()
+            .find(|(name, _)| *name == command)
+            .map(|(_, exercise_command)| match *exercise_command {
+                ExerciseCommand::Initialize => Self::Initialize,
+                ExerciseCommand::StartResume => Self::Startup,
+                ExerciseCommand::StopFreeze(reason) => {
+                    Self::StopFreeze(StopFreezeOptions::halt(reason))
+                }
+            })
+    }
 
-    handle_ack(&socket)?;
+    /// What this command asks of the exercise.
+    const fn exercise_command(self) -> ExerciseCommand {
+        match self {
+            Self::Initialize => ExerciseCommand::Initialize,
+            Self::Startup => ExerciseCommand::StartResume,
+            Self::StopFreeze(options) => ExerciseCommand::StopFreeze(options.reason),
+        }
+    }
 
-    Ok(())
+    /// Allocates the next request ID from the counter of the PDU this command sends.
+    const fn next_request_id(self, ids: &mut RequestIds) -> u32 {
+        let counter = match self {
+            Self::Initialize => &mut ids.action_request,
+            Self::Startup => &mut ids.start_resume,
+            Self::StopFreeze(_) => &mut ids.stop_freeze,
+        };
+        let id = *counter;
+        *counter += 1;
+        id
+    }
 }
 
+/// Sends one of the global SIMAN commands to the targeted simulations and reports every
+/// participant that acknowledged it within the response window.
+///
+/// Start/Resume and Stop/Freeze take effect at the scheduled instants, or after the
+/// scenario's lead time when no schedule is given. The `stop_freeze` command sends the
+/// reason and frozen behavior given in `freeze`.
+///
+/// Commands to every simulation advance the exercise state once every participant that
+/// replied has complied, or once they are sent when SIMAN-R is configured not to
+/// acknowledge them.
+///
+/// # Errors
+/// - Returns an error if the command cannot be sent from the current exercise state, the
+///   network service is not running, the schedule lies in the past or no participant in the
+///   target replies
 #[tauri::command]
 pub async fn send_siman_pdu(
+    app: AppHandle,
     state: State<'_, AppState>,
     config: State<'_, RwLock<AppConfig>>,
     command: String,
-) -> Result<(), String> {
-    let config = config.read().unwrap();
-
-    // Modifying UDP socket settings using values from scenario configuration file
-    let enable_broadcast = config.scenario_config.network.enable_broadcast.clone();
-    tracing::trace!("enable_broadcast={enable_broadcast}");
-    let multicast_ttl = config.scenario_config.network.multicast_ttl.clone();
-    tracing::trace!("multicast_ttl={multicast_ttl}");
-    let interface_ip = config.scenario_config.network.interface_ip.clone();
-    tracing::trace!("interface_ip={interface_ip}");
-    let interface_port = config.scenario_config.network.interface_port.clone();
-    tracing::trace!("interface_port={interface_port}");
-
-    let interface_addr = format!("{interface_ip}:{interface_port}");
-
-    let socket = UdpSocket::bind(interface_addr).map_err(|e| e.to_string())?;
-    socket
-        .set_broadcast(enable_broadcast)
-        .unwrap_or_else(|_| tracing::error!("unable to configure UDP broadcast"));
-    socket
-        .set_multicast_ttl_v4(multicast_ttl)
-        .unwrap_or_else(|_| tracing::error!("unable to configure multicast TTL"));
-
-    let ip = config.scenario_config.network.destination_ip.clone();
-    tracing::trace!("destination_ip={ip}");
-    let port = config.scenario_config.network.destination_port.clone();
-    tracing::trace!("destination_port={port}");
-
-    let dest_addr = format!("{ip}:{port}");
+    target: CommandTarget,
+    schedule: Option<Schedule>,
+    freeze: Option<StopFreezeOptions>,
+) -> Result<Acknowledgement, String> {
+    let Some(global_command) = GlobalCommand::parse(&command, freeze) else {
+        tracing::error!(
+            "Received an invalid command from the frontend: {}. This should be impossible... \
+            if you see this log, please report this as a bug on https://github.com/crhowell3/centurion",
+            command.as_str()
+        );
+        return Err("Invalid command".to_string());
+    };
+
+    let exercise_command = exercise_wide(target, global_command.exercise_command());
+    check_transition(&state, exercise_command, &command)?;
+
+    let link = {
+        let config = config.read().map_err(|_| "AppConfig lock poisoned")?;
+        open_link(&config, app, &state.network)?
+    };
 
     // This is for updating the global request ID count
-    let ids = state
-        .request_ids
-        .lock()
-        .map_err(|_| "AppData lock poisoned")?;
-
-    match command.as_str() {
-        "initialize" => initialize_cmd(socket, ids, dest_addr),
-        "startup" => startup_cmd(socket, ids, dest_addr),
-        "terminate" => terminate_cmd(socket, ids, dest_addr),
-        "standby" => standby_cmd(socket, ids, dest_addr),
-        "reset" => reset_cmd(socket, ids, dest_addr),
-        _ => {
-            tracing::error!(
-                "Received an invalid command from the frontend: {}. This should be impossible... \
-                if you see this log, please report this as a bug on https://github.com/crhowell3/centurion",
-                command.as_str()
-            );
-            Err("Invalid command".to_string())
+    let request_id = {
+        let mut ids = state
+            .request_ids
+            .lock()
+            .map_err(|_| "AppData lock poisoned")?;
+        global_command.next_request_id(&mut ids)
+    };
+
+    let (real_world_ms, simulation_ms) = schedule
+        .unwrap_or_default()
+        .resolve(Duration::from_millis(link.siman.lead_time_ms))?;
+
+    let participants = match global_command {
+        GlobalCommand::Initialize => {
+            action_request_cmd(
+                &link,
+                request_id,
+                target,
+                ActionRequestActionID::InitializeInternalParameters as u32,
+                &DatumSet::default(),
+            )
+            .await
         }
+        GlobalCommand::Startup => {
+            tracing::info!("Scheduling start at {real_world_ms} (simulation time {simulation_ms})");
+            startup_cmd(&link, request_id, target, (real_world_ms, simulation_ms)).await
+        }
+        GlobalCommand::StopFreeze(options) => {
+            tracing::info!("Scheduling stop/freeze ({options:?}) at {real_world_ms}");
+            stop_freeze_cmd(&link, request_id, target, options, real_world_ms).await
+        }
+    }?;
+
+    if let Some(participants) = &participants {
+        tracing::info!(
+            "{} participants acknowledged {command} request {request_id} sent to {target}",
+            participants.len()
+        );
     }
+
+    settle(
+        &link.app,
+        &state,
+        exercise_command,
+        &command,
+        request_id,
+        participants.as_deref(),
+    )?;
+
+    Ok(Acknowledgement {
+        target,
+        command,
+        request_id,
+        participants,
+    })
+}
+
+/// What a command sent to `target` asks of the exercise. Commands to part of the exercise,
+/// such as pausing one federate, leave the exercise state alone.
+fn exercise_wide(target: CommandTarget, command: ExerciseCommand) -> Option<ExerciseCommand> {
+    (target == CommandTarget::All).then_some(command)
+}
+
+/// Refuses an exercise-wide command that cannot be sent from the current exercise state.
+fn check_transition(
+    state: &AppState,
+    command: Option<ExerciseCommand>,
+    name: &str,
+) -> Result<(), String> {
+    let Some(command) = command else {
+        return Ok(());
+    };
+
+    let current = *state
+        .simulation_state
+        .lock()
+        .map_err(|_| "Simulation state lock poisoned")?;
+
+    if current.after(command).is_none() {
+        return Err(format!(
+            "{} cannot be sent while the exercise is {current}",
+            name.to_uppercase()
+        ));
+    }
+
+    Ok(())
+}
+
+/// Advances the exercise once every participant that replied to an exercise-wide command
+/// has complied, or once it is sent when SIMAN-R is configured not to acknowledge it.
+fn settle(
+    app: &AppHandle,
+    state: &AppState,
+    command: Option<ExerciseCommand>,
+    name: &str,
+    request_id: u32,
+    participants: Option<&[ParticipantReply]>,
+) -> Result<(), String> {
+    let Some(command) = command else {
+        return Ok(());
+    };
+
+    let complied = participants.is_none_or(|participants| {
+        participants
+            .iter()
+            .all(|participant| participant.outcome == Outcome::Complied)
+    });
+
+    if complied {
+        advance(app, &state.simulation_state, command)?;
+    } else {
+        notify(
+            app,
+            Notice::warning(format!(
+                "The exercise state is unchanged because not every participant complied with \
+                {} request {request_id}",
+                name.to_uppercase()
+            )),
+        );
+    }
+
+    Ok(())
+}
+
+/// Returns the exercise state and the global SIMAN commands that can be sent from it.
+///
+/// # Errors
+/// - Returns an error if the state lock is poisoned
+#[tauri::command]
+pub async fn get_simulation_state(state: State<'_, AppState>) -> Result<SimulationStatus, String> {
+    Ok(state
+        .simulation_state
+        .lock()
+        .map_err(|_| "Simulation state lock poisoned")?
+        .status())
+}
+
+/// Lists the Action Request action IDs known to Centurion.
+#[tauri::command]
+#[must_use]
+pub fn list_actions() -> Vec<ActionOption> {
+    action_catalogue()
+}
+
+/// Sends an Action Request with an arbitrary action ID and datum payload to the targeted
+/// simulations and reports every participant's Action Response.
+///
+/// Action IDs outside the catalogue are sent unchanged so simulator-specific actions can
+/// be driven without a code change. Initializing the whole exercise moves its state as the
+/// Global Controls command does.
+///
+/// # Errors
+/// - Returns an error if initialization cannot be sent from the current exercise state, the
+///   network service is not running, the datums cannot be encoded or no participant in the
+///   target replies
+#[tauri::command]
+pub async fn send_action_request(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    config: State<'_, RwLock<AppConfig>>,
+    action: u32,
+    target: CommandTarget,
+    datums: DatumSet,
+) -> Result<Acknowledgement, String> {
+    let command = action_name(action).map_or_else(|| format!("Action {action}"), String::from);
+
+    let exercise_command = (action == ActionRequestActionID::InitializeInternalParameters as u32)
+        .then_some(ExerciseCommand::Initialize)
+        .and_then(|initialize| exercise_wide(target, initialize));
+    check_transition(&state, exercise_command, &command)?;
+
+    let link = {
+        let config = config.read().map_err(|_| "AppConfig lock poisoned")?;
+        open_link(&config, app, &state.network)?
+    };
+
+    let request_id = {
+        let mut ids = state
+            .request_ids
+            .lock()
+            .map_err(|_| "AppData lock poisoned")?;
+        let id = ids.action_request;
+        ids.action_request += 1;
+        id
+    };
+
+    let participants = action_request_cmd(&link, request_id, target, action, &datums).await?;
+
+    if let Some(participants) = &participants {
+        tracing::info!(
+            "{} participants responded to {command} request {request_id} sent to {target}",
+            participants.len()
+        );
+    }
+
+    settle(
+        &link.app,
+        &state,
+        exercise_command,
+        &command,
+        request_id,
+        participants.as_deref(),
+    )?;
+
+    Ok(Acknowledgement {
+        target,
+        command,
+        request_id,
+        participants,
+    })
+}
+
+/// Asks a simulation application to create a new entity and returns the ID it assigned.
+///
+/// No ID is returned when the request is sent as unacknowledged SIMAN-R.
+///
+/// # Errors
+/// - Returns an error if the network service is not running or no Create Entity acknowledgement is received
+#[tauri::command]
+pub async fn create_entity(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    config: State<'_, RwLock<AppConfig>>,
+    application: ApplicationAddress,
+) -> Result<Option<EntityIdentifier>, String> {
+    let link = {
+        let config = config.read().map_err(|_| "AppConfig lock poisoned")?;
+        open_link(&config, app, &state.network)?
+    };
+
+    let request_id = {
+        let mut ids = state
+            .request_ids
+            .lock()
+            .map_err(|_| "AppData lock poisoned")?;
+        let id = ids.create_entity;
+        ids.create_entity += 1;
+        id
+    };
+
+    let entity = create_entity_cmd(&link, request_id, application).await?;
+
+    if let Some(entity) = entity {
+        tracing::info!("Simulation application {application} created entity {entity}");
+    }
+
+    Ok(entity)
+}
+
+/// Asks the owning simulation application to remove an entity from the exercise.
+///
+/// # Errors
+/// - Returns an error if the network service is not running or no Remove Entity acknowledgement is received
+#[tauri::command]
+pub async fn remove_entity(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    config: State<'_, RwLock<AppConfig>>,
+    entity: EntityIdentifier,
+) -> Result<Option<EntityIdentifier>, String> {
+    let link = {
+        let config = config.read().map_err(|_| "AppConfig lock poisoned")?;
+        open_link(&config, app, &state.network)?
+    };
+
+    let request_id = {
+        let mut ids = state
+            .request_ids
+            .lock()
+            .map_err(|_| "AppData lock poisoned")?;
+        let id = ids.remove_entity;
+        ids.remove_entity += 1;
+        id
+    };
+
+    let removed = remove_entity_cmd(&link, request_id, entity).await?;
+
+    if let Some(removed) = removed {
+        tracing::info!("Entity {removed} acknowledged removal");
+    }
+
+    Ok(removed)
+}
+
+/// Sets datum values on a receiving entity and returns the values it reports back.
+///
+/// # Errors
+/// - Returns an error if the network service is not running, the datums cannot be encoded or no Data
+///   PDU is received in reply
+#[tauri::command]
+pub async fn set_data(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    config: State<'_, RwLock<AppConfig>>,
+    entity: EntityIdentifier,
+    datums: DatumSet,
+) -> Result<Option<DataReply>, String> {
+    let link = {
+        let config = config.read().map_err(|_| "AppConfig lock poisoned")?;
+        open_link(&config, app, &state.network)?
+    };
+
+    let request_id = {
+        let mut ids = state
+            .request_ids
+            .lock()
+            .map_err(|_| "AppData lock poisoned")?;
+        let id = ids.set_data;
+        ids.set_data += 1;
+        id
+    };
+
+    set_data_cmd(&link, request_id, entity, &datums).await
+}
+
+/// Queries datum values from a receiving entity.
+///
+/// # Errors
+/// - Returns an error if the network service is not running or no Data PDU is received in reply
+#[tauri::command]
+pub async fn query_data(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    config: State<'_, RwLock<AppConfig>>,
+    entity: EntityIdentifier,
+    datums: DatumIds,
+) -> Result<Option<DataReply>, String> {
+    let link = {
+        let config = config.read().map_err(|_| "AppConfig lock poisoned")?;
+        open_link(&config, app, &state.network)?
+    };
+
+    let request_id = {
+        let mut ids = state
+            .request_ids
+            .lock()
+            .map_err(|_| "AppData lock poisoned")?;
+        let id = ids.data_query;
+        ids.data_query += 1;
+        id
+    };
+
+    query_data_cmd(&link, request_id, entity, &datums).await
 }
@@ -1,8 +1,15 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
 use serde::{Deserialize, Serialize};
-use tokio::sync::RwLock;
 
+use crate::core::EntityIdentifier;
 use crate::utils::LogLevel;
 
+/// Site and application numbers reserved by DIS for "none" and "all"
+const RESERVED_ADDRESS_VALUES: [u16; 2] = [0, 0xFFFF];
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SimulationAddress {
     pub site_id: u32,
@@ -10,6 +17,42 @@ pub struct SimulationAddress {
     pub entity_id: u32,
 }
 
+impl SimulationAddress {
+    /// Returns the entity identifier Centurion originates SIMAN PDUs from.
+    ///
+    /// # Errors
+    /// - Returns an error if a field does not fit the 16-bit DIS range, the site or application
+    ///   is a reserved value, or the entity is the all-entities wildcard
+    pub fn originating_id(&self) -> Result<EntityIdentifier, String> {
+        let field = |name: &str, value: u32| {
+            u16::try_from(value)
+                .map_err(|_| format!("simulation address {name} {value} is outside 0-65535"))
+        };
+
+        let site_id = field("site", self.site_id)?;
+        let application_id = field("application", self.application_id)?;
+        let entity_id = field("entity", self.entity_id)?;
+
+        if RESERVED_ADDRESS_VALUES.contains(&site_id) {
+            return Err(format!("simulation address site {site_id} is reserved"));
+        }
+        if RESERVED_ADDRESS_VALUES.contains(&application_id) {
+            return Err(format!(
+                "simulation address application {application_id} is reserved"
+            ));
+        }
+        if entity_id == 0xFFFF {
+            return Err("simulation address entity 65535 is reserved".into());
+        }
+
+        Ok(EntityIdentifier {
+            site_id,
+            application_id,
+            entity_id,
+        })
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
 #[serde(default)]
 pub struct AdvancedConfig {
@@ -17,14 +60,97 @@ pub struct AdvancedConfig {
     pub log_level: LogLevel,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+/// How entity locations are shown.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum CoordinateFormat {
+    /// DIS world coordinates in metres, with orientations as raw Euler angles
+    Geocentric,
+    /// WGS-84 latitude, longitude and altitude
+    #[default]
+    Geodetic,
+    Mgrs,
+}
+
+/// Per-user presentation preferences shared by the monitors, inspectors and exports.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(default)]
+pub struct DisplayConfig {
+    pub coordinates: CoordinateFormat,
+}
+
+/// Settings of the tactical map.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[serde(default)]
+pub struct MapConfig {
+    /// `GeoJSON` file drawn behind the map, if any
+    pub background: Option<PathBuf>,
+}
+
+/// Where traffic is recorded and when a recording moves on to a new capture file.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct RecordingConfig {
+    /// Directory capture files are written to, defaulting to `recordings` in the app
+    /// data directory
+    pub directory: Option<PathBuf>,
+    /// Size at which a new capture file is started, 0 for no limit
+    pub max_file_size_mb: u64,
+    /// Duration after which a new capture file is started, 0 for no limit
+    pub max_file_duration_min: u64,
+}
+
+impl RecordingConfig {
+    /// Whether a capture file `size` bytes long and open for `elapsed` should be closed
+    /// and a new one started.
+    #[must_use]
+    pub fn rollover_due(&self, size: u64, elapsed: Duration) -> bool {
+        let size_limit = self.max_file_size_mb.saturating_mul(1024 * 1024);
+        let duration_limit = Duration::from_secs(self.max_file_duration_min.saturating_mul(60));
+
+        (self.max_file_size_mb > 0 && size >= size_limit)
+            || (self.max_file_duration_min > 0 && elapsed >= duration_limit)
+    }
+}
+
+impl Default for RecordingConfig {
+    fn default() -> Self {
+        Self {
+            directory: None,
+            max_file_size_mb: 512,
+            max_file_duration_min: 60,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct Network {
+    /// Local address PDUs are received on
     pub interface_ip: String,
     pub interface_port: u16,
+    /// Local address PDUs are sent from, defaulting to `interface_ip`
+    #[serde(default)]
+    pub send_interface_ip: Option<String>,
     pub destination_ip: String,
     pub destination_port: u16,
     pub enable_broadcast: bool,
+    /// Time-to-live of IPv4 multicast datagrams, or the hop limit of IPv6 ones
     pub multicast_ttl: u32,
+    /// Multicast groups joined on the receive interface
+    #[serde(default)]
+    pub multicast_groups: Vec<String>,
+    /// Whether multicast PDUs sent by Centurion are delivered to listeners on this host
+    #[serde(default = "Network::default_multicast_loopback")]
+    pub multicast_loopback: bool,
+    /// Index of the interface IPv6 multicast is joined and sent on, 0 letting the OS choose
+    #[serde(default)]
+    pub multicast_interface_index: u32,
+}
+
+impl Network {
+    const fn default_multicast_loopback() -> bool {
+        true
+    }
 }
 
 impl Default for Network {
@@ -32,26 +158,201 @@ impl Default for Network {
         Self {
             interface_ip: "0.0.0.0".to_string(),
             interface_port: 3000,
+            send_interface_ip: None,
             destination_ip: "0.0.0.0".to_string(),
             destination_port: 3000,
             enable_broadcast: false,
             multicast_ttl: 42,
+            multicast_groups: Vec::new(),
+            multicast_loopback: true,
+            multicast_interface_index: 0,
         }
     }
 }
 
+/// Selects between the plain SIMAN PDUs and their SIMAN-R (reliable) counterparts.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SimanMode {
+    #[default]
+    Standard,
+    Reliable,
+}
+
+/// The required reliability service requested in SIMAN-R PDUs.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ReliabilityService {
+    #[default]
+    Acknowledged,
+    Unacknowledged,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct Siman {
+    pub mode: SimanMode,
+    pub required_reliability: ReliabilityService,
+    /// Number of retransmissions attempted in SIMAN-R mode before giving up
+    pub retries: u32,
+    /// Time to wait for a response to each transmission
+    pub timeout_ms: u64,
+    /// Delay between sending an unscheduled Start/Resume or Stop/Freeze and the instant
+    /// it takes effect
+    pub lead_time_ms: u64,
+}
+
+impl Default for Siman {
+    fn default() -> Self {
+        Self {
+            mode: SimanMode::Standard,
+            required_reliability: ReliabilityService::Acknowledged,
+            retries: 3,
+            timeout_ms: 2000,
+            lead_time_ms: 5000,
+        }
+    }
+}
+
+/// Tolerances beyond which an entity's dead-reckoned state is flagged once its next
+/// Entity State PDU arrives.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct DeadReckoning {
+    /// Distance between the extrapolated and reported locations, in metres
+    pub position_threshold_m: f64,
+    /// Angle between the extrapolated and reported orientations, in degrees
+    pub orientation_threshold_deg: f64,
+}
+
+impl Default for DeadReckoning {
+    fn default() -> Self {
+        Self {
+            position_threshold_m: 1.0,
+            orientation_threshold_deg: 3.0,
+        }
+    }
+}
+
+/// How long entities and applications may go without sending anything before they are
+/// considered gone.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct Timeouts {
+    /// Interval at which simulations are expected to resend Entity State PDUs
+    pub heartbeat_ms: u64,
+    /// Number of heartbeat intervals without an update after which an entity is stale
+    pub timeout_multiplier: f64,
+    /// Time a stale entity stays in the registry before it is removed
+    pub removal_delay_ms: u64,
+}
+
+impl Timeouts {
+    /// Time without an update after which an entity or application is considered silent.
+    #[must_use]
+    pub fn timeout_ms(&self) -> u64 {
+        // Heartbeats are seconds long, far inside the exact range of f64
+        #[allow(
+            clippy::cast_precision_loss,
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss
+        )]
+        let timeout = (self.heartbeat_ms as f64 * self.timeout_multiplier.max(0.0)).round() as u64;
+        timeout
+    }
+}
+
+impl Default for Timeouts {
+    /// The heartbeat and timeout multiplier recommended by IEEE 1278.1
+    fn default() -> Self {
+        Self {
+            heartbeat_ms: 5000,
+            timeout_multiplier: 2.4,
+            removal_delay_ms: 30_000,
+        }
+    }
+}
+
+/// Settings of the traffic statistics.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct Statistics {
+    /// Time constant of the moving average rates, in seconds
+    pub average_window_s: f64,
+    /// Average rate of PDUs per second above which an entity is flagged as chatty
+    pub chatty_entity_rate: f64,
+}
+
+impl Default for Statistics {
+    fn default() -> Self {
+        Self {
+            average_window_s: 60.0,
+            chatty_entity_rate: 20.0,
+        }
+    }
+}
+
+/// A filter expression the operator saved under a name.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SavedFilter {
+    pub name: String,
+    pub expression: String,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct ScenarioConfig {
     pub network: Network,
+    #[serde(default)]
+    pub siman: Siman,
+    #[serde(default)]
+    pub dead_reckoning: DeadReckoning,
+    #[serde(default)]
+    pub timeouts: Timeouts,
+    #[serde(default)]
+    pub statistics: Statistics,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct AppConfig {
     pub simulation_address: SimulationAddress,
     pub advanced: AdvancedConfig,
+    #[serde(default)]
+    pub display: DisplayConfig,
+    #[serde(default)]
+    pub map: MapConfig,
+    #[serde(default)]
+    pub recording: RecordingConfig,
+    /// The scenario file `scenario_config` was loaded from, if any
+    #[serde(default)]
+    pub scenario_file: Option<PathBuf>,
+    /// Filter expressions saved for the monitors, keyed by the scenario file they were saved
+    /// with. Filters saved before any scenario file was loaded are kept under an empty path.
+    #[serde(default)]
+    pub filters: BTreeMap<PathBuf, Vec<SavedFilter>>,
     pub scenario_config: ScenarioConfig,
 }
 
+impl AppConfig {
+    /// Returns the filters saved with the current scenario.
+    #[must_use]
+    pub fn saved_filters(&self) -> &[SavedFilter] {
+        self.filters
+            .get(
+                self.scenario_file
+                    .as_deref()
+                    .unwrap_or_else(|| Path::new("")),
+            )
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns the filters saved with the current scenario for editing.
+    pub fn saved_filters_mut(&mut self) -> &mut Vec<SavedFilter> {
+        self.filters
+            .entry(self.scenario_file.clone().unwrap_or_default())
+            .or_default()
+    }
+}
+
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
@@ -61,9 +362,12 @@ impl Default for AppConfig {
                 entity_id: 1,
             },
             advanced: AdvancedConfig::default(),
+            display: DisplayConfig::default(),
+            map: MapConfig::default(),
+            recording: RecordingConfig::default(),
+            scenario_file: None,
+            filters: BTreeMap::new(),
             scenario_config: ScenarioConfig::default(),
         }
     }
 }
-
-pub type SharedConfig = RwLock<AppConfig>;
@@ -1,4 +1,50 @@
+pub mod actions;
 pub mod app_state;
+pub mod capture;
+pub mod clock;
+pub mod conflict;
+pub mod coordinates;
+pub mod datum;
+pub mod dead_reckoning;
+pub mod entities;
+pub mod exercise;
+pub mod filter;
+pub mod identifiers;
+pub mod inspector;
+pub mod map;
+pub mod monitor;
+pub mod network;
+pub mod outcome;
+pub mod pcap;
+pub mod pdu_log;
+pub mod recorder;
+pub mod replay;
+pub mod roster;
+pub mod statistics;
+pub mod stop_freeze;
 pub mod theme;
 
+pub use actions::*;
 pub use app_state::*;
+pub use capture::*;
+pub use clock::*;
+pub use conflict::*;
+pub use coordinates::*;
+pub use datum::*;
+pub use dead_reckoning::*;
+pub use entities::*;
+pub use exercise::*;
+pub use filter::*;
+pub use identifiers::*;
+pub use inspector::*;
+pub use map::*;
+pub use monitor::*;
+pub use network::*;
+pub use outcome::*;
+pub use pcap::*;
+pub use pdu_log::*;
+pub use recorder::*;
+pub use replay::*;
+pub use roster::*;
+pub use statistics::*;
+pub use stop_freeze::*;
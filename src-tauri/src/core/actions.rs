@@ -0,0 +1,190 @@
+//! The Action Request action IDs offered to the operator.
+
+use open_dis_rust::common::enums::ActionRequestActionID;
+use serde::Serialize;
+
+/// An entry of the action catalogue that can cross the Tauri boundary.
+#[derive(Serialize, Debug, Clone, Copy)]
+pub struct ActionOption {
+    pub id: u32,
+    pub name: &'static str,
+}
+
+/// Every action ID defined by SISO-REF-010 [UID 71], labelled for display.
+const CATALOGUE: &[(ActionRequestActionID, &str)] = &[
+    (ActionRequestActionID::Other, "Other"),
+    (
+        ActionRequestActionID::LocalStorageOfTherRequestedInformation,
+        "Local Storage of the Requested Information",
+    ),
+    (
+        ActionRequestActionID::InformSMofEventRanOutOfAmmunition,
+        "Inform SM of Event \"Ran Out of Ammunition\"",
+    ),
+    (
+        ActionRequestActionID::InformSMofEventKilledInAction,
+        "Inform SM of Event \"Killed in Action\"",
+    ),
+    (
+        ActionRequestActionID::InformSMofEventDamage,
+        "Inform SM of Event \"Damage\"",
+    ),
+    (
+        ActionRequestActionID::InformSMofEventMobilityDisabled,
+        "Inform SM of Event \"Mobility Disabled\"",
+    ),
+    (
+        ActionRequestActionID::InformSMofEventFiredDisabled,
+        "Inform SM of Event \"Fire Disabled\"",
+    ),
+    (
+        ActionRequestActionID::InformSMofEventRanOutOfFuel,
+        "Inform SM of Event \"Ran Out of Fuel\"",
+    ),
+    (
+        ActionRequestActionID::RecallCheckpointData,
+        "Recall Checkpoint Data",
+    ),
+    (
+        ActionRequestActionID::RecallInitialParameters,
+        "Recall Initial Parameters",
+    ),
+    (
+        ActionRequestActionID::InitiateTetherLead,
+        "Initiate Tether-Lead",
+    ),
+    (
+        ActionRequestActionID::InitiateTetherFollow,
+        "Initiate Tether-Follow",
+    ),
+    (ActionRequestActionID::Unthether, "Untether"),
+    (
+        ActionRequestActionID::InitiateServiceStationResupply,
+        "Initiate Service Station Resupply",
+    ),
+    (
+        ActionRequestActionID::InitiateTailgateResupply,
+        "Initiate Tailgate Resupply",
+    ),
+    (
+        ActionRequestActionID::InitiateHitchLead,
+        "Initiate Hitch Lead",
+    ),
+    (
+        ActionRequestActionID::InitiateHitchFollow,
+        "Initiate Hitch Follow",
+    ),
+    (ActionRequestActionID::Unhitch, "Unhitch"),
+    (ActionRequestActionID::Mount, "Mount"),
+    (ActionRequestActionID::Dismount, "Dismount"),
+    (
+        ActionRequestActionID::StartDRC,
+        "Start DRC (Daily Readiness Check)",
+    ),
+    (ActionRequestActionID::StopDRC, "Stop DRC"),
+    (ActionRequestActionID::DataQuery, "Data Query"),
+    (ActionRequestActionID::StatusRequest, "Status Request"),
+    (
+        ActionRequestActionID::SendObjectStateData,
+        "Send Object State Data",
+    ),
+    (ActionRequestActionID::Reconstitute, "Reconstitute"),
+    (
+        ActionRequestActionID::LockSiteConfiguration,
+        "Lock Site Configuration",
+    ),
+    (
+        ActionRequestActionID::UnlockSiteConfiguration,
+        "Unlock Site Configuration",
+    ),
+    (
+        ActionRequestActionID::UpdateSiteConfiguration,
+        "Update Site Configuration",
+    ),
+    (
+        ActionRequestActionID::QuerySiteConfiguration,
+        "Query Site Configuration",
+    ),
+    (
+        ActionRequestActionID::TetheringInformation,
+        "Tethering Information",
+    ),
+    (ActionRequestActionID::MountIntent, "Mount Intent"),
+    (
+        ActionRequestActionID::AcceptSubscription,
+        "Accept Subscription",
+    ),
+    (ActionRequestActionID::Unsubscribe, "Unsubscribe"),
+    (ActionRequestActionID::TeleportEntity, "Teleport Entity"),
+    (
+        ActionRequestActionID::ChangeAggregateState,
+        "Change Aggregate State",
+    ),
+    (ActionRequestActionID::RequestStartPDU, "Request Start PDU"),
+    (
+        ActionRequestActionID::WakeupGetReadyForInitialization,
+        "Wakeup Get Ready for Initialization",
+    ),
+    (
+        ActionRequestActionID::InitializeInternalParameters,
+        "Initialize Internal Parameters",
+    ),
+    (ActionRequestActionID::SendPlanData, "Send Plan Data"),
+    (
+        ActionRequestActionID::SynchronizeInternalClocks,
+        "Synchronize Internal Clocks",
+    ),
+    (ActionRequestActionID::Run, "Run"),
+    (
+        ActionRequestActionID::SaveInternalParameters,
+        "Save Internal Parameters",
+    ),
+    (
+        ActionRequestActionID::SimulateMalfunction,
+        "Simulate Malfunction",
+    ),
+    (ActionRequestActionID::JoinExercise, "Join Exercise"),
+    (ActionRequestActionID::ResignExercise, "Resign Exercise"),
+    (ActionRequestActionID::TimeAdvance, "Time Advance"),
+    (
+        ActionRequestActionID::TACCSFLOSRequestType1,
+        "TACCSF LOS Request-Type 1",
+    ),
+    (
+        ActionRequestActionID::TACCSFLOSRequestType2,
+        "TACCSF LOS Request-Type 2",
+    ),
+    (
+        ActionRequestActionID::AirmountMountRequest,
+        "Airmount Mount Request",
+    ),
+    (
+        ActionRequestActionID::AirmountDismountRequest,
+        "Airmount Dismount Request",
+    ),
+    (
+        ActionRequestActionID::AirmountInformationRequest,
+        "Airmount Information Request",
+    ),
+];
+
+/// Returns the full action catalogue in ascending action ID order.
+#[must_use]
+pub fn action_catalogue() -> Vec<ActionOption> {
+    CATALOGUE
+        .iter()
+        .map(|&(id, name)| ActionOption {
+            id: id as u32,
+            name,
+        })
+        .collect()
+}
+
+/// Returns the display name of an action ID, or `None` for simulator-specific values.
+#[must_use]
+pub fn action_name(action_id: u32) -> Option<&'static str> {
+    CATALOGUE
+        .iter()
+        .find(|&&(id, _)| id as u32 == action_id)
+        .map(|&(_, name)| name)
+}
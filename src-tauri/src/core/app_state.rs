@@ -1,19 +1,20 @@
-use std::fmt;
-
 use std::sync::Mutex;
 
-#[derive(Debug)]
-pub enum SimulationState {
-    Stopped,
-    Standby,
-    Running,
-}
+use crate::config::ScenarioConfig;
+use crate::core::{
+    AddressConflicts, CaptureBrowser, EntityRegistry, NetworkService, PduLog, Player, Recorder,
+    Roster, SimulationState, TrafficStatistics,
+};
 
 #[derive(Debug)]
 pub struct RequestIds {
     pub start_resume: u32,
     pub stop_freeze: u32,
     pub action_request: u32,
+    pub create_entity: u32,
+    pub remove_entity: u32,
+    pub set_data: u32,
+    pub data_query: u32,
 }
 
 impl RequestIds {
@@ -22,17 +23,50 @@ impl RequestIds {
             start_resume: 0,
             stop_freeze: 0,
             action_request: 0,
+            create_entity: 0,
+            remove_entity: 0,
+            set_data: 0,
+            data_query: 0,
         }
     }
 }
 
-impl fmt::Display for SimulationState {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{self:?}")
-    }
-}
-
 pub struct AppState {
+    /// Advanced only as participants acknowledge the global SIMAN commands
     pub simulation_state: Mutex<SimulationState>,
     pub request_ids: Mutex<RequestIds>,
+    pub network: NetworkService,
+    pub roster: Mutex<Roster>,
+    pub conflicts: Mutex<AddressConflicts>,
+    pub entities: Mutex<EntityRegistry>,
+    pub traffic: Mutex<TrafficStatistics>,
+    pub recorder: Recorder,
+    pub player: Player,
+    pub pdu_log: Mutex<PduLog>,
+    /// The capture open in the inspector
+    pub browser: Mutex<Option<CaptureBrowser>>,
+}
+
+impl AppState {
+    /// Applies a loaded scenario's settings to the shared network service and monitors.
+    ///
+    /// # Errors
+    /// - Returns an error if the network service cannot be bound with the new settings
+    pub async fn apply_scenario(&self, scenario: &ScenarioConfig) -> Result<(), String> {
+        if let Ok(mut entities) = self.entities.lock() {
+            entities.set_thresholds(scenario.dead_reckoning);
+            entities.set_timeouts(scenario.timeouts);
+        }
+
+        if let Ok(mut roster) = self.roster.lock() {
+            roster.set_timeouts(scenario.timeouts);
+        }
+
+        if let Ok(mut traffic) = self.traffic.lock() {
+            traffic.set_settings(scenario.statistics);
+        }
+
+        // Rebinds the shared sockets only if the network settings changed
+        self.network.apply(&scenario.network).await
+    }
 }
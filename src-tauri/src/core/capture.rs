@@ -0,0 +1,525 @@
+//! The indexed capture file exercise traffic is recorded to and replayed from.
+//!
+//! A capture starts with the 8-byte magic `DISCAP\r\n`, a version and six reserved bytes.
+//! Each record then holds the receive time in Unix microseconds, the source address as an
+//! IPv6 (or IPv4-mapped) address and port, the datagram length and the datagram itself.
+//! Closing a capture appends an index of the time and offset of every record, followed by
+//! a trailer with the index offset, the record count and the magic `DISCAPIX`. Captures cut
+//! short without an index are indexed by scanning their records instead. All integers are
+//! big-endian, as in DIS.
+
+use std::fs;
+use std::io::{self, BufReader, Read, Seek, SeekFrom};
+use std::net::{IpAddr, Ipv6Addr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use bytes::{BufMut, Bytes, BytesMut};
+use open_dis_rust::common::constants::MAX_PDU_SIZE_OCTETS;
+use tokio::fs::File;
+use tokio::io::{AsyncWriteExt, BufWriter};
+
+use crate::core::Datagram;
+
+/// File extension of capture files
+pub const CAPTURE_EXTENSION: &str = "dcap";
+
+const MAGIC: &[u8; 8] = b"DISCAP\r\n";
+const INDEX_MAGIC: &[u8; 8] = b"DISCAPIX";
+const VERSION: u16 = 1;
+
+/// Magic, version and reserved bytes
+const HEADER_LEN: u64 = 16;
+
+/// Time, address, port and length preceding each datagram
+const RECORD_HEADER_LEN: usize = 8 + 16 + 2 + 4;
+
+/// Time and offset of each record in the index
+const INDEX_ENTRY_LEN: u64 = 16;
+
+/// Index offset, record count and magic
+const TRAILER_LEN: usize = 8 + 8 + 8;
+
+#[derive(Debug, Clone, Copy)]
+struct IndexEntry {
+    time_us: u64,
+    offset: u64,
+}
+
+/// Converts a system time to Unix microseconds, clamping times before the epoch to zero.
+fn unix_us(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map_or(0, |elapsed| {
+        u64::try_from(elapsed.as_micros()).unwrap_or(u64::MAX)
+    })
+}
+
+/// Writes datagrams to a new capture file.
+pub struct CaptureWriter {
+    file: BufWriter<File>,
+    path: PathBuf,
+    size: u64,
+    index: Vec<IndexEntry>,
+}
+
+impl CaptureWriter {
+    /// Creates the capture file at `path`, replacing any file already there.
+    ///
+    /// # Errors
+    /// - Returns an error if the file cannot be created or written
+    pub async fn create(path: &Path) -> Result<Self, String> {
+        let file = File::create(path)
+            .await
+            .map_err(|e| format!("Unable to create {}: {e}", path.display()))?;
+
+        let mut writer = Self {
+            file: BufWriter::new(file),
+            path: path.to_path_buf(),
+            size: 0,
+            index: Vec::new(),
+        };
+
+        let mut header = BytesMut::new();
+        header.put_slice(MAGIC);
+        header.put_u16(VERSION);
+        header.put_bytes(0, 6);
+        writer.put(&header).await?;
+
+        Ok(writer)
+    }
+
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Bytes written so far, excluding the index.
+    #[must_use]
+    pub const fn size(&self) -> u64 {
+        self.size
+    }
+
+    #[must_use]
+    pub const fn records(&self) -> usize {
+        self.index.len()
+    }
+
+    /// Appends a datagram with its receive time and source address.
+    ///
+    /// # Errors
+    /// - Returns an error if the file cannot be written
+    pub async fn write(&mut self, datagram: &Datagram) -> Result<(), String> {
+        let length = u32::try_from(datagram.data.len())
+            .map_err(|_| "Datagram too large to capture".to_string())?;
+        let address = match datagram.source.ip() {
+            IpAddr::V4(address) => address.to_ipv6_mapped(),
+            IpAddr::V6(address) => address,
+        };
+        let time_us = unix_us(datagram.received_at);
+
+        let mut header = BytesMut::with_capacity(RECORD_HEADER_LEN);
+        header.put_u64(time_us);
+        header.put_slice(&address.octets());
+        header.put_u16(datagram.source.port());
+        header.put_u32(length);
+
+        self.index.push(IndexEntry {
+            time_us,
+            offset: self.size,
+        });
+
+        self.put(&header).await?;
+        self.put(&datagram.data).await
+    }
+
+    /// Writes buffered records to disk, so that little is lost if Centurion stops abruptly.
+    ///
+    /// # Errors
+    /// - Returns an error if the file cannot be written
+    pub async fn flush(&mut self) -> Result<(), String> {
+        self.file
+            .flush()
+            .await
+            .map_err(|e| format!("Unable to write {}: {e}", self.path.display()))
+    }
+
+    /// Appends the index and trailer and flushes the file.
+    ///
+    /// # Errors
+    /// - Returns an error if the file cannot be written
+    pub async fn finish(mut self) -> Result<(), String> {
+        let index_offset = self.size;
+
+        let mut index = BytesMut::with_capacity(
+            self.index.len() * usize::try_from(INDEX_ENTRY_LEN).unwrap_or_default() + TRAILER_LEN,
+        );
+        for entry in &self.index {
+            index.put_u64(entry.time_us);
+            index.put_u64(entry.offset);
+        }
+        index.put_u64(index_offset);
+        index.put_u64(self.index.len() as u64);
+        index.put_slice(INDEX_MAGIC);
+
+        self.put(&index).await?;
+        self.flush().await
+    }
+
+    async fn put(&mut self, bytes: &[u8]) -> Result<(), String> {
+        self.file
+            .write_all(bytes)
+            .await
+            .map_err(|e| format!("Unable to write {}: {e}", self.path.display()))?;
+        self.size += bytes.len() as u64;
+
+        Ok(())
+    }
+}
+
+/// Reads the records of a capture file in any order.
+pub struct CaptureReader {
+    file: BufReader<fs::File>,
+    path: PathBuf,
+    index: Vec<IndexEntry>,
+}
+
+impl CaptureReader {
+    /// Opens a capture file, reading its index or rebuilding it if the capture was not
+    /// closed cleanly.
+    ///
+    /// # Errors
+    /// - Returns an error if the file cannot be read or is not a capture file
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let read_error = |e: io::Error| format!("Unable to read {}: {e}", path.display());
+
+        let file = fs::File::open(path).map_err(read_error)?;
+        let size = file.metadata().map_err(read_error)?.len();
+        let mut file = BufReader::new(file);
+
+        let mut header = [0; 10];
+        if file.read_exact(&mut header).is_err() || !header.starts_with(MAGIC) {
+            return Err(format!("{} is not a capture file", path.display()));
+        }
+
+        let version = u16::from_be_bytes([header[8], header[9]]);
+        if version != VERSION {
+            return Err(format!(
+                "{} is a version {version} capture, which is not supported",
+                path.display()
+            ));
+        }
+
+        let index = if let Some(index) = read_index(&mut file, size) {
+            index
+        } else {
+            tracing::warn!("{} has no index, scanning its records", path.display());
+            scan(&mut file, size).map_err(read_error)?
+        };
+
+        Ok(Self {
+            file,
+            path: path.to_path_buf(),
+            index,
+        })
+    }
+
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// Receive time of a record in Unix microseconds.
+    #[must_use]
+    pub fn time_us(&self, record: usize) -> Option<u64> {
+        self.index.get(record).map(|entry| entry.time_us)
+    }
+
+    /// Returns the first record received at or after `time_us`, or the record count if
+    /// every record was received earlier.
+    #[must_use]
+    pub fn seek_time(&self, time_us: u64) -> usize {
+        self.index.partition_point(|entry| entry.time_us < time_us)
+    }
+
+    /// Reads a record as the datagram that was captured.
+    ///
+    /// # Errors
+    /// - Returns an error if the record does not exist or cannot be read
+    pub fn read(&mut self, record: usize) -> Result<Datagram, String> {
+        let entry = self
+            .index
+            .get(record)
+            .ok_or_else(|| format!("{} has no record {record}", self.path.display()))?;
+
+        self.file
+            .seek(SeekFrom::Start(entry.offset))
+            .and_then(|_| read_record(&mut self.file))
+            .map_err(|e| format!("Unable to read {}: {e}", self.path.display()))
+    }
+}
+
+/// Reads the index written when the capture was closed, if it has one.
+fn read_index(file: &mut BufReader<fs::File>, size: u64) -> Option<Vec<IndexEntry>> {
+    let mut trailer = [0; TRAILER_LEN];
+    file.seek(SeekFrom::End(-i64::try_from(TRAILER_LEN).ok()?))
+        .ok()?;
+    file.read_exact(&mut trailer).ok()?;
+
+    if &trailer[16..] != INDEX_MAGIC {
+        return None;
+    }
+
+    let field = |at: usize| trailer[at..at + 8].try_into().ok().map(u64::from_be_bytes);
+    let index_offset = field(0)?;
+    let count = field(8)?;
+
+    let index_len = count.checked_mul(INDEX_ENTRY_LEN)?;
+    if index_offset < HEADER_LEN
+        || index_offset.checked_add(index_len)? + TRAILER_LEN as u64 != size
+    {
+        return None;
+    }
+
+    file.seek(SeekFrom::Start(index_offset)).ok()?;
+
+    let mut entries = vec![0; usize::try_from(index_len).ok()?];
+    file.read_exact(&mut entries).ok()?;
+
+    Some(
+        entries
+            .chunks_exact(16)
+            .map(|entry| {
+                let (time, offset) = entry.split_at(8);
+                IndexEntry {
+                    time_us: u64::from_be_bytes(time.try_into().unwrap_or_default()),
+                    offset: u64::from_be_bytes(offset.try_into().unwrap_or_default()),
+                }
+            })
+            .collect(),
+    )
+}
+
+/// Indexes the complete records of a capture that has no index, ignoring a final record
+/// cut short.
+fn scan(file: &mut BufReader<fs::File>, size: u64) -> io::Result<Vec<IndexEntry>> {
+    let mut index = Vec::new();
+    let mut offset = HEADER_LEN;
+
+    file.seek(SeekFrom::Start(offset))?;
+
+    while offset + RECORD_HEADER_LEN as u64 <= size {
+        let mut header = [0; RECORD_HEADER_LEN];
+        file.read_exact(&mut header)?;
+
+        let time_us = u64::from_be_bytes(header[..8].try_into().unwrap_or_default());
+        let length = u32::from_be_bytes(header[26..].try_into().unwrap_or_default());
+        let next = offset + RECORD_HEADER_LEN as u64 + u64::from(length);
+
+        if next > size || length as usize > MAX_PDU_SIZE_OCTETS {
+            break;
+        }
+
+        index.push(IndexEntry { time_us, offset });
+        file.seek_relative(i64::from(length))?;
+        offset = next;
+    }
+
+    Ok(index)
+}
+
+fn read_record(file: &mut impl Read) -> io::Result<Datagram> {
+    let mut header = [0; RECORD_HEADER_LEN];
+    file.read_exact(&mut header)?;
+
+    let field = |range: std::ops::Range<usize>| &header[range];
+    let time_us = u64::from_be_bytes(field(0..8).try_into().unwrap_or_default());
+    let address = Ipv6Addr::from(<[u8; 16]>::try_from(field(8..24)).unwrap_or_default());
+    let port = u16::from_be_bytes(field(24..26).try_into().unwrap_or_default());
+    let length = u32::from_be_bytes(field(26..30).try_into().unwrap_or_default());
+
+    // Only whole datagrams are recorded, so a longer record is corrupt
+    if length as usize > MAX_PDU_SIZE_OCTETS {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("capture record length {length} exceeds the largest DIS PDU"),
+        ));
+    }
+
+    let mut data = vec![0; length as usize];
+    file.read_exact(&mut data)?;
+
+    let ip = address
+        .to_ipv4_mapped()
+        .map_or(IpAddr::V6(address), IpAddr::V4);
+
+    Ok(Datagram {
+        data: Bytes::from(data),
+        source: SocketAddr::new(ip, port),
+        received_at: UNIX_EPOCH + Duration::from_micros(time_us),
+        replayed: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    /// A path in the temporary directory unique to this process and `name`
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "centurion-{}-{name}.{CAPTURE_EXTENSION}",
+            std::process::id()
+        ))
+    }
+
+    fn datagram(index: u8) -> Datagram {
+        let source = if index.is_multiple_of(2) {
+            format!("10.0.0.{index}:3000")
+        } else {
+            format!("[fe80::{index}]:3001")
+        };
+
+        Datagram {
+            data: Bytes::from(vec![index; 12 + usize::from(index)]),
+            source: source.parse().expect("a socket address"),
+            received_at: UNIX_EPOCH + Duration::from_micros(1_000_000 * u64::from(index) + 7),
+            replayed: false,
+        }
+    }
+
+    async fn write(path: &Path, count: u8) -> CaptureWriter {
+        let mut writer = CaptureWriter::create(path).await.expect("a new capture");
+        for index in 0..count {
+            writer
+                .write(&datagram(index))
+                .await
+                .expect("a written record");
+        }
+        writer
+    }
+
+    fn assert_records(reader: &mut CaptureReader, count: u8) {
+        assert_eq!(reader.len(), usize::from(count));
+
+        for index in 0..count {
+            let expected = datagram(index);
+            let record = reader.read(usize::from(index)).expect("a readable record");
+
+            assert_eq!(record.data, expected.data);
+            assert_eq!(record.source, expected.source);
+            assert_eq!(record.received_at, expected.received_at);
+            assert_eq!(
+                reader.time_us(usize::from(index)),
+                Some(unix_us(expected.received_at))
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn round_trip() {
+        let path = temp_path("round-trip");
+        let writer = write(&path, 5).await;
+        assert_eq!(writer.records(), 5);
+        writer.finish().await.expect("a closed capture");
+
+        let mut reader = CaptureReader::open(&path).expect("an indexed capture");
+        assert_records(&mut reader, 5);
+
+        // Reading out of order follows the index
+        assert_eq!(reader.read(3).expect("record 3").data, datagram(3).data);
+        assert_eq!(reader.seek_time(2_000_000), 2);
+        assert_eq!(reader.seek_time(2_000_008), 3);
+        assert_eq!(reader.seek_time(u64::MAX), 5);
+        assert!(reader.read(5).is_err());
+
+        let _ = fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn unfinished_capture_is_scanned() {
+        let path = temp_path("unfinished");
+        let mut writer = write(&path, 4).await;
+        writer.flush().await.expect("a flushed capture");
+        drop(writer);
+
+        let mut reader = CaptureReader::open(&path).expect("a capture without an index");
+        assert_records(&mut reader, 4);
+
+        let _ = fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn truncated_tail_is_ignored() {
+        let path = temp_path("truncated");
+        let mut writer = write(&path, 4).await;
+        writer.flush().await.expect("a flushed capture");
+        let size = writer.size();
+        drop(writer);
+
+        // Cut the last record short, as a crash part way through a write would
+        let file = fs::OpenOptions::new()
+            .write(true)
+            .open(&path)
+            .expect("the capture");
+        file.set_len(size - 5).expect("a truncated capture");
+        drop(file);
+
+        let mut reader = CaptureReader::open(&path).expect("a capture without an index");
+        assert_records(&mut reader, 3);
+
+        // A record header cut short is ignored as well
+        let file = fs::OpenOptions::new()
+            .write(true)
+            .open(&path)
+            .expect("the capture");
+        file.set_len(size - datagram(3).data.len() as u64 - 10)
+            .expect("a truncated capture");
+        drop(file);
+
+        let mut reader = CaptureReader::open(&path).expect("a capture without an index");
+        assert_records(&mut reader, 3);
+
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn oversized_record_is_rejected() {
+        let mut record = vec![0; RECORD_HEADER_LEN];
+        let length = u32::try_from(MAX_PDU_SIZE_OCTETS + 1).expect("a record length");
+        record[26..].copy_from_slice(&length.to_be_bytes());
+
+        let error = read_record(&mut Cursor::new(record)).expect_err("an oversized record");
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn other_files_are_refused() {
+        let path = temp_path("other");
+
+        fs::write(&path, b"not a capture file").expect("a file");
+        assert!(CaptureReader::open(&path).is_err());
+
+        let mut header = MAGIC.to_vec();
+        header.extend_from_slice(&2u16.to_be_bytes());
+        header.extend_from_slice(&[0; 6]);
+        fs::write(&path, header).expect("a file");
+        assert!(
+            CaptureReader::open(&path)
+                .err()
+                .is_some_and(|e| e.contains("version 2"))
+        );
+
+        let _ = fs::remove_file(path);
+    }
+}
@@ -0,0 +1,121 @@
+//! Conversion between Unix time and the DIS clock time record used by Start/Resume and
+//! Stop/Freeze PDUs.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use open_dis_rust::common::data_types::ClockTime;
+use serde::Deserialize;
+
+const MS_PER_HOUR: u64 = 3_600_000;
+
+/// Timestamp units per hour; the time past the hour is a 31-bit count of these
+const TIMESTAMP_UNITS_PER_HOUR: u64 = 1 << 31;
+
+/// Real-world and simulation instants chosen by the operator, as Unix milliseconds (UTC).
+#[derive(Deserialize, Debug, Clone, Copy, Default)]
+#[serde(default)]
+pub struct Schedule {
+    pub real_world_ms: Option<u64>,
+    pub simulation_ms: Option<u64>,
+}
+
+impl Schedule {
+    /// Resolves the real-world and simulation instants of a command.
+    ///
+    /// Unscheduled real-world time defaults to now plus `lead_time`, and unscheduled
+    /// simulation time follows the real-world time.
+    ///
+    /// # Errors
+    /// - Returns an error if the system clock is before the Unix epoch or the scheduled
+    ///   real-world time has already passed
+    pub fn resolve(self, lead_time: Duration) -> Result<(u64, u64), String> {
+        let now = now_ms()?;
+
+        let real_world_ms = match self.real_world_ms {
+            Some(scheduled) if scheduled < now => {
+                return Err(format!(
+                    "scheduled real-world time is {} ms in the past",
+                    now - scheduled
+                ));
+            }
+            Some(scheduled) => scheduled,
+            None => now.saturating_add(u64::try_from(lead_time.as_millis()).unwrap_or(u64::MAX)),
+        };
+
+        Ok((real_world_ms, self.simulation_ms.unwrap_or(real_world_ms)))
+    }
+}
+
+/// Returns the current system time as Unix milliseconds.
+///
+/// # Errors
+/// - Returns an error if the system clock is before the Unix epoch
+pub fn now_ms() -> Result<u64, String> {
+    unix_ms(SystemTime::now())
+}
+
+/// Formats Unix milliseconds as a compact UTC date and time, such as `20261018-101500`.
+#[must_use]
+pub fn utc_stamp(unix_ms: u64) -> String {
+    let seconds = unix_ms / 1000;
+    let (days, time) = (seconds / 86_400, seconds % 86_400);
+    let (year, month, day) = civil_date(days);
+
+    format!(
+        "{year:04}{month:02}{day:02}-{:02}{:02}{:02}",
+        time / 3600,
+        time / 60 % 60,
+        time % 60
+    )
+}
+
+/// Converts days since the Unix epoch to a Gregorian year, month and day.
+fn civil_date(days: u64) -> (u64, u64, u64) {
+    // Counts from 1 March 0000 so that leap days fall at the end of each year
+    let shifted = days + 719_468;
+    let era = shifted / 146_097;
+    let day_of_era = shifted % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+
+    let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+    let month = if month_from_march < 10 {
+        month_from_march + 3
+    } else {
+        month_from_march - 9
+    };
+    let year = era * 400 + year_of_era + u64::from(month <= 2);
+
+    (year, month, day)
+}
+
+/// Converts a system time to Unix milliseconds.
+///
+/// # Errors
+/// - Returns an error if the time is before the Unix epoch
+pub fn unix_ms(time: SystemTime) -> Result<u64, String> {
+    let elapsed = time
+        .duration_since(UNIX_EPOCH)
+        .map_err(|_| "system clock is before the Unix epoch")?;
+
+    u64::try_from(elapsed.as_millis()).map_err(|_| "system clock is out of range".into())
+}
+
+/// Encodes Unix milliseconds as an absolute DIS clock time.
+#[must_use]
+pub fn clock_time(unix_ms: u64) -> ClockTime {
+    let hour = u32::try_from(unix_ms / MS_PER_HOUR).unwrap_or(u32::MAX);
+
+    ClockTime::new(hour, timestamp(unix_ms, true))
+}
+
+/// Encodes the time past the hour of Unix milliseconds as a DIS timestamp.
+#[must_use]
+pub fn timestamp(unix_ms: u64, absolute: bool) -> u32 {
+    let units = (unix_ms % MS_PER_HOUR) * TIMESTAMP_UNITS_PER_HOUR / MS_PER_HOUR;
+
+    // The least significant bit marks the timestamp as absolute (synchronised to UTC)
+    u32::try_from((units << 1) | u64::from(absolute)).unwrap_or(u32::MAX)
+}
@@ -0,0 +1,120 @@
+//! Detection of another simulation manager sending SIMAN PDUs from Centurion's address.
+
+use bytes::BytesMut;
+use open_dis_rust::common::constants::PDU_HEADER_SIZE;
+use open_dis_rust::common::enums::ProtocolFamily;
+use open_dis_rust::common::{GenericHeader, PduHeader};
+
+use crate::core::{Datagram, EntityIdentifier, Monitor, Notice, OwnTraffic, unix_ms};
+
+/// A conflict is over once no conflicting PDU has been heard for this long
+const CONFLICT_TIMEOUT_MS: u64 = 10_000;
+
+/// Watches received SIMAN PDUs for ones that claim Centurion's simulation address but
+/// were not sent by Centurion.
+#[derive(Default)]
+pub struct AddressConflicts {
+    /// The configured simulation address Centurion sends from
+    originator: Option<EntityIdentifier>,
+    own: OwnTraffic,
+    /// Reception time of the last conflicting PDU while a conflict is ongoing
+    last_heard_ms: Option<u64>,
+}
+
+impl AddressConflicts {
+    /// Sets the simulation address Centurion sends from, or `None` if it is not valid.
+    pub fn set_originator(&mut self, originator: Option<EntityIdentifier>) {
+        if self.originator != originator {
+            self.originator = originator;
+            self.last_heard_ms = None;
+        }
+    }
+}
+
+impl Monitor for AddressConflicts {
+    const EVENT: &'static str = "address-conflict";
+
+    /// The address being claimed while a conflict is ongoing
+    type Snapshot = Option<EntityIdentifier>;
+
+    /// Records a SIMAN PDU from Centurion's address that Centurion did not send, returning
+    /// whether it starts a conflict.
+    fn observe(&mut self, datagram: &Datagram) -> bool {
+        let Some(originator) = self.originator else {
+            return false;
+        };
+
+        if self.own.is_own(datagram) {
+            return false;
+        }
+
+        let data = &datagram.data;
+        if data.len() < PDU_HEADER_SIZE + 6 {
+            return false;
+        }
+
+        let header = PduHeader::deserialize(&mut BytesMut::from(&data[..PDU_HEADER_SIZE]));
+        if !is_siman(header.protocol_family)
+            || originating_entity(&data[PDU_HEADER_SIZE..]) != Some(originator)
+        {
+            return false;
+        }
+
+        let started = self.last_heard_ms.is_none();
+        self.last_heard_ms = Some(unix_ms(datagram.received_at).unwrap_or_default());
+
+        if started {
+            tracing::warn!(
+                "Another simulation manager is sending SIMAN PDUs from simulation address \
+                 {originator} (from {})",
+                datagram.source
+            );
+        }
+
+        started
+    }
+
+    fn observe_sent(&mut self, datagram: &Datagram) -> bool {
+        self.own.sent(datagram);
+        false
+    }
+
+    /// Ends a conflict once nothing has claimed the address for a while, so a later one
+    /// is reported again.
+    fn expire(&mut self, now_ms: u64, _notices: &mut Vec<Notice>) -> bool {
+        let ended = self
+            .last_heard_ms
+            .is_some_and(|heard| now_ms.saturating_sub(heard) >= CONFLICT_TIMEOUT_MS);
+
+        if ended {
+            self.last_heard_ms = None;
+        }
+
+        ended
+    }
+
+    fn snapshot(&self) -> Self::Snapshot {
+        self.last_heard_ms.and(self.originator)
+    }
+}
+
+pub(crate) const fn is_siman(family: ProtocolFamily) -> bool {
+    matches!(
+        family,
+        ProtocolFamily::SimulationManagement | ProtocolFamily::SimulationManagementWithReliability
+    )
+}
+
+/// Reads the originating entity ID that starts the body of every SIMAN PDU.
+pub(crate) fn originating_entity(body: &[u8]) -> Option<EntityIdentifier> {
+    let field = |offset: usize| {
+        body.get(offset..offset + 2)
+            .map(|b| u16::from_be_bytes([b[0], b[1]]))
+    };
+
+    Some(EntityIdentifier {
+        site_id: field(0)?,
+        application_id: field(2)?,
+        entity_id: field(4)?,
+    })
+}
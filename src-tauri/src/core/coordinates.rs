@@ -0,0 +1,617 @@
+//! Conversions between DIS world coordinates (geocentric, earth-centred earth-fixed),
+//! WGS-84 geodetic coordinates and MGRS, and of DIS orientations to local attitudes.
+
+use std::f64::consts::{FRAC_PI_2, PI};
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::{Matrix, Orientation, Vector};
+
+/// WGS-84 semi-major axis in metres
+const SEMI_MAJOR_AXIS: f64 = 6_378_137.0;
+
+/// WGS-84 flattening
+const FLATTENING: f64 = 1.0 / 298.257_223_563;
+
+/// Square of the first eccentricity
+const ECCENTRICITY_SQUARED: f64 = FLATTENING * (2.0 - FLATTENING);
+
+/// Iterations of the geodetic latitude, each gaining several orders of magnitude of accuracy
+const LATITUDE_ITERATIONS: usize = 4;
+
+/// UTM scale factor on the central meridian
+const UTM_SCALE: f64 = 0.9996;
+
+const UTM_FALSE_EASTING: f64 = 500_000.0;
+
+/// False northing of the southern hemisphere
+const UTM_FALSE_NORTHING: f64 = 10_000_000.0;
+
+/// Latitudes covered by UTM and therefore MGRS; the polar regions use UPS instead
+const UTM_MIN_LATITUDE: f64 = -80.0;
+const UTM_MAX_LATITUDE: f64 = 84.0;
+
+/// MGRS latitude bands, each 8° tall except X which is 12°
+const LATITUDE_BANDS: &[u8] = b"CDEFGHJKLMNPQRSTUVWX";
+
+/// MGRS 100 km column letters, cycling through three sets of eight across zones
+const SQUARE_COLUMNS: [&[u8]; 3] = [b"STUVWXYZ", b"ABCDEFGH", b"JKLMNPQR"];
+
+/// MGRS 100 km row letters, offset by five in even zones
+const SQUARE_ROWS: &[u8] = b"ABCDEFGHJKLMNPQRSTUV";
+
+const HUNDRED_KM: f64 = 100_000.0;
+
+/// Northings repeat their 100 km row letters every 2,000 km
+const ROW_CYCLE: f64 = 2_000_000.0;
+
+/// Headings from here up round to 360.0 at the 0.1° they are shown to, so are north
+const HEADING_WRAP: f64 = 359.95;
+
+/// A WGS-84 location.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Geodetic {
+    pub latitude_deg: f64,
+    pub longitude_deg: f64,
+    /// Height above the ellipsoid in metres
+    pub altitude_m: f64,
+}
+
+impl Geodetic {
+    /// Converts DIS world coordinates to WGS-84.
+    #[must_use]
+    pub fn from_geocentric(location: Vector) -> Self {
+        let Vector { x, y, z } = location;
+        let p = x.hypot(y);
+
+        let mut latitude = z.atan2(p * (1.0 - ECCENTRICITY_SQUARED));
+        for _ in 0..LATITUDE_ITERATIONS {
+            let sin = latitude.sin();
+            latitude = (ECCENTRICITY_SQUARED * prime_vertical_radius(sin))
+                .mul_add(sin, z)
+                .atan2(p);
+        }
+
+        let (sin, cos) = latitude.sin_cos();
+        // Stable at the poles, unlike dividing by the cosine of the latitude
+        let altitude = SEMI_MAJOR_AXIS.mul_add(
+            -(ECCENTRICITY_SQUARED * sin).mul_add(-sin, 1.0).sqrt(),
+            p.mul_add(cos, z * sin),
+        );
+
+        Self {
+            latitude_deg: latitude.to_degrees(),
+            longitude_deg: y.atan2(x).to_degrees(),
+            altitude_m: altitude,
+        }
+    }
+
+    /// Converts the location to DIS world coordinates.
+    #[must_use]
+    pub fn to_geocentric(self) -> Vector {
+        let (sin_lat, cos_lat) = self.latitude_deg.to_radians().sin_cos();
+        let (sin_lon, cos_lon) = self.longitude_deg.to_radians().sin_cos();
+        let radius = prime_vertical_radius(sin_lat);
+        let horizontal = (radius + self.altitude_m) * cos_lat;
+
+        Vector::new(
+            horizontal * cos_lon,
+            horizontal * sin_lon,
+            radius.mul_add(1.0 - ECCENTRICITY_SQUARED, self.altitude_m) * sin_lat,
+        )
+    }
+
+    /// Formats the location as a 1 m MGRS reference such as `18S UJ 23487 06483`.
+    ///
+    /// Returns `None` in the polar regions, which MGRS covers with UPS instead of UTM.
+    #[must_use]
+    pub fn to_mgrs(self) -> Option<String> {
+        if !(UTM_MIN_LATITUDE..=UTM_MAX_LATITUDE).contains(&self.latitude_deg) {
+            return None;
+        }
+
+        let zone = utm_zone(self.latitude_deg, self.longitude_deg);
+        let (easting, northing) = utm_forward(self.latitude_deg, self.longitude_deg, zone);
+
+        // The band index is clamped because band X extends to 84°
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let band =
+            LATITUDE_BANDS[(((self.latitude_deg - UTM_MIN_LATITUDE) / 8.0) as usize).min(19)];
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let (column, row) = (
+            (easting / HUNDRED_KM) as usize,
+            (northing / HUNDRED_KM) as usize,
+        );
+        let column_letter = SQUARE_COLUMNS[zone % 3].get(column.checked_sub(1)?)?;
+        let row_letter = SQUARE_ROWS[(row + row_offset(zone)) % SQUARE_ROWS.len()];
+
+        Some(format!(
+            "{zone}{} {}{} {:05.0} {:05.0}",
+            char::from(band),
+            char::from(*column_letter),
+            char::from(row_letter),
+            easting.rem_euclid(HUNDRED_KM).floor(),
+            northing.rem_euclid(HUNDRED_KM).floor(),
+        ))
+    }
+
+    /// Parses an MGRS reference of any precision from 100 km to 1 m, with or without
+    /// spaces, returning the south-west corner of the square it identifies.
+    ///
+    /// # Errors
+    /// - Returns an error if the reference is malformed or lies in a polar (UPS) region
+    pub fn from_mgrs(reference: &str) -> Result<Self, String> {
+        let compact: String = reference
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>()
+            .to_ascii_uppercase();
+        let invalid = || format!("invalid MGRS reference {reference}");
+
+        let digits = compact.chars().take_while(char::is_ascii_digit).count();
+        let zone: usize = compact[..digits].parse().map_err(|_| invalid())?;
+        if !(1..=60).contains(&zone) {
+            return Err(invalid());
+        }
+
+        let letters = compact
+            .as_bytes()
+            .get(digits..digits + 3)
+            .ok_or_else(invalid)?;
+        let band = LATITUDE_BANDS
+            .iter()
+            .position(|b| *b == letters[0])
+            .ok_or_else(|| format!("MGRS band {} is not covered by UTM", char::from(letters[0])))?;
+        let column = SQUARE_COLUMNS[zone % 3]
+            .iter()
+            .position(|c| *c == letters[1])
+            .ok_or_else(invalid)?;
+        let row = SQUARE_ROWS
+            .iter()
+            .position(|r| *r == letters[2])
+            .ok_or_else(invalid)?;
+
+        let numbers = &compact[digits + 3..];
+        if !numbers.len().is_multiple_of(2)
+            || numbers.len() > 10
+            || !numbers.bytes().all(|b| b.is_ascii_digit())
+        {
+            return Err(invalid());
+        }
+        let (east, north) = numbers.split_at(numbers.len() / 2);
+        let scale = |digits: &str| -> Result<f64, String> {
+            if digits.is_empty() {
+                return Ok(0.0);
+            }
+            let value: f64 = digits.parse().map_err(|_| invalid())?;
+            Ok(value * 10f64.powi(5 - i32::try_from(digits.len()).map_err(|_| invalid())?))
+        };
+
+        #[allow(clippy::cast_precision_loss)]
+        let easting = ((column + 1) as f64).mul_add(HUNDRED_KM, scale(east)?);
+
+        #[allow(clippy::cast_precision_loss)]
+        let northing = (((row + SQUARE_ROWS.len() - row_offset(zone)) % SQUARE_ROWS.len()) as f64)
+            .mul_add(HUNDRED_KM, scale(north)?);
+
+        // Lift the northing into the 2,000 km cycle that reaches the band, allowing for the
+        // bottom 100 km square of the band starting below it
+        #[allow(clippy::cast_precision_loss)]
+        let band_south = (band as f64).mul_add(8.0, UTM_MIN_LATITUDE);
+        let north_hemisphere = band_south >= 0.0;
+        let central_meridian = central_meridian(zone);
+        let (_, band_northing) = utm_forward(band_south, central_meridian, zone);
+        let band_floor = (band_northing / HUNDRED_KM).floor() * HUNDRED_KM;
+
+        let cycles = ((band_floor - northing) / ROW_CYCLE).ceil().max(0.0);
+        let northing = cycles.mul_add(ROW_CYCLE, northing);
+
+        let (latitude_deg, longitude_deg) = utm_inverse(easting, northing, zone, north_hemisphere);
+
+        Ok(Self {
+            latitude_deg,
+            longitude_deg,
+            altitude_m: 0.0,
+        })
+    }
+}
+
+/// Heading, pitch and roll in degrees relative to local north, east and down.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Attitude {
+    /// Clockwise from true north, from 0 to 360
+    pub heading: f64,
+    /// Nose up is positive
+    pub pitch: f64,
+    /// Right wing down is positive
+    pub roll: f64,
+}
+
+impl Attitude {
+    /// Converts a DIS orientation to the attitude it represents at `location`.
+    #[must_use]
+    pub fn from_orientation(orientation: Orientation, location: Geodetic) -> Self {
+        let local = Matrix::from_orientation(local_level(location)).transpose();
+        let relative = (Matrix::from_orientation(orientation) * local).orientation();
+
+        // A heading a hair below north would otherwise read 360.0 rather than 0.0
+        let heading = relative.psi.to_degrees().rem_euclid(360.0);
+
+        Self {
+            heading: if heading >= HEADING_WRAP {
+                0.0
+            } else {
+                heading
+            },
+            pitch: relative.theta.to_degrees(),
+            roll: relative.phi.to_degrees(),
+        }
+    }
+
+    /// Converts the attitude at `location` to a DIS orientation.
+    #[must_use]
+    pub fn to_orientation(self, location: Geodetic) -> Orientation {
+        let relative = Orientation {
+            psi: self.heading.to_radians(),
+            theta: self.pitch.to_radians(),
+            phi: self.roll.to_radians(),
+        };
+
+        (Matrix::from_orientation(relative) * Matrix::from_orientation(local_level(location)))
+            .orientation()
+    }
+}
+
+/// A DIS location and orientation in the forms operators read.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct Placement {
+    pub geodetic: Geodetic,
+    /// Absent in the polar regions
+    pub mgrs: Option<String>,
+    pub attitude: Attitude,
+}
+
+impl Placement {
+    #[must_use]
+    pub fn new(location: Vector, orientation: Orientation) -> Self {
+        let geodetic = Geodetic::from_geocentric(location);
+
+        Self {
+            geodetic,
+            mgrs: geodetic.to_mgrs(),
+            attitude: Attitude::from_orientation(orientation, geodetic),
+        }
+    }
+}
+
+/// The orientation of the local north-east-down frame at `location`.
+///
+/// Rotating world coordinates by the longitude about Z and then by minus the latitude
+/// less 90° about the new Y axis aligns them with north, east and down.
+fn local_level(location: Geodetic) -> Orientation {
+    Orientation {
+        psi: location.longitude_deg.to_radians(),
+        theta: -location.latitude_deg.to_radians() - FRAC_PI_2,
+        phi: 0.0,
+    }
+}
+
+/// Radius of curvature in the prime vertical at a latitude with the given sine.
+fn prime_vertical_radius(sin_latitude: f64) -> f64 {
+    SEMI_MAJOR_AXIS
+        / (ECCENTRICITY_SQUARED * sin_latitude)
+            .mul_add(-sin_latitude, 1.0)
+            .sqrt()
+}
+
+/// Returns the UTM zone of a location, including the Norway and Svalbard exceptions.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn utm_zone(latitude: f64, longitude: f64) -> usize {
+    let longitude = (longitude + 180.0).rem_euclid(360.0) - 180.0;
+    let zone = (((longitude + 180.0) / 6.0) as usize + 1).min(60);
+
+    if (56.0..64.0).contains(&latitude) && (3.0..12.0).contains(&longitude) {
+        return 32;
+    }
+
+    if (72.0..=84.0).contains(&latitude) && (0.0..42.0).contains(&longitude) {
+        return match longitude {
+            l if l < 9.0 => 31,
+            l if l < 21.0 => 33,
+            l if l < 33.0 => 35,
+            _ => 37,
+        };
+    }
+
+    zone
+}
+
+#[allow(clippy::cast_precision_loss)]
+const fn central_meridian(zone: usize) -> f64 {
+    (zone as f64).mul_add(6.0, -183.0)
+}
+
+/// Row letters of even zones start five letters later so that adjacent zones differ.
+const fn row_offset(zone: usize) -> usize {
+    if zone.is_multiple_of(2) { 5 } else { 0 }
+}
+
+/// Coefficients of the Krüger series for the transverse Mercator projection.
+struct Kruger {
+    /// Radius of the rectifying sphere
+    radius: f64,
+    alpha: [f64; 3],
+    beta: [f64; 3],
+    delta: [f64; 3],
+}
+
+impl Kruger {
+    // Kept in the textbook form of the series rather than fused multiply-adds
+    #[allow(clippy::suboptimal_flops)]
+    fn wgs84() -> Self {
+        let n = FLATTENING / (2.0 - FLATTENING);
+        let (n2, n3) = (n * n, n * n * n);
+
+        Self {
+            radius: SEMI_MAJOR_AXIS / (1.0 + n) * (1.0 + n2 / 4.0 + n2 * n2 / 64.0),
+            alpha: [
+                n / 2.0 - 2.0 * n2 / 3.0 + 5.0 * n3 / 16.0,
+                13.0 * n2 / 48.0 - 3.0 * n3 / 5.0,
+                61.0 * n3 / 240.0,
+            ],
+            beta: [
+                n / 2.0 - 2.0 * n2 / 3.0 + 37.0 * n3 / 96.0,
+                n2 / 48.0 + n3 / 15.0,
+                17.0 * n3 / 480.0,
+            ],
+            delta: [
+                2.0 * n - 2.0 * n2 / 3.0 - 2.0 * n3,
+                7.0 * n2 / 3.0 - 8.0 * n3 / 5.0,
+                56.0 * n3 / 15.0,
+            ],
+        }
+    }
+}
+
+/// Projects a location into `zone`, returning its easting and northing in metres.
+fn utm_forward(latitude: f64, longitude: f64, zone: usize) -> (f64, f64) {
+    let series = Kruger::wgs84();
+    let eccentricity = ECCENTRICITY_SQUARED.sqrt();
+
+    let phi = latitude.to_radians();
+    let lambda = (longitude - central_meridian(zone)).to_radians();
+    let lambda = (lambda + PI).rem_euclid(2.0 * PI) - PI;
+
+    let t = eccentricity
+        .mul_add(-(eccentricity * phi.sin()).atanh(), phi.sin().atanh())
+        .sinh();
+    let xi = t.atan2(lambda.cos());
+    let eta = (lambda.sin() / t.hypot(1.0)).atanh();
+
+    let (mut x, mut y) = (eta, xi);
+    for (j, alpha) in (1..=3).zip(series.alpha) {
+        let k = f64::from(j) * 2.0;
+        x += alpha * (k * xi).cos() * (k * eta).sinh();
+        y += alpha * (k * xi).sin() * (k * eta).cosh();
+    }
+
+    let easting = (UTM_SCALE * series.radius).mul_add(x, UTM_FALSE_EASTING);
+    let northing = UTM_SCALE * series.radius * y;
+
+    (
+        easting,
+        if latitude < 0.0 {
+            northing + UTM_FALSE_NORTHING
+        } else {
+            northing
+        },
+    )
+}
+
+/// Returns the latitude and longitude in degrees of a UTM easting and northing.
+fn utm_inverse(easting: f64, northing: f64, zone: usize, north: bool) -> (f64, f64) {
+    let series = Kruger::wgs84();
+    let scaled = UTM_SCALE * series.radius;
+
+    let northing = if north {
+        northing
+    } else {
+        northing - UTM_FALSE_NORTHING
+    };
+    let xi = northing / scaled;
+    let eta = (easting - UTM_FALSE_EASTING) / scaled;
+
+    let (mut xi_prime, mut eta_prime) = (xi, eta);
+    for (j, beta) in (1..=3).zip(series.beta) {
+        let k = f64::from(j) * 2.0;
+        xi_prime -= beta * (k * xi).sin() * (k * eta).cosh();
+        eta_prime -= beta * (k * xi).cos() * (k * eta).sinh();
+    }
+
+    let chi = (xi_prime.sin() / eta_prime.cosh()).asin();
+    let mut phi = chi;
+    for (j, delta) in (1..=3).zip(series.delta) {
+        phi += delta * (f64::from(j) * 2.0 * chi).sin();
+    }
+
+    let lambda = eta_prime.sinh().atan2(xi_prime.cos());
+
+    (
+        phi.to_degrees(),
+        central_meridian(zone) + lambda.to_degrees(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(latitude_deg: f64, longitude_deg: f64, altitude_m: f64) -> Geodetic {
+        Geodetic {
+            latitude_deg,
+            longitude_deg,
+            altitude_m,
+        }
+    }
+
+    /// Straight-line distance in metres between two locations
+    fn distance(a: Geodetic, b: Geodetic) -> f64 {
+        let (a, b) = (a.to_geocentric(), b.to_geocentric());
+        (a.x - b.x).hypot(a.y - b.y).hypot(a.z - b.z)
+    }
+
+    #[test]
+    fn geocentric_round_trip() {
+        let locations = [
+            at(0.0, 0.0, 0.0),
+            at(38.8895, -77.0353, 120.0),
+            at(-33.8568, 151.2153, 5.0),
+            at(51.4779, -0.0015, 8_000.0),
+            at(89.9999, 45.0, 0.0),
+            at(-90.0, 0.0, -50.0),
+            at(0.0, 180.0, 35_786_000.0),
+        ];
+
+        for location in locations {
+            let back = Geodetic::from_geocentric(location.to_geocentric());
+            assert!(
+                distance(location, back) < 1e-3,
+                "{location:?} came back as {back:?}"
+            );
+            assert!((location.altitude_m - back.altitude_m).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn geocentric_axes() {
+        let equator = at(0.0, 0.0, 0.0).to_geocentric();
+        assert!((equator.x - SEMI_MAJOR_AXIS).abs() < 1e-6);
+        assert!(equator.y.abs() < 1e-6 && equator.z.abs() < 1e-6);
+
+        let pole = Geodetic::from_geocentric(Vector::new(0.0, 0.0, 6_356_752.314_245));
+        assert!((pole.latitude_deg - 90.0).abs() < 1e-9);
+        assert!(pole.altitude_m.abs() < 1e-3);
+    }
+
+    #[test]
+    fn known_mgrs_reference() {
+        assert_eq!(
+            at(0.0, 0.0, 0.0).to_mgrs().as_deref(),
+            Some("31N AA 66021 00000")
+        );
+    }
+
+    #[test]
+    fn mgrs_round_trip() {
+        let locations = [
+            at(38.8895, -77.0353, 0.0),
+            at(-33.8568, 151.2153, 0.0),
+            at(0.0001, 0.0001, 0.0),
+            at(-0.0001, -0.0001, 0.0),
+            // Either side of the boundary between zones 30 and 31
+            at(45.0, -0.000_01, 0.0),
+            at(45.0, 0.000_01, 0.0),
+            // Zone 60 up to the antimeridian
+            at(-41.0, 179.9999, 0.0),
+            // The widened zone 32 of south-west Norway
+            at(60.0, 5.0, 0.0),
+            // The Svalbard zones of band X
+            at(78.0, 10.0, 0.0),
+            at(78.0, 20.0, 0.0),
+            at(83.9, 40.0, 0.0),
+            // The edges of the UTM latitudes
+            at(-79.9, 0.0, 0.0),
+            at(83.99, -170.0, 0.0),
+        ];
+
+        for location in locations {
+            let reference = location.to_mgrs().expect("within the UTM latitudes");
+            let corner = Geodetic::from_mgrs(&reference).expect("a valid reference");
+
+            // The reference names the 1 m square the location lies in
+            assert!(
+                distance(location, corner) < 1.5,
+                "{location:?} as {reference} came back as {corner:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn mgrs_zone_exceptions() {
+        let zone = |location: Geodetic| location.to_mgrs().map(|r| r[..3].to_string());
+
+        assert_eq!(zone(at(60.0, 5.0, 0.0)).as_deref(), Some("32V"));
+        assert_eq!(zone(at(60.0, 2.0, 0.0)).as_deref(), Some("31V"));
+        assert_eq!(zone(at(78.0, 8.0, 0.0)).as_deref(), Some("31X"));
+        assert_eq!(zone(at(78.0, 10.0, 0.0)).as_deref(), Some("33X"));
+        assert_eq!(zone(at(78.0, 40.0, 0.0)).as_deref(), Some("37X"));
+    }
+
+    #[test]
+    fn mgrs_polar_regions() {
+        assert_eq!(at(84.5, 0.0, 0.0).to_mgrs(), None);
+        assert_eq!(at(-80.5, 0.0, 0.0).to_mgrs(), None);
+        assert!(Geodetic::from_mgrs("ZAH 12345 12345").is_err());
+        assert!(Geodetic::from_mgrs("31Z AH").is_err());
+    }
+
+    #[test]
+    fn mgrs_precisions() {
+        let square = Geodetic::from_mgrs("18SUJ").expect("a 100 km reference");
+        let metre = Geodetic::from_mgrs("18S UJ 00000 00000").expect("a 1 m reference");
+        assert!(distance(square, metre) < 1e-6);
+
+        assert!(Geodetic::from_mgrs("18S UJ 123 45").is_err());
+        assert!(Geodetic::from_mgrs("61S UJ").is_err());
+        assert!(Geodetic::from_mgrs("18S UI").is_err());
+    }
+
+    #[test]
+    fn attitude_round_trip() {
+        let locations = [
+            at(0.0, 0.0, 0.0),
+            at(38.9, -77.0, 0.0),
+            at(-60.0, 120.0, 0.0),
+        ];
+        let attitudes = [(0.0, 0.0, 0.0), (90.0, 10.0, -5.0), (271.5, -30.0, 45.0)];
+
+        for location in locations {
+            for (heading, pitch, roll) in attitudes {
+                let attitude = Attitude {
+                    heading,
+                    pitch,
+                    roll,
+                };
+                let back = Attitude::from_orientation(attitude.to_orientation(location), location);
+
+                assert!(
+                    (back.heading - heading).abs() < 1e-6,
+                    "{back:?} at {location:?}"
+                );
+                assert!((back.pitch - pitch).abs() < 1e-6);
+                assert!((back.roll - roll).abs() < 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn heading_just_below_north_is_north() {
+        let location = at(38.9, -77.0, 0.0);
+
+        for heading in [0.0, 359.99, -1e-9] {
+            let attitude = Attitude {
+                heading,
+                pitch: 0.0,
+                roll: 0.0,
+            };
+            let back = Attitude::from_orientation(attitude.to_orientation(location), location);
+
+            assert!(
+                back.heading < 1e-6,
+                "{heading} came back as {}",
+                back.heading
+            );
+        }
+    }
+}
@@ -0,0 +1,183 @@
+//! Datum record encoding shared by the SIMAN PDUs that carry fixed and variable datums.
+//!
+//! `open_dis_rust` does not yet populate the datum record counts when serializing, and
+//! leaves the record lists empty when deserializing. The records are therefore written
+//! and read here, directly after the fixed-length portion of each PDU.
+
+use bytes::{Buf, BufMut, BytesMut};
+use serde::{Deserialize, Serialize};
+
+/// Byte offset of the length field within the PDU header
+const PDU_LENGTH_OFFSET: usize = 8;
+
+/// Variable datum values are padded to a 64-bit boundary
+pub(crate) const DATUM_ALIGNMENT_BYTES: usize = 8;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct FixedDatum {
+    pub datum_id: u32,
+    pub value: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct VariableDatum {
+    pub datum_id: u32,
+    pub length_bits: u32,
+    pub value: Vec<u8>,
+}
+
+impl VariableDatum {
+    const fn value_len(&self) -> usize {
+        (self.length_bits as usize).div_ceil(8)
+    }
+
+    const fn padded_len(&self) -> usize {
+        self.value_len().div_ceil(DATUM_ALIGNMENT_BYTES) * DATUM_ALIGNMENT_BYTES
+    }
+}
+
+/// The datum IDs requested by a Data Query PDU.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct DatumIds {
+    #[serde(default)]
+    pub fixed: Vec<u32>,
+    #[serde(default)]
+    pub variable: Vec<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct DatumSet {
+    #[serde(default)]
+    pub fixed: Vec<FixedDatum>,
+    #[serde(default)]
+    pub variable: Vec<VariableDatum>,
+}
+
+impl DatumSet {
+    /// Returns the number of fixed and variable datum records as carried in the PDU.
+    ///
+    /// # Errors
+    /// - Returns an error if either list is longer than a 32-bit count allows
+    pub fn counts(&self) -> Result<(u32, u32), String> {
+        let fixed = u32::try_from(self.fixed.len()).map_err(|_| "too many fixed datums")?;
+        let variable =
+            u32::try_from(self.variable.len()).map_err(|_| "too many variable datums")?;
+        Ok((fixed, variable))
+    }
+
+    /// Appends the datum records to a serialized PDU and updates its header length.
+    ///
+    /// # Errors
+    /// - Returns an error if a variable datum value is shorter than its length in bits or
+    ///   the resulting PDU exceeds the maximum PDU length
+    pub fn append_to(&self, bytes: &mut BytesMut) -> Result<(), String> {
+        for datum in &self.fixed {
+            bytes.put_u32(datum.datum_id);
+            bytes.put_u32(datum.value);
+        }
+
+        for datum in &self.variable {
+            let value_len = datum.value_len();
+            if datum.value.len() < value_len {
+                return Err(format!(
+                    "variable datum {} holds {} bytes but declares {} bits",
+                    datum.datum_id,
+                    datum.value.len(),
+                    datum.length_bits
+                ));
+            }
+
+            bytes.put_u32(datum.datum_id);
+            bytes.put_u32(datum.length_bits);
+            bytes.put_slice(&datum.value[..value_len]);
+            bytes.put_bytes(0, datum.padded_len() - value_len);
+        }
+
+        update_pdu_length(bytes)
+    }
+
+    /// Reads the given number of fixed and variable datum records from `buf`.
+    ///
+    /// # Errors
+    /// - Returns an error if the buffer ends before all records have been read
+    pub fn read_from<B: Buf>(buf: &mut B, fixed: u32, variable: u32) -> Result<Self, String> {
+        let mut datums = Self::default();
+
+        for _ in 0..fixed {
+            ensure_remaining(buf, 8)?;
+            datums.fixed.push(FixedDatum {
+                datum_id: buf.get_u32(),
+                value: buf.get_u32(),
+            });
+        }
+
+        for _ in 0..variable {
+            ensure_remaining(buf, 8)?;
+            let datum_id = buf.get_u32();
+            let length_bits = buf.get_u32();
+
+            let mut datum = VariableDatum {
+                datum_id,
+                length_bits,
+                value: Vec::new(),
+            };
+
+            let padded_len = datum.padded_len();
+            ensure_remaining(buf, padded_len)?;
+
+            datum.value = buf.copy_to_bytes(datum.value_len()).to_vec();
+            buf.advance(padded_len - datum.value_len());
+
+            datums.variable.push(datum);
+        }
+
+        Ok(datums)
+    }
+}
+
+impl DatumIds {
+    /// Returns the number of fixed and variable datum IDs as carried in the PDU.
+    ///
+    /// # Errors
+    /// - Returns an error if either list is longer than a 32-bit count allows
+    pub fn counts(&self) -> Result<(u32, u32), String> {
+        let fixed = u32::try_from(self.fixed.len()).map_err(|_| "too many fixed datum IDs")?;
+        let variable =
+            u32::try_from(self.variable.len()).map_err(|_| "too many variable datum IDs")?;
+        Ok((fixed, variable))
+    }
+
+    /// Appends the datum IDs to a serialized Data Query PDU and updates its header length.
+    ///
+    /// # Errors
+    /// - Returns an error if the resulting PDU exceeds the maximum PDU length
+    pub fn append_to(&self, bytes: &mut BytesMut) -> Result<(), String> {
+        for id in self.fixed.iter().chain(&self.variable) {
+            bytes.put_u32(*id);
+        }
+
+        update_pdu_length(bytes)
+    }
+}
+
+fn ensure_remaining<B: Buf>(buf: &B, needed: usize) -> Result<(), String> {
+    if buf.remaining() < needed {
+        return Err(format!(
+            "datum record truncated: needed {needed} bytes but only {} remain",
+            buf.remaining()
+        ));
+    }
+    Ok(())
+}
+
+fn update_pdu_length(bytes: &mut BytesMut) -> Result<(), String> {
+    let length = u16::try_from(bytes.len())
+        .map_err(|_| format!("PDU length of {} bytes exceeds the maximum", bytes.len()))?;
+
+    bytes
+        .get_mut(PDU_LENGTH_OFFSET..PDU_LENGTH_OFFSET + 2)
+        .ok_or("PDU is shorter than its header")?
+        .copy_from_slice(&length.to_be_bytes());
+
+    Ok(())
+}
@@ -0,0 +1,347 @@
+//! The dead-reckoning models of IEEE 1278.1-2012 Annex E.
+
+use std::ops::{Add, Mul, Sub};
+
+use open_dis_rust::common::enums::DeadReckoningAlgorithm;
+use serde::Serialize;
+
+/// Angular speeds below this many radians per second are treated as no rotation
+const MIN_ANGULAR_SPEED: f64 = 1e-9;
+
+/// A three-component vector in DIS world or entity coordinates.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Default)]
+pub struct Vector {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Vector {
+    pub const ZERO: Self = Self::new(0.0, 0.0, 0.0);
+
+    #[must_use]
+    pub const fn new(x: f64, y: f64, z: f64) -> Self {
+        Self { x, y, z }
+    }
+
+    #[must_use]
+    pub fn dot(self, other: Self) -> f64 {
+        self.z
+            .mul_add(other.z, self.x.mul_add(other.x, self.y * other.y))
+    }
+
+    #[must_use]
+    pub fn magnitude(self) -> f64 {
+        self.dot(self).sqrt()
+    }
+}
+
+impl Add for Vector {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl Sub for Vector {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl Mul<f64> for Vector {
+    type Output = Self;
+
+    fn mul(self, factor: f64) -> Self {
+        Self::new(self.x * factor, self.y * factor, self.z * factor)
+    }
+}
+
+/// DIS Euler angles in radians, rotating world coordinates into entity coordinates.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Default)]
+pub struct Orientation {
+    pub psi: f64,
+    pub theta: f64,
+    pub phi: f64,
+}
+
+/// A 3x3 matrix stored by rows.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Matrix([[f64; 3]; 3]);
+
+impl Matrix {
+    pub const IDENTITY: Self = Self([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]);
+
+    /// Builds the world-to-entity rotation described by an orientation.
+    #[must_use]
+    pub fn from_orientation(orientation: Orientation) -> Self {
+        let (sin_yaw, cos_yaw) = orientation.psi.sin_cos();
+        let (sin_pitch, cos_pitch) = orientation.theta.sin_cos();
+        let (sin_roll, cos_roll) = orientation.phi.sin_cos();
+
+        Self([
+            [cos_pitch * cos_yaw, cos_pitch * sin_yaw, -sin_pitch],
+            [
+                (sin_roll * sin_pitch).mul_add(cos_yaw, -cos_roll * sin_yaw),
+                (sin_roll * sin_pitch).mul_add(sin_yaw, cos_roll * cos_yaw),
+                sin_roll * cos_pitch,
+            ],
+            [
+                (cos_roll * sin_pitch).mul_add(cos_yaw, sin_roll * sin_yaw),
+                (cos_roll * sin_pitch).mul_add(sin_yaw, -sin_roll * cos_yaw),
+                cos_roll * cos_pitch,
+            ],
+        ])
+    }
+
+    /// Recovers the Euler angles of a world-to-entity rotation.
+    #[must_use]
+    pub fn orientation(&self) -> Orientation {
+        let m = &self.0;
+
+        Orientation {
+            psi: m[0][1].atan2(m[0][0]),
+            theta: -m[0][2].clamp(-1.0, 1.0).asin(),
+            phi: m[1][2].atan2(m[2][2]),
+        }
+    }
+
+    #[must_use]
+    pub fn transpose(&self) -> Self {
+        let m = &self.0;
+        Self(std::array::from_fn(|row| {
+            std::array::from_fn(|col| m[col][row])
+        }))
+    }
+
+    #[must_use]
+    pub fn apply(&self, v: Vector) -> Vector {
+        let row = |r: [f64; 3]| Vector::new(r[0], r[1], r[2]).dot(v);
+        Vector::new(row(self.0[0]), row(self.0[1]), row(self.0[2]))
+    }
+
+    /// Returns the rotation angle in radians between two rotations.
+    #[must_use]
+    pub fn angle_to(&self, other: &Self) -> f64 {
+        let relative = *self * other.transpose();
+        let trace = relative.0[0][0] + relative.0[1][1] + relative.0[2][2];
+        ((trace - 1.0) / 2.0).clamp(-1.0, 1.0).acos()
+    }
+
+    /// The outer product v vᵀ.
+    fn outer(v: Vector) -> Self {
+        let c = [v.x, v.y, v.z];
+        Self(std::array::from_fn(|row| {
+            std::array::from_fn(|col| c[row] * c[col])
+        }))
+    }
+
+    /// The skew-symmetric matrix Ω for which Ω u = v × u.
+    const fn skew(v: Vector) -> Self {
+        Self([[0.0, -v.z, v.y], [v.z, 0.0, -v.x], [-v.y, v.x, 0.0]])
+    }
+}
+
+impl Add for Matrix {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self(std::array::from_fn(|row| {
+            std::array::from_fn(|col| self.0[row][col] + other.0[row][col])
+        }))
+    }
+}
+
+impl Mul<f64> for Matrix {
+    type Output = Self;
+
+    fn mul(self, factor: f64) -> Self {
+        Self(self.0.map(|row| row.map(|value| value * factor)))
+    }
+}
+
+impl Mul for Matrix {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self(std::array::from_fn(|row| {
+            std::array::from_fn(|col| (0..3).map(|k| self.0[row][k] * other.0[k][col]).sum())
+        }))
+    }
+}
+
+/// The dead-reckoning model an entity asks receivers to extrapolate it with.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Model {
+    Other,
+    Static,
+    Fpw,
+    Rpw,
+    Rvw,
+    Fvw,
+    Fpb,
+    Rpb,
+    Rvb,
+    Fvb,
+}
+
+impl From<DeadReckoningAlgorithm> for Model {
+    fn from(algorithm: DeadReckoningAlgorithm) -> Self {
+        match algorithm {
+            DeadReckoningAlgorithm::Other => Self::Other,
+            DeadReckoningAlgorithm::StaticNonmovingEntity => Self::Static,
+            DeadReckoningAlgorithm::DRMConstantVelocityLowAccelerationLinearMotionEntity => {
+                Self::Fpw
+            }
+            DeadReckoningAlgorithm::DRMConstantVelocityLowAccelerationLinearMotionEntitywithExtrapolationofOrientation => {
+                Self::Rpw
+            }
+            DeadReckoningAlgorithm::DRMHighSpeedorManeuveringEntitywithExtrapolationofOrientation => {
+                Self::Rvw
+            }
+            DeadReckoningAlgorithm::DRMHighSpeedorManeuveringEntity => Self::Fvw,
+            DeadReckoningAlgorithm::DRMSimilartoFPWexceptinBodyCoordinates => Self::Fpb,
+            DeadReckoningAlgorithm::DRMSimilartoRPWexceptinBodyCoordinates => Self::Rpb,
+            DeadReckoningAlgorithm::DRMSimilartoRVWexceptinBodyCoordinates => Self::Rvb,
+            DeadReckoningAlgorithm::DRMSimilartoFVWexceptinBodyCoordinates => Self::Fvb,
+        }
+    }
+}
+
+impl Model {
+    /// Whether the model moves the entity at all.
+    #[must_use]
+    pub const fn moves(self) -> bool {
+        !matches!(self, Self::Other | Self::Static)
+    }
+
+    const fn rotates(self) -> bool {
+        matches!(self, Self::Rpw | Self::Rvw | Self::Rpb | Self::Rvb)
+    }
+
+    const fn accelerates(self) -> bool {
+        matches!(self, Self::Rvw | Self::Fvw | Self::Rvb | Self::Fvb)
+    }
+
+    const fn in_body_coordinates(self) -> bool {
+        matches!(self, Self::Fpb | Self::Rpb | Self::Rvb | Self::Fvb)
+    }
+}
+
+/// The motion an entity reported in its last Entity State PDU.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+pub struct Kinematics {
+    pub model: Model,
+    /// Location of the entity's centre in world coordinates, in metres
+    pub location: Vector,
+    pub orientation: Orientation,
+    /// Linear velocity in world coordinates, in metres per second
+    pub velocity: Vector,
+    /// Linear acceleration in metres per second squared, in world coordinates for the
+    /// world models and entity coordinates for the body models
+    pub acceleration: Vector,
+    /// Angular velocity about the entity axes, in radians per second
+    pub angular_velocity: Vector,
+}
+
+impl Kinematics {
+    /// Whether extrapolation changes the entity's location or orientation over time.
+    #[must_use]
+    pub fn is_moving(&self) -> bool {
+        let model = self.model;
+
+        model.moves()
+            && (self.velocity != Vector::ZERO
+                || (model.accelerates() && self.acceleration != Vector::ZERO)
+                || (model.rotates() && self.angular_velocity != Vector::ZERO))
+    }
+
+    /// Extrapolates the location and orientation `dt` seconds after the report.
+    #[must_use]
+    pub fn extrapolate(&self, dt: f64) -> (Vector, Orientation) {
+        let model = self.model;
+
+        if !model.moves() || dt <= 0.0 {
+            return (self.location, self.orientation);
+        }
+
+        let acceleration = if model.accelerates() {
+            self.acceleration
+        } else {
+            Vector::ZERO
+        };
+        let omega = if model.rotates() {
+            self.angular_velocity
+        } else {
+            Vector::ZERO
+        };
+
+        let location = if model.in_body_coordinates() {
+            let to_body = Matrix::from_orientation(self.orientation);
+            let (r1, r2) = rotation_integrals(omega, dt);
+            let body_velocity = to_body.apply(self.velocity);
+
+            self.location
+                + to_body
+                    .transpose()
+                    .apply(r1.apply(body_velocity) + r2.apply(acceleration))
+        } else {
+            self.location + self.velocity * dt + acceleration * (0.5 * dt * dt)
+        };
+
+        let orientation = if model.rotates() {
+            (rotation(omega, dt) * Matrix::from_orientation(self.orientation)).orientation()
+        } else {
+            self.orientation
+        };
+
+        (location, orientation)
+    }
+}
+
+/// The rotation an entity turning at `omega` undergoes in `dt` seconds (Annex E, DR matrix).
+fn rotation(omega: Vector, dt: f64) -> Matrix {
+    let speed = omega.magnitude();
+
+    if speed < MIN_ANGULAR_SPEED {
+        return Matrix::IDENTITY;
+    }
+
+    let angle = speed * dt;
+    let (sin, cos) = angle.sin_cos();
+
+    Matrix::outer(omega) * ((1.0 - cos) / (speed * speed))
+        + Matrix::IDENTITY * cos
+        + Matrix::skew(omega) * (-sin / speed)
+}
+
+/// The R1 and R2 matrices integrating body velocity and acceleration over `dt` seconds
+/// while the body turns at `omega`.
+fn rotation_integrals(omega: Vector, dt: f64) -> (Matrix, Matrix) {
+    let speed = omega.magnitude();
+
+    if speed < MIN_ANGULAR_SPEED {
+        return (Matrix::IDENTITY * dt, Matrix::IDENTITY * (0.5 * dt * dt));
+    }
+
+    let angle = speed * dt;
+    let (sin, cos) = angle.sin_cos();
+    let outer = Matrix::outer(omega);
+    let skew = Matrix::skew(omega);
+
+    let r1 = outer * ((angle - sin) / speed.powi(3))
+        + Matrix::IDENTITY * (sin / speed)
+        + skew * ((1.0 - cos) / speed.powi(2));
+
+    let r2 = outer
+        * ((0.5 * angle).mul_add(angle, -cos) - angle.mul_add(sin, -1.0))
+        * (1.0 / speed.powi(4))
+        + Matrix::IDENTITY * (angle.mul_add(sin, cos - 1.0) / speed.powi(2))
+        + skew * (angle.mul_add(-cos, sin) / speed.powi(3));
+
+    (r1, r2)
+}
@@ -0,0 +1,416 @@
+//! The registry of entities reported through Entity State PDUs.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use bytes::Buf;
+use open_dis_rust::common::data_types::dead_reckoning_parameters::DeadReckoningParameters;
+use open_dis_rust::common::data_types::{
+    AngularVelocity, EntityId, EntityType, EulerAngles, LinearAcceleration, LinearVelocity,
+    WorldCoordinate,
+};
+use open_dis_rust::common::enums::{ForceId, PduType};
+use open_dis_rust::common::{GenericHeader, PduHeader};
+use serde::Serialize;
+
+use crate::config::{DeadReckoning, Timeouts};
+use crate::core::{
+    Datagram, EntityIdentifier, Kinematics, Matrix, Monitor, Notice, Orientation, Placement,
+    Vector, now_ms, unix_ms,
+};
+
+/// Offset of the entity marking record within an Entity State PDU. The library's marking
+/// decoder does not consume the marking characters, so it and everything after it are read
+/// from the raw PDU instead.
+pub(crate) const MARKING_OFFSET: usize = 128;
+
+/// Length of the character set and the 11 marking characters
+pub(crate) const MARKING_LEN: usize = 12;
+
+/// Length of the PDU up to the end of the capabilities record
+pub(crate) const FIXED_LEN: usize = MARKING_OFFSET + MARKING_LEN + 4;
+
+/// Appearance bit set by a simulation when it deactivates one of its entities
+const DEACTIVATED: u32 = 1 << 23;
+
+/// The seven fields of a DIS entity type.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EntityTypeCode {
+    pub kind: u8,
+    pub domain: u8,
+    pub country: u16,
+    pub category: u8,
+    pub subcategory: u8,
+    pub specific: u8,
+    pub extra: u8,
+}
+
+impl From<EntityType> for EntityTypeCode {
+    fn from(entity_type: EntityType) -> Self {
+        Self {
+            kind: entity_type.kind as u8,
+            domain: entity_type.domain,
+            country: entity_type.country as u16,
+            category: entity_type.category,
+            subcategory: entity_type.subcategory,
+            specific: entity_type.specific,
+            extra: entity_type.extra,
+        }
+    }
+}
+
+impl From<WorldCoordinate> for Vector {
+    fn from(location: WorldCoordinate) -> Self {
+        Self::new(location.x, location.y, location.z)
+    }
+}
+
+impl From<LinearVelocity> for Vector {
+    fn from(velocity: LinearVelocity) -> Self {
+        Self::new(
+            f64::from(velocity.first_vector_component),
+            f64::from(velocity.second_vector_component),
+            f64::from(velocity.third_vector_component),
+        )
+    }
+}
+
+impl From<LinearAcceleration> for Vector {
+    fn from(acceleration: LinearAcceleration) -> Self {
+        Self::new(
+            f64::from(acceleration.first_vector_component),
+            f64::from(acceleration.second_vector_component),
+            f64::from(acceleration.third_vector_component),
+        )
+    }
+}
+
+impl From<AngularVelocity> for Vector {
+    fn from(rates: AngularVelocity) -> Self {
+        Self::new(
+            f64::from(rates.rate_about_x_axis),
+            f64::from(rates.rate_about_y_axis),
+            f64::from(rates.rate_about_z_axis),
+        )
+    }
+}
+
+impl From<EulerAngles> for Orientation {
+    fn from(angles: EulerAngles) -> Self {
+        Self {
+            psi: f64::from(angles.psi),
+            theta: f64::from(angles.theta),
+            phi: f64::from(angles.phi),
+        }
+    }
+}
+
+/// How far an entity's dead-reckoned state had drifted from the update that replaced it.
+#[derive(Serialize, Debug, Clone, Copy)]
+pub struct DeadReckoningError {
+    /// Distance between the extrapolated and reported locations, in metres
+    pub position_m: f64,
+    /// Angle between the extrapolated and reported orientations, in degrees
+    pub orientation_deg: f64,
+    /// Whether either error exceeded the scenario's thresholds
+    pub exceeded: bool,
+}
+
+/// The latest reported state of an entity.
+#[derive(Serialize, Debug, Clone)]
+pub struct EntityRecord {
+    pub id: EntityIdentifier,
+    /// Name of the force the entity belongs to
+    pub force: String,
+    pub entity_type: EntityTypeCode,
+    pub marking: String,
+    /// Motion reported in the last update
+    pub kinematics: Kinematics,
+    /// Location dead-reckoned to the time of the snapshot
+    pub extrapolated_location: Vector,
+    /// Orientation dead-reckoned to the time of the snapshot
+    pub extrapolated_orientation: Orientation,
+    /// The extrapolated location and orientation in geodetic terms
+    pub placement: Placement,
+    /// Error of the previous update's extrapolation, absent for the first update
+    pub dead_reckoning_error: Option<DeadReckoningError>,
+    pub appearance: u32,
+    pub capabilities: u32,
+    pub last_update_ms: u64,
+    /// Whether the entity has been silent for longer than the scenario's timeout
+    pub stale: bool,
+}
+
+impl EntityRecord {
+    /// Dead-reckons the reported motion to `at_ms` Unix milliseconds.
+    ///
+    /// Extrapolation runs from the time the update was received rather than its DIS
+    /// timestamp, so it does not depend on the sender's clock being synchronised.
+    #[must_use]
+    pub fn extrapolate(&self, at_ms: u64) -> (Vector, Orientation) {
+        // Updates are never more than a few minutes apart, well within f64 precision
+        #[allow(clippy::cast_precision_loss)]
+        let elapsed = at_ms.saturating_sub(self.last_update_ms) as f64 / 1000.0;
+
+        self.kinematics.extrapolate(elapsed)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct EntityRegistry {
+    entities: HashMap<EntityIdentifier, EntityRecord>,
+    thresholds: DeadReckoning,
+    timeouts: Timeouts,
+}
+
+impl EntityRegistry {
+    /// Returns every active entity ordered by ID, dead-reckoned to the current time.
+    ///
+    /// Stale entities are left where they were dead-reckoned to be when they timed out.
+    #[must_use]
+    pub fn entities(&self) -> Vec<EntityRecord> {
+        let now = now_ms().unwrap_or_default();
+        let timeout = self.timeouts.timeout_ms();
+
+        let mut entities: Vec<EntityRecord> = self
+            .entities
+            .values()
+            .map(|entity| {
+                let at = if entity.stale {
+                    now.min(entity.last_update_ms + timeout)
+                } else {
+                    now
+                };
+                let (location, orientation) = entity.extrapolate(at);
+                EntityRecord {
+                    extrapolated_location: location,
+                    extrapolated_orientation: orientation,
+                    placement: Placement::new(location, orientation),
+                    ..entity.clone()
+                }
+            })
+            .collect();
+        entities.sort_by_key(|e| (e.id.site_id, e.id.application_id, e.id.entity_id));
+        entities
+    }
+
+    /// Sets the tolerances later updates are checked against.
+    pub const fn set_thresholds(&mut self, thresholds: DeadReckoning) {
+        self.thresholds = thresholds;
+    }
+
+    /// Sets how long entities may go without an update before they are stale and removed.
+    pub const fn set_timeouts(&mut self, timeouts: Timeouts) {
+        self.timeouts = timeouts;
+    }
+
+    /// Compares where `previous` was dead-reckoned to be with the state `update` reports.
+    fn error(&self, previous: &EntityRecord, update: &EntityRecord) -> DeadReckoningError {
+        let (location, orientation) = previous.extrapolate(update.last_update_ms);
+        let reported = &update.kinematics;
+
+        let position_m = (location - reported.location).magnitude();
+        let orientation_deg = Matrix::from_orientation(orientation)
+            .angle_to(&Matrix::from_orientation(reported.orientation))
+            .to_degrees();
+
+        DeadReckoningError {
+            position_m,
+            orientation_deg,
+            exceeded: position_m > self.thresholds.position_threshold_m
+                || orientation_deg > self.thresholds.orientation_threshold_deg,
+        }
+    }
+}
+
+impl Monitor for EntityRegistry {
+    const EVENT: &'static str = "entities";
+
+    /// Moving entities are republished often enough for their extrapolation to look smooth
+    const PUBLISH_INTERVAL: Duration = Duration::from_millis(250);
+
+    type Snapshot = Vec<EntityRecord>;
+
+    fn observe(&mut self, datagram: &Datagram) -> bool {
+        let Some(mut record) = decode(&datagram.data) else {
+            return false;
+        };
+
+        if record.appearance & DEACTIVATED != 0 {
+            return self.entities.remove(&record.id).is_some();
+        }
+
+        record.last_update_ms = unix_ms(datagram.received_at).unwrap_or_default();
+
+        if let Some(previous) = self.entities.get(&record.id) {
+            let error = self.error(previous, &record);
+
+            if error.exceeded {
+                tracing::debug!(
+                    "Entity {} drifted {:.2} m and {:.2} deg from its dead-reckoned state",
+                    record.id,
+                    error.position_m,
+                    error.orientation_deg
+                );
+            }
+
+            record.dead_reckoning_error = Some(error);
+        }
+
+        self.entities.insert(record.id, record);
+
+        true
+    }
+
+    fn animated(&self) -> bool {
+        self.entities
+            .values()
+            .any(|e| !e.stale && e.kinematics.is_moving())
+    }
+
+    /// Marks entities silent for longer than the timeout as stale and removes those that
+    /// stay silent for the removal delay as well.
+    ///
+    /// Entities timing out together, typically because their application stopped, are
+    /// reported in a single notice.
+    fn expire(&mut self, now_ms: u64, notices: &mut Vec<Notice>) -> bool {
+        let timeout = self.timeouts.timeout_ms();
+        let removal = timeout + self.timeouts.removal_delay_ms;
+
+        let mut stale = Vec::new();
+        let mut removed = Vec::new();
+
+        self.entities.retain(|id, entity| {
+            let silent_ms = now_ms.saturating_sub(entity.last_update_ms);
+
+            if silent_ms >= removal {
+                removed.push(describe(entity));
+                return false;
+            }
+
+            if silent_ms >= timeout && !entity.stale {
+                entity.stale = true;
+                tracing::debug!("Entity {id} timed out after {silent_ms} ms");
+                stale.push(describe(entity));
+            }
+
+            true
+        });
+
+        if !stale.is_empty() {
+            notices.push(Notice::warning(format!(
+                "No update for {:.1} s from {}",
+                Duration::from_millis(timeout).as_secs_f64(),
+                summarise(&mut stale)
+            )));
+        }
+
+        if !removed.is_empty() {
+            notices.push(Notice::info(format!(
+                "Removed stale {}",
+                summarise(&mut removed)
+            )));
+        }
+
+        !stale.is_empty() || !removed.is_empty()
+    }
+
+    fn snapshot(&self) -> Self::Snapshot {
+        self.entities()
+    }
+}
+
+/// Identifies an entity by ID and, when it has one, its marking.
+fn describe(entity: &EntityRecord) -> String {
+    if entity.marking.is_empty() {
+        entity.id.to_string()
+    } else {
+        format!("{} ({})", entity.id, entity.marking)
+    }
+}
+
+/// Lists a few entity descriptions, counting the rest.
+fn summarise(entities: &mut [String]) -> String {
+    const LISTED: usize = 3;
+
+    entities.sort();
+
+    match entities {
+        [entity] => format!("entity {entity}"),
+        _ if entities.len() <= LISTED => format!("entities {}", entities.join(", ")),
+        _ => format!(
+            "{} entities including {}",
+            entities.len(),
+            entities[..LISTED].join(", ")
+        ),
+    }
+}
+
+/// Decodes the fixed part of an Entity State PDU, ignoring articulation parameters.
+fn decode(data: &[u8]) -> Option<EntityRecord> {
+    if data.len() < FIXED_LEN {
+        return None;
+    }
+
+    let mut buf = data;
+    let header = PduHeader::deserialize(&mut buf);
+
+    if header.pdu_type != PduType::EntityState {
+        return None;
+    }
+
+    let id = EntityId::deserialize(&mut buf);
+    let force = ForceId::deserialize(&mut buf);
+    buf.advance(1); // number of variable parameter records
+    let entity_type = EntityType::deserialize(&mut buf);
+    let _alternative_type = EntityType::deserialize(&mut buf);
+    let velocity = LinearVelocity::deserialize(&mut buf);
+    let location = WorldCoordinate::deserialize(&mut buf);
+    let orientation = EulerAngles::deserialize(&mut buf);
+    let appearance = buf.get_u32();
+    let dead_reckoning = DeadReckoningParameters::deserialize(&mut buf);
+
+    let kinematics = Kinematics {
+        model: dead_reckoning.dead_reckoning_algorithm.into(),
+        location: location.into(),
+        orientation: orientation.into(),
+        velocity: velocity.into(),
+        acceleration: dead_reckoning.entity_linear_acceleration.into(),
+        angular_velocity: dead_reckoning.entity_angular_velocity.into(),
+    };
+
+    let mut capabilities = &data[MARKING_OFFSET + MARKING_LEN..FIXED_LEN];
+
+    Some(EntityRecord {
+        id: id.into(),
+        force: format!("{force:?}"),
+        entity_type: entity_type.into(),
+        marking: marking(&data[MARKING_OFFSET + 1..MARKING_OFFSET + MARKING_LEN]),
+        kinematics,
+        extrapolated_location: kinematics.location,
+        extrapolated_orientation: kinematics.orientation,
+        placement: Placement::new(kinematics.location, kinematics.orientation),
+        dead_reckoning_error: None,
+        appearance,
+        capabilities: capabilities.get_u32(),
+        last_update_ms: 0,
+        stale: false,
+    })
+}
+
+/// Reads the marking characters up to the first NUL, replacing unprintable ones.
+pub(crate) fn marking(characters: &[u8]) -> String {
+    characters
+        .iter()
+        .take_while(|c| **c != 0)
+        .map(|c| {
+            if c.is_ascii_graphic() || *c == b' ' {
+                char::from(*c)
+            } else {
+                '?'
+            }
+        })
+        .collect::<String>()
+        .trim_end()
+        .to_string()
+}
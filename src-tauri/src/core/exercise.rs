@@ -0,0 +1,260 @@
+//! The exercise state driven by the global SIMAN commands.
+
+use std::fmt;
+use std::sync::Mutex;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use crate::core::StopReason;
+
+/// Name of the event carrying the exercise state
+const STATE_EVENT: &str = "simulation-state";
+
+/// The Global Controls commands sent without options, and what each asks of the exercise.
+/// `stop_freeze` sends an operator-chosen reason instead.
+pub const GLOBAL_COMMANDS: [(&str, ExerciseCommand); 5] = [
+    ("initialize", ExerciseCommand::Initialize),
+    ("startup", ExerciseCommand::StartResume),
+    ("standby", ExerciseCommand::StopFreeze(StopReason::Recess)),
+    (
+        "terminate",
+        ExerciseCommand::StopFreeze(StopReason::Termination),
+    ),
+    (
+        "reset",
+        ExerciseCommand::StopFreeze(StopReason::StopForRestart),
+    ),
+];
+
+/// Where the exercise stands, as far as its participants have acknowledged.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SimulationState {
+    #[default]
+    Uninitialized,
+    Initialized,
+    Running,
+    Paused,
+    Stopped,
+}
+
+impl fmt::Display for SimulationState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+/// A global SIMAN command, reduced to what it asks of the exercise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExerciseCommand {
+    /// Action Request to initialize internal parameters
+    Initialize,
+    StartResume,
+    StopFreeze(StopReason),
+}
+
+impl SimulationState {
+    /// The state the exercise moves to once `command` is acknowledged, or `None` if the
+    /// command cannot be sent from this state.
+    #[must_use]
+    pub const fn after(self, command: ExerciseCommand) -> Option<Self> {
+        let next = match command {
+            ExerciseCommand::Initialize => Self::Initialized,
+            ExerciseCommand::StartResume => Self::Running,
+            ExerciseCommand::StopFreeze(
+                StopReason::Termination | StopReason::AbortTrainingReturnToTacticalOperations,
+            ) => Self::Stopped,
+            // Participants return to their initial conditions and must be initialized again
+            ExerciseCommand::StopFreeze(StopReason::StopForReset | StopReason::StopForRestart) => {
+                Self::Uninitialized
+            }
+            ExerciseCommand::StopFreeze(
+                StopReason::Other
+                | StopReason::Recess
+                | StopReason::SystemFailure
+                | StopReason::SecurityViolation
+                | StopReason::EntityReconstitution,
+            ) => Self::Paused,
+        };
+
+        let valid = match next {
+            // Initializing again reaches participants that missed the first request
+            Self::Initialized => matches!(
+                self,
+                Self::Uninitialized | Self::Initialized | Self::Stopped
+            ),
+            Self::Running => matches!(self, Self::Initialized | Self::Paused),
+            Self::Paused => matches!(self, Self::Running),
+            Self::Stopped => matches!(self, Self::Initialized | Self::Running | Self::Paused),
+            Self::Uninitialized => !matches!(self, Self::Uninitialized),
+        };
+
+        if valid { Some(next) } else { None }
+    }
+
+    /// The state with the Global Controls commands that can be sent from it.
+    #[must_use]
+    pub fn status(self) -> SimulationStatus {
+        let mut commands: Vec<&'static str> = GLOBAL_COMMANDS
+            .iter()
+            .filter(|(_, command)| self.after(*command).is_some())
+            .map(|(name, _)| *name)
+            .collect();
+
+        // Every Stop/Freeze reason leads to the state of one of the shortcut commands
+        if commands
+            .iter()
+            .any(|name| matches!(*name, "standby" | "terminate" | "reset"))
+        {
+            commands.push("stop_freeze");
+        }
+
+        SimulationStatus {
+            state: self,
+            commands,
+        }
+    }
+}
+
+/// The exercise state as shown to the operator.
+#[derive(Serialize, Debug, Clone)]
+pub struct SimulationStatus {
+    pub state: SimulationState,
+    /// Names of the Global Controls commands valid from this state
+    pub commands: Vec<&'static str>,
+}
+
+/// Moves the exercise on once its participants have acknowledged `command`, and publishes
+/// the new state.
+///
+/// The exercise stays put if another command has since moved it to a state `command`
+/// cannot be sent from.
+///
+/// # Errors
+/// - Returns an error if the state lock is poisoned
+pub fn advance(
+    app: &AppHandle,
+    state: &Mutex<SimulationState>,
+    command: ExerciseCommand,
+) -> Result<SimulationState, String> {
+    let mut current = state.lock().map_err(|_| "Simulation state lock poisoned")?;
+
+    let Some(next) = current.after(command) else {
+        tracing::warn!("{command:?} was acknowledged after the exercise became {current}");
+        return Ok(*current);
+    };
+
+    *current = next;
+    drop(current);
+
+    tracing::info!("Exercise is now {next}");
+    app.emit(STATE_EVENT, next.status())
+        .unwrap_or_else(|e| tracing::error!("unable to emit {STATE_EVENT} event: {e}"));
+
+    Ok(next)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use SimulationState::{Initialized, Paused, Running, Stopped, Uninitialized};
+
+    const STATES: [SimulationState; 5] = [Uninitialized, Initialized, Running, Paused, Stopped];
+
+    /// Every command, with the state it leads to from each of `STATES` in turn
+    const TRANSITIONS: [(ExerciseCommand, [Option<SimulationState>; 5]); 11] = [
+        (
+            ExerciseCommand::Initialize,
+            [
+                Some(Initialized),
+                Some(Initialized),
+                None,
+                None,
+                Some(Initialized),
+            ],
+        ),
+        (
+            ExerciseCommand::StartResume,
+            [None, Some(Running), None, Some(Running), None],
+        ),
+        (
+            ExerciseCommand::StopFreeze(StopReason::Other),
+            [None, None, Some(Paused), None, None],
+        ),
+        (
+            ExerciseCommand::StopFreeze(StopReason::Recess),
+            [None, None, Some(Paused), None, None],
+        ),
+        (
+            ExerciseCommand::StopFreeze(StopReason::SystemFailure),
+            [None, None, Some(Paused), None, None],
+        ),
+        (
+            ExerciseCommand::StopFreeze(StopReason::SecurityViolation),
+            [None, None, Some(Paused), None, None],
+        ),
+        (
+            ExerciseCommand::StopFreeze(StopReason::EntityReconstitution),
+            [None, None, Some(Paused), None, None],
+        ),
+        (
+            ExerciseCommand::StopFreeze(StopReason::Termination),
+            [None, Some(Stopped), Some(Stopped), Some(Stopped), None],
+        ),
+        (
+            ExerciseCommand::StopFreeze(StopReason::AbortTrainingReturnToTacticalOperations),
+            [None, Some(Stopped), Some(Stopped), Some(Stopped), None],
+        ),
+        (
+            ExerciseCommand::StopFreeze(StopReason::StopForReset),
+            [
+                None,
+                Some(Uninitialized),
+                Some(Uninitialized),
+                Some(Uninitialized),
+                Some(Uninitialized),
+            ],
+        ),
+        (
+            ExerciseCommand::StopFreeze(StopReason::StopForRestart),
+            [
+                None,
+                Some(Uninitialized),
+                Some(Uninitialized),
+                Some(Uninitialized),
+                Some(Uninitialized),
+            ],
+        ),
+    ];
+
+    #[test]
+    fn every_transition() {
+        for (command, expected) in TRANSITIONS {
+            for (state, next) in STATES.into_iter().zip(expected) {
+                assert_eq!(state.after(command), next, "{command:?} from {state}");
+            }
+        }
+    }
+
+    #[test]
+    fn offered_commands_follow_transitions() {
+        let offered = |state: SimulationState| state.status().commands;
+
+        assert_eq!(offered(Uninitialized), ["initialize"]);
+        assert_eq!(
+            offered(Initialized),
+            ["initialize", "startup", "terminate", "reset", "stop_freeze"]
+        );
+        assert_eq!(
+            offered(Running),
+            ["standby", "terminate", "reset", "stop_freeze"]
+        );
+        assert_eq!(
+            offered(Paused),
+            ["startup", "terminate", "reset", "stop_freeze"]
+        );
+        assert_eq!(offered(Stopped), ["initialize", "reset", "stop_freeze"]);
+    }
+}
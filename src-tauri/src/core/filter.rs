@@ -0,0 +1,729 @@
+//! The filter language for narrowing the PDUs shown or replayed.
+//!
+//! A filter compares fields of each PDU with values, joined with `&&`, `||` and `!` and
+//! grouped with parentheses, for example `type == EntityState && site == 3 && force ==
+//! Opposing`. Enumerated fields take either the enumerator's name or its number, and the
+//! entity fields only match Entity State PDUs.
+
+use std::fmt;
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+use open_dis_rust::common::constants::PDU_HEADER_SIZE;
+use open_dis_rust::common::enums::{EntityKind, ForceId, PduType, ProtocolFamily};
+use serde::{Serialize, Serializer};
+
+use crate::core::{Direction, MARKING_LEN, MARKING_OFFSET, marking, originator, sender};
+
+/// Offsets within the PDU header
+const EXERCISE_OFFSET: usize = 1;
+const PDU_TYPE_OFFSET: usize = 2;
+const PROTOCOL_FAMILY_OFFSET: usize = 3;
+
+/// Offsets of the force ID and entity type within an Entity State PDU
+const FORCE_OFFSET: usize = PDU_HEADER_SIZE + 6;
+const ENTITY_TYPE_OFFSET: usize = PDU_HEADER_SIZE + 8;
+
+/// A field of a PDU that filters can compare.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Type,
+    Family,
+    Exercise,
+    Length,
+    Direction,
+    Site,
+    Application,
+    Entity,
+    Force,
+    Kind,
+    Domain,
+    Country,
+    Category,
+    Subcategory,
+    Specific,
+    Extra,
+    Marking,
+}
+
+impl Field {
+    const ALL: [(Self, &'static str); 17] = [
+        (Self::Type, "type"),
+        (Self::Family, "family"),
+        (Self::Exercise, "exercise"),
+        (Self::Length, "length"),
+        (Self::Direction, "direction"),
+        (Self::Site, "site"),
+        (Self::Application, "application"),
+        (Self::Entity, "entity"),
+        (Self::Force, "force"),
+        (Self::Kind, "kind"),
+        (Self::Domain, "domain"),
+        (Self::Country, "country"),
+        (Self::Category, "category"),
+        (Self::Subcategory, "subcategory"),
+        (Self::Specific, "specific"),
+        (Self::Extra, "extra"),
+        (Self::Marking, "marking"),
+    ];
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .find(|(_, field)| field.eq_ignore_ascii_case(name))
+            .map(|(field, _)| *field)
+    }
+
+    /// Names the enumerator `code` stands for in this field, if the field is enumerated
+    /// and the code is defined.
+    fn enumerator(self, code: u8) -> Option<String> {
+        let buf = &mut &[code][..];
+        let (name, value) = match self {
+            Self::Type => {
+                let value = PduType::deserialize(buf);
+                (format!("{value:?}"), value as u8)
+            }
+            Self::Family => {
+                let value = ProtocolFamily::deserialize(buf);
+                (format!("{value:?}"), value as u8)
+            }
+            Self::Force => {
+                let value = ForceId::deserialize(buf);
+                (format!("{value:?}"), value as u8)
+            }
+            Self::Kind => {
+                let value = EntityKind::deserialize(buf);
+                (format!("{value:?}"), value as u8)
+            }
+            Self::Direction => {
+                let direction = [Direction::Received, Direction::Sent].get(usize::from(code))?;
+                return Some(format!("{direction:?}"));
+            }
+            _ => return None,
+        };
+
+        // Undefined codes decode as the default enumerator
+        (value == code).then_some(name)
+    }
+
+    /// Finds the code of the enumerator called `name`, ignoring case.
+    fn code(self, name: &str) -> Option<u8> {
+        (0..=u8::MAX).find(|code| {
+            self.enumerator(*code)
+                .is_some_and(|enumerator| enumerator.eq_ignore_ascii_case(name))
+        })
+    }
+
+    const fn is_text(self) -> bool {
+        matches!(self, Self::Marking)
+    }
+
+    const fn is_enumerated(self) -> bool {
+        matches!(
+            self,
+            Self::Type | Self::Family | Self::Force | Self::Kind | Self::Direction
+        )
+    }
+
+    /// Reads the field from a PDU, if the PDU has it.
+    fn number(self, data: &[u8], direction: Direction) -> Option<u64> {
+        let byte = |offset: usize| data.get(offset).copied().map(u64::from);
+        let entity_state =
+            || (data.get(PDU_TYPE_OFFSET) == Some(&(PduType::EntityState as u8))).then_some(());
+
+        match self {
+            Self::Type => byte(PDU_TYPE_OFFSET),
+            Self::Family => byte(PROTOCOL_FAMILY_OFFSET),
+            Self::Exercise => byte(EXERCISE_OFFSET),
+            Self::Length => u64::try_from(data.len()).ok(),
+            Self::Direction => Some(match direction {
+                Direction::Received => 0,
+                Direction::Sent => 1,
+            }),
+            Self::Site => sender(data).map(|sender| u64::from(sender.site_id)),
+            Self::Application => sender(data).map(|sender| u64::from(sender.application_id)),
+            Self::Entity => originator(data).map(|entity| u64::from(entity.entity_id)),
+            Self::Force => entity_state().and_then(|()| byte(FORCE_OFFSET)),
+            Self::Kind => entity_state().and_then(|()| byte(ENTITY_TYPE_OFFSET)),
+            Self::Domain => entity_state().and_then(|()| byte(ENTITY_TYPE_OFFSET + 1)),
+            Self::Country => entity_state().and_then(|()| {
+                data.get(ENTITY_TYPE_OFFSET + 2..ENTITY_TYPE_OFFSET + 4)
+                    .map(|b| u64::from(u16::from_be_bytes([b[0], b[1]])))
+            }),
+            Self::Category => entity_state().and_then(|()| byte(ENTITY_TYPE_OFFSET + 4)),
+            Self::Subcategory => entity_state().and_then(|()| byte(ENTITY_TYPE_OFFSET + 5)),
+            Self::Specific => entity_state().and_then(|()| byte(ENTITY_TYPE_OFFSET + 6)),
+            Self::Extra => entity_state().and_then(|()| byte(ENTITY_TYPE_OFFSET + 7)),
+            Self::Marking => None,
+        }
+    }
+
+    fn text(self, data: &[u8]) -> Option<String> {
+        match self {
+            Self::Marking if data.get(PDU_TYPE_OFFSET) == Some(&(PduType::EntityState as u8)) => {
+                data.get(MARKING_OFFSET + 1..MARKING_OFFSET + MARKING_LEN)
+                    .map(marking)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    /// The text contains the value, ignoring case
+    Contains,
+}
+
+impl Operator {
+    const fn symbol(self) -> &'static str {
+        match self {
+            Self::Equal => "==",
+            Self::NotEqual => "!=",
+            Self::Less => "<",
+            Self::LessOrEqual => "<=",
+            Self::Greater => ">",
+            Self::GreaterOrEqual => ">=",
+            Self::Contains => "~",
+        }
+    }
+
+    fn compare<T: PartialOrd>(self, left: &T, right: &T) -> bool {
+        match self {
+            Self::Equal => left == right,
+            Self::NotEqual => left != right,
+            Self::Less => left < right,
+            Self::LessOrEqual => left <= right,
+            Self::Greater => left > right,
+            Self::GreaterOrEqual => left >= right,
+            Self::Contains => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Value {
+    Number(u64),
+    Text(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Expression {
+    Compare {
+        field: Field,
+        operator: Operator,
+        value: Value,
+    },
+    Not(Box<Self>),
+    And(Box<Self>, Box<Self>),
+    Or(Box<Self>, Box<Self>),
+}
+
+impl Expression {
+    fn matches(&self, data: &[u8], direction: Direction) -> bool {
+        match self {
+            Self::Compare {
+                field,
+                operator,
+                value,
+            } => match value {
+                // PDUs without the field never match, whatever the operator
+                Value::Number(value) => field
+                    .number(data, direction)
+                    .is_some_and(|actual| operator.compare(&actual, value)),
+                Value::Text(value) => field.text(data).is_some_and(|actual| match operator {
+                    Operator::Contains => actual.to_lowercase().contains(&value.to_lowercase()),
+                    _ => operator.compare(&actual.as_str(), &value.as_str()),
+                }),
+            },
+            Self::Not(expression) => !expression.matches(data, direction),
+            Self::And(left, right) => {
+                left.matches(data, direction) && right.matches(data, direction)
+            }
+            Self::Or(left, right) => {
+                left.matches(data, direction) || right.matches(data, direction)
+            }
+        }
+    }
+}
+
+/// A parsed filter expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Filter {
+    text: String,
+    expression: Expression,
+}
+
+impl Filter {
+    /// Parses a filter expression.
+    ///
+    /// # Errors
+    /// - Returns an error naming the column of the first problem if the expression is not
+    ///   a valid filter
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut parser = Parser {
+            tokens: tokenize(text)?.into_iter().peekable(),
+            end: text.chars().count() + 1,
+            depth: 0,
+        };
+
+        let expression = parser.or()?;
+
+        if let Some(token) = parser.tokens.next() {
+            return Err(format!(
+                "Unexpected {} at column {}",
+                token.kind, token.column
+            ));
+        }
+
+        Ok(Self {
+            text: text.trim().to_string(),
+            expression,
+        })
+    }
+
+    /// Parses an optional filter expression, treating a blank one as no filter.
+    ///
+    /// # Errors
+    /// - Returns an error if the expression is not a valid filter
+    pub fn parse_optional(text: Option<&str>) -> Result<Option<Self>, String> {
+        text.filter(|text| !text.trim().is_empty())
+            .map(Self::parse)
+            .transpose()
+    }
+
+    #[must_use]
+    pub fn matches(&self, data: &[u8], direction: Direction) -> bool {
+        self.expression.matches(data, direction)
+    }
+
+    /// The expression as written.
+    #[must_use]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+/// Filters are shown to the operator as written.
+impl Serialize for Filter {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.text)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TokenKind {
+    Word(String),
+    /// A number, with the digits as written for comparing with text
+    Number(u64, String),
+    Text(String),
+    Operator(Operator),
+    And,
+    Or,
+    Not,
+    Open,
+    Close,
+}
+
+impl fmt::Display for TokenKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Word(word) => write!(f, "`{word}`"),
+            Self::Number(_, text) => write!(f, "`{text}`"),
+            Self::Text(text) => write!(f, "{text:?}"),
+            Self::Operator(operator) => write!(f, "`{}`", operator.symbol()),
+            Self::And => write!(f, "`&&`"),
+            Self::Or => write!(f, "`||`"),
+            Self::Not => write!(f, "`!`"),
+            Self::Open => write!(f, "`(`"),
+            Self::Close => write!(f, "`)`"),
+        }
+    }
+}
+
+struct Token {
+    kind: TokenKind,
+    /// Column of the token's first character, counting from 1
+    column: usize,
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars: Peekable<CharIndices<'_>> = text.char_indices().peekable();
+    let mut column = 0;
+
+    while let Some((_, c)) = chars.next() {
+        column += 1;
+        let start = column;
+        let mut next_is = |expected: char| {
+            let matched = chars.next_if(|(_, c)| *c == expected).is_some();
+            column += usize::from(matched);
+            matched
+        };
+
+        let kind = match c {
+            c if c.is_whitespace() => continue,
+            '(' => TokenKind::Open,
+            ')' => TokenKind::Close,
+            '~' => TokenKind::Operator(Operator::Contains),
+            '&' if next_is('&') => TokenKind::And,
+            '|' if next_is('|') => TokenKind::Or,
+            '=' if next_is('=') => TokenKind::Operator(Operator::Equal),
+            '!' if next_is('=') => TokenKind::Operator(Operator::NotEqual),
+            '!' => TokenKind::Not,
+            '<' if next_is('=') => TokenKind::Operator(Operator::LessOrEqual),
+            '<' => TokenKind::Operator(Operator::Less),
+            '>' if next_is('=') => TokenKind::Operator(Operator::GreaterOrEqual),
+            '>' => TokenKind::Operator(Operator::Greater),
+            '"' => {
+                let mut quoted = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, '"')) => break,
+                        Some((_, c)) => quoted.push(c),
+                        None => return Err(format!("Unclosed quote at column {start}")),
+                    }
+                    column += 1;
+                }
+                column += 1;
+                TokenKind::Text(quoted)
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut word = String::from(c);
+                while let Some((_, c)) = chars.next_if(|(_, c)| c.is_alphanumeric() || *c == '_') {
+                    word.push(c);
+                    column += 1;
+                }
+                match number(&word) {
+                    Some(number) => TokenKind::Number(number, word),
+                    None => TokenKind::Word(word),
+                }
+            }
+            c => return Err(format!("Unexpected `{c}` at column {start}")),
+        };
+
+        tokens.push(Token {
+            kind,
+            column: start,
+        });
+    }
+
+    Ok(tokens)
+}
+
+/// Reads a decimal number or a hexadecimal one with a `0x` prefix.
+fn number(word: &str) -> Option<u64> {
+    word.strip_prefix("0x")
+        .or_else(|| word.strip_prefix("0X"))
+        .map_or_else(
+            || word.parse().ok(),
+            |hex| u64::from_str_radix(hex, 16).ok(),
+        )
+}
+
+/// Deepest nesting of `!` and parentheses a filter may use, so that parsing cannot
+/// exhaust the stack
+const MAX_DEPTH: usize = 64;
+
+/// Parses tokens by recursive descent, with `!` binding tightest and `||` loosest.
+struct Parser<I: Iterator<Item = Token>> {
+    tokens: Peekable<I>,
+    /// Column just past the end of the expression, for errors at the end
+    end: usize,
+    /// `!` and `(` enclosing the token being parsed
+    depth: usize,
+}
+
+impl<I: Iterator<Item = Token>> Parser<I> {
+    fn or(&mut self) -> Result<Expression, String> {
+        let mut expression = self.and()?;
+        while self
+            .tokens
+            .next_if(|token| token.kind == TokenKind::Or)
+            .is_some()
+        {
+            expression = Expression::Or(Box::new(expression), Box::new(self.and()?));
+        }
+        Ok(expression)
+    }
+
+    fn and(&mut self) -> Result<Expression, String> {
+        let mut expression = self.not()?;
+        while self
+            .tokens
+            .next_if(|token| token.kind == TokenKind::And)
+            .is_some()
+        {
+            expression = Expression::And(Box::new(expression), Box::new(self.not()?));
+        }
+        Ok(expression)
+    }
+
+    fn not(&mut self) -> Result<Expression, String> {
+        if let Some(not) = self.tokens.next_if(|token| token.kind == TokenKind::Not) {
+            self.enter(&not)?;
+            let expression = Expression::Not(Box::new(self.not()?));
+            self.depth -= 1;
+            return Ok(expression);
+        }
+
+        if let Some(open) = self.tokens.next_if(|token| token.kind == TokenKind::Open) {
+            self.enter(&open)?;
+            let expression = self.or()?;
+            self.depth -= 1;
+            return match self.tokens.next() {
+                Some(Token {
+                    kind: TokenKind::Close,
+                    ..
+                }) => Ok(expression),
+                _ => Err(format!("Unclosed `(` at column {}", open.column)),
+            };
+        }
+
+        self.comparison()
+    }
+
+    /// Descends into the operand of a `!` or the inside of a `(`.
+    fn enter(&mut self, token: &Token) -> Result<(), String> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(format!(
+                "{} at column {} nests deeper than {MAX_DEPTH} levels",
+                token.kind, token.column
+            ));
+        }
+        Ok(())
+    }
+
+    fn comparison(&mut self) -> Result<Expression, String> {
+        let (name, column) = match self.tokens.next() {
+            Some(Token {
+                kind: TokenKind::Word(name),
+                column,
+            }) => (name, column),
+            Some(token) => {
+                return Err(format!(
+                    "Expected a field at column {} but found {}",
+                    token.column, token.kind
+                ));
+            }
+            None => return Err(format!("Expected a field at column {}", self.end)),
+        };
+
+        let field = Field::from_name(&name).ok_or_else(|| {
+            let fields: Vec<_> = Field::ALL.iter().map(|(_, name)| *name).collect();
+            format!(
+                "Unknown field `{name}` at column {column}; filters can compare {}",
+                fields.join(", ")
+            )
+        })?;
+
+        let operator = match self.tokens.next() {
+            Some(Token {
+                kind: TokenKind::Operator(operator),
+                ..
+            }) => operator,
+            Some(token) => {
+                return Err(format!(
+                    "Expected a comparison after `{name}` at column {} but found {}",
+                    token.column, token.kind
+                ));
+            }
+            None => {
+                return Err(format!(
+                    "Expected a comparison after `{name}` at column {}",
+                    self.end
+                ));
+            }
+        };
+
+        let Some(token) = self.tokens.next() else {
+            return Err(format!("Expected a value at column {}", self.end));
+        };
+
+        let value = match (field.is_text(), token.kind) {
+            (true, TokenKind::Word(text) | TokenKind::Text(text) | TokenKind::Number(_, text)) => {
+                Value::Text(text)
+            }
+            (false, TokenKind::Number(number, _)) => Value::Number(number),
+            (false, TokenKind::Word(word)) if field.is_enumerated() => field
+                .code(&word)
+                .map(|code| Value::Number(u64::from(code)))
+                .ok_or_else(|| format!("Unknown {name} `{word}` at column {}", token.column))?,
+            (_, kind) => {
+                return Err(format!(
+                    "Expected a value for `{name}` at column {} but found {kind}",
+                    token.column
+                ));
+            }
+        };
+
+        match (field.is_text(), operator) {
+            (true, Operator::Equal | Operator::NotEqual | Operator::Contains)
+            | (
+                false,
+                Operator::Equal
+                | Operator::NotEqual
+                | Operator::Less
+                | Operator::LessOrEqual
+                | Operator::Greater
+                | Operator::GreaterOrEqual,
+            ) => Ok(Expression::Compare {
+                field,
+                operator,
+                value,
+            }),
+            _ => Err(format!(
+                "`{name}` cannot be compared with `{}` at column {column}",
+                operator.symbol()
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::entities::FIXED_LEN;
+
+    use super::*;
+
+    /// An Entity State PDU from site 3 in exercise 1, for an opposing entity marked `007`.
+    fn entity_state() -> Vec<u8> {
+        let mut data = vec![0; FIXED_LEN];
+        data[0] = 7;
+        data[EXERCISE_OFFSET] = 1;
+        data[PDU_TYPE_OFFSET] = PduType::EntityState as u8;
+        data[PROTOCOL_FAMILY_OFFSET] = ProtocolFamily::EntityInformation as u8;
+        data[8..10].copy_from_slice(
+            &u16::try_from(FIXED_LEN)
+                .expect("a PDU length")
+                .to_be_bytes(),
+        );
+        data[PDU_HEADER_SIZE..PDU_HEADER_SIZE + 6].copy_from_slice(&[0, 3, 0, 4, 0, 5]);
+        data[FORCE_OFFSET] = ForceId::Opposing as u8;
+        data[ENTITY_TYPE_OFFSET] = EntityKind::Platform as u8;
+        data[MARKING_OFFSET] = 1;
+        data[MARKING_OFFSET + 1..MARKING_OFFSET + 4].copy_from_slice(b"007");
+        data
+    }
+
+    fn matches(text: &str, data: &[u8]) -> bool {
+        Filter::parse(text)
+            .expect("a valid filter")
+            .matches(data, Direction::Received)
+    }
+
+    fn error(text: &str) -> String {
+        Filter::parse(text).expect_err("an invalid filter")
+    }
+
+    #[test]
+    fn and_binds_tighter_than_or() {
+        let data = entity_state();
+        assert!(matches(
+            "exercise == 1 || exercise == 2 && site == 9",
+            &data
+        ));
+        assert!(matches("exercise == 2 && site == 9 || site == 3", &data));
+        assert!(!matches(
+            "exercise == 2 || exercise == 1 && site == 9",
+            &data
+        ));
+    }
+
+    #[test]
+    fn parentheses_group() {
+        let data = entity_state();
+        assert!(!matches(
+            "(exercise == 1 || exercise == 2) && site == 9",
+            &data
+        ));
+        assert!(matches(
+            "exercise == 2 && (site == 9 || site == 3) || entity == 5",
+            &data
+        ));
+        assert!(matches("((site == 3))", &data));
+        assert!(error("(site == 3").contains("Unclosed `(` at column 1"));
+        assert!(error("site == 3)").contains("Unexpected `)` at column 10"));
+    }
+
+    #[test]
+    fn not_negates() {
+        let data = entity_state();
+        assert!(!matches("!site == 3", &data));
+        assert!(matches("!!site == 3", &data));
+        assert!(matches("!site == 3 || application == 4", &data));
+        assert!(!matches("!(site == 3 || application == 9)", &data));
+        assert!(matches("site != 9", &data));
+    }
+
+    #[test]
+    fn compares_numbers_and_enumerators() {
+        let data = entity_state();
+        assert!(matches("type == EntityState && family == 1", &data));
+        assert!(matches("force == opposing && kind == Platform", &data));
+        assert!(matches("length >= 144 && length <= 0x90", &data));
+        assert!(matches("direction == Received", &data));
+        assert!(!matches("exercise > 1 || exercise < 1", &data));
+    }
+
+    #[test]
+    fn entity_fields_only_match_entity_state() {
+        let mut data = entity_state();
+        data[PDU_TYPE_OFFSET] = PduType::Fire as u8;
+        assert!(!matches("force == Opposing", &data));
+        assert!(!matches("force != Opposing", &data));
+        assert!(!matches("marking == 007", &data));
+        assert!(matches("site == 3", &data));
+    }
+
+    #[test]
+    fn markings_compare_as_written() {
+        let data = entity_state();
+        assert!(matches("marking == 007", &data));
+        assert!(matches("marking == \"007\"", &data));
+        assert!(matches("marking ~ 07", &data));
+        assert!(!matches("marking == 7", &data));
+        assert!(!matches("marking != 007", &data));
+    }
+
+    #[test]
+    fn unknown_names_are_rejected() {
+        assert!(error("speed == 3").starts_with("Unknown field `speed` at column 1"));
+        assert!(error("force == Purple").contains("Unknown force `Purple` at column 10"));
+        assert!(error("type == 0x").contains("Unknown type `0x`"));
+        assert!(error("site == 3 & site == 4").contains("Unexpected `&` at column 11"));
+        assert!(error("marking == \"007").contains("Unclosed quote at column 12"));
+    }
+
+    #[test]
+    fn mismatched_types_are_rejected() {
+        assert!(error("site == abc").contains("Expected a value for `site`"));
+        assert!(error("site == \"3\"").contains("Expected a value for `site`"));
+        assert!(error("site ~ 3").contains("`site` cannot be compared with `~`"));
+        assert!(error("marking < abc").contains("`marking` cannot be compared with `<`"));
+        assert!(error("marking == (").contains("Expected a value for `marking`"));
+    }
+
+    #[test]
+    fn incomplete_filters_are_rejected() {
+        assert!(error("").contains("Expected a field at column 1"));
+        assert!(error("site").contains("Expected a comparison after `site` at column 5"));
+        assert!(error("site ==").contains("Expected a value at column 8"));
+        assert!(error("site == 3 &&").contains("Expected a field at column 13"));
+    }
+
+    #[test]
+    fn nesting_is_limited() {
+        let nested = |depth: usize| format!("{}site == 3{}", "(".repeat(depth), ")".repeat(depth));
+        assert!(matches(&nested(MAX_DEPTH), &entity_state()));
+        assert!(error(&nested(MAX_DEPTH + 1)).contains("nests deeper than"));
+        assert!(error(&format!("{}site == 3", "!".repeat(100_000))).contains("nests deeper than"));
+    }
+}
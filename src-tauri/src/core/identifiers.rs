@@ -0,0 +1,119 @@
+use std::fmt;
+
+use open_dis_rust::common::constants::{ALL_APPLIC, ALL_ENTITIES, ALL_SITES, NO_ENTITY};
+use open_dis_rust::common::data_types::{EntityId, SimulationAddress};
+use serde::{Deserialize, Serialize};
+
+/// A DIS simulation address (site and application) that can cross the Tauri boundary.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ApplicationAddress {
+    pub site_id: u16,
+    pub application_id: u16,
+}
+
+/// A DIS entity identifier that can cross the Tauri boundary.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EntityIdentifier {
+    pub site_id: u16,
+    pub application_id: u16,
+    pub entity_id: u16,
+}
+
+/// The simulations a SIMAN command is addressed to.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum CommandTarget {
+    All,
+    Site { site_id: u16 },
+    Application(ApplicationAddress),
+    Entity(EntityIdentifier),
+}
+
+impl CommandTarget {
+    /// Returns the receiving entity ID, using the DIS "all" wildcard for unspecified fields.
+    #[must_use]
+    pub const fn receiving_id(&self) -> EntityId {
+        match *self {
+            Self::All => EntityId::new(ALL_SITES, ALL_APPLIC, ALL_ENTITIES),
+            Self::Site { site_id } => EntityId::new(site_id, ALL_APPLIC, ALL_ENTITIES),
+            // Addressed to the simulation application itself rather than its entities
+            Self::Application(address) => {
+                EntityId::new(address.site_id, address.application_id, NO_ENTITY)
+            }
+            Self::Entity(id) => EntityId::new(id.site_id, id.application_id, id.entity_id),
+        }
+    }
+
+    /// Returns whether a reply from `entity` falls within this target.
+    #[must_use]
+    pub fn contains(&self, entity: EntityIdentifier) -> bool {
+        match *self {
+            Self::All => true,
+            Self::Site { site_id } => entity.site_id == site_id,
+            Self::Application(address) => entity.application() == address,
+            Self::Entity(id) => entity == id,
+        }
+    }
+}
+
+impl EntityIdentifier {
+    #[must_use]
+    pub const fn application(&self) -> ApplicationAddress {
+        ApplicationAddress {
+            site_id: self.site_id,
+            application_id: self.application_id,
+        }
+    }
+}
+
+impl From<SimulationAddress> for ApplicationAddress {
+    fn from(address: SimulationAddress) -> Self {
+        Self {
+            site_id: address.site_id,
+            application_id: address.application_id,
+        }
+    }
+}
+
+impl From<EntityId> for EntityIdentifier {
+    fn from(id: EntityId) -> Self {
+        Self {
+            site_id: id.simulation_address.site_id,
+            application_id: id.simulation_address.application_id,
+            entity_id: id.entity_id,
+        }
+    }
+}
+
+impl From<EntityIdentifier> for EntityId {
+    fn from(id: EntityIdentifier) -> Self {
+        Self::new(id.site_id, id.application_id, id.entity_id)
+    }
+}
+
+impl fmt::Display for ApplicationAddress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.site_id, self.application_id)
+    }
+}
+
+impl fmt::Display for EntityIdentifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}",
+            self.site_id, self.application_id, self.entity_id
+        )
+    }
+}
+
+impl fmt::Display for CommandTarget {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::All => write!(f, "all simulations"),
+            Self::Site { site_id } => write!(f, "site {site_id}"),
+            Self::Application(address) => write!(f, "application {address}"),
+            Self::Entity(id) => write!(f, "entity {id}"),
+        }
+    }
+}
@@ -0,0 +1,2326 @@
+//! Decoding of single PDUs into a tree of fields tied to the bytes they were read from.
+//!
+//! Each PDU type is read against an explicit layout of its fields, following IEEE
+//! 1278.1-2012, so every field is placed by the bytes it is read from. Decoding stops at
+//! the first field that does not fit within the PDU, keeping the fields read before it,
+//! and any bytes after the last field read are shown as undecoded.
+
+use std::ops::Range;
+
+use open_dis_rust::common::constants::PDU_HEADER_SIZE;
+use open_dis_rust::common::enums::{self, PduType};
+use serde::Serialize;
+
+use crate::core::{DATUM_ALIGNMENT_BYTES, Geodetic, Vector, marking, pdu_type_name};
+
+/// Offset of the PDU type within the PDU header
+const PDU_TYPE_OFFSET: usize = 2;
+
+/// Offset of the PDU length within the PDU header
+const LENGTH_OFFSET: usize = 8;
+
+/// Locations nearer the centre of the earth than this many metres are left unconverted
+const MIN_GEOCENTRIC_RADIUS_M: f64 = 1e6;
+
+/// One decoded field, with the fields of any record it holds.
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct Field {
+    pub name: String,
+    /// Decoded value, or a summary of a record such as `1:2:3` for an entity ID
+    pub value: String,
+    /// Offset of the field's first byte within the PDU, if its position is known
+    pub offset: Option<usize>,
+    /// Number of bytes the field spans, zero for fields not read from the PDU
+    pub length: usize,
+    /// Where a world coordinate lies, for display in the operator's chosen format
+    pub location: Option<Geodetic>,
+    pub mgrs: Option<String>,
+    pub children: Vec<Self>,
+    /// The field's bytes as a big-endian unsigned integer
+    #[serde(skip)]
+    raw: Option<u64>,
+}
+
+impl Field {
+    fn new(name: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            value: value.into(),
+            ..Self::default()
+        }
+    }
+
+    /// A field read from `span` of `data`.
+    fn read(
+        name: impl Into<String>,
+        value: impl Into<String>,
+        data: &[u8],
+        span: Range<usize>,
+    ) -> Self {
+        Self {
+            raw: data.get(span.clone()).and_then(big_endian),
+            offset: Some(span.start),
+            length: span.len(),
+            ..Self::new(name, value)
+        }
+    }
+
+    /// A record spanning the bytes of all its children.
+    fn record(name: impl Into<String>, value: impl Into<String>, children: Vec<Self>) -> Self {
+        let start = children.iter().filter_map(|child| child.offset).min();
+        let end = children.iter().filter_map(Self::end).max();
+
+        Self {
+            offset: start,
+            length: end.zip(start).map_or(0, |(end, start)| end - start),
+            children,
+            ..Self::new(name, value)
+        }
+    }
+
+    fn end(&self) -> Option<usize> {
+        self.offset.map(|offset| offset + self.length)
+    }
+
+    fn child(&self, name: &str) -> Option<&Self> {
+        self.children.iter().find(|child| child.name == name)
+    }
+
+    /// The fields holding values rather than records, in order.
+    fn leaves(&self) -> Vec<&Self> {
+        if self.children.is_empty() {
+            vec![self]
+        } else {
+            self.children.iter().flat_map(Self::leaves).collect()
+        }
+    }
+}
+
+/// A PDU decoded for the inspector.
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct Inspection {
+    /// PDU type named in the header, such as `EntityState (1)`
+    pub pdu_type: String,
+    pub data: Vec<u8>,
+    pub fields: Vec<Field>,
+    /// Bytes decoded from the start of the PDU; any after them could not be decoded
+    pub decoded_len: usize,
+    /// Problems met while decoding, such as a PDU shorter than its layout
+    pub warnings: Vec<String>,
+}
+
+/// Decodes a PDU into its fields, placing each within the PDU.
+///
+/// PDU types without a layout are decoded as far as their header.
+#[must_use]
+pub fn inspect(data: &[u8]) -> Inspection {
+    let mut inspection = Inspection {
+        data: data.to_vec(),
+        ..Inspection::default()
+    };
+
+    let Some(&code) = data
+        .get(PDU_TYPE_OFFSET)
+        .filter(|_| data.len() >= PDU_HEADER_SIZE)
+    else {
+        inspection.pdu_type = "Unknown".into();
+        inspection.warnings.push(format!(
+            "{} bytes is too short for a PDU header",
+            data.len()
+        ));
+        return inspection;
+    };
+    inspection.pdu_type = pdu_type_name(code);
+
+    let mut reader = Reader { data, offset: 0 };
+    let mut fields = Vec::new();
+
+    if let Err(e) = reader
+        .field("header", HEADER, 0, &mut fields)
+        .and_then(|()| decode_body(code, &mut reader, &mut fields))
+    {
+        inspection.warnings.push(e);
+    }
+
+    inspection.decoded_len = reader.offset;
+
+    if inspection.decoded_len < data.len() {
+        let trailing = data.len() - inspection.decoded_len;
+        fields.push(Field {
+            offset: Some(inspection.decoded_len),
+            length: trailing,
+            ..Field::new("undecoded", format!("{trailing} trailing bytes"))
+        });
+    }
+
+    inspection.fields = fields;
+    inspection
+}
+
+/// Decodes the body of the PDU following its header, adding its fields to `fields`.
+///
+/// # Errors
+/// - Returns an error if the length in the header does not fit the PDU, the PDU type has
+///   no layout, or the PDU ends before its layout does
+fn decode_body(code: u8, reader: &mut Reader<'_>, fields: &mut Vec<Field>) -> Result<(), String> {
+    let data = reader.data;
+    let stated_len = usize::from(u16::from_be_bytes([
+        data[LENGTH_OFFSET],
+        data[LENGTH_OFFSET + 1],
+    ]));
+    if !(PDU_HEADER_SIZE..=data.len()).contains(&stated_len) {
+        return Err(format!(
+            "The header gives a length of {stated_len} bytes for a {} byte PDU, so only the \
+             header is decoded",
+            data.len()
+        ));
+    }
+
+    let layout = body(PduType::deserialize(&mut &[code][..])).ok_or_else(|| {
+        format!(
+            "{} PDUs are not supported, so only the header is decoded",
+            pdu_type_name(code)
+        )
+    })?;
+
+    // Bytes after the length the header states are not part of the PDU
+    reader.data = &data[..stated_len];
+    reader.record(layout, fields)
+}
+
+/// Reads fields from a PDU by their layouts.
+struct Reader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl Reader<'_> {
+    fn take(&mut self, name: &str, len: usize) -> Result<Range<usize>, String> {
+        let end = self.offset.saturating_add(len);
+        if end > self.data.len() {
+            return Err(format!(
+                "The PDU ends {} bytes short of the end of `{name}`",
+                end - self.data.len()
+            ));
+        }
+
+        let span = self.offset..end;
+        self.offset = end;
+        Ok(span)
+    }
+
+    /// Reads the fields of a record into `fields`, keeping those read before any error.
+    fn record(&mut self, items: &[Item], fields: &mut Vec<Field>) -> Result<(), String> {
+        let start = self.offset;
+        items
+            .iter()
+            .try_for_each(|&(name, layout)| self.field(name, layout, start, fields))
+    }
+
+    /// Reads one field of a record that started at `start`, adding it to `fields`, the
+    /// fields of the record read so far.
+    fn field(
+        &mut self,
+        name: &str,
+        layout: Layout,
+        start: usize,
+        fields: &mut Vec<Field>,
+    ) -> Result<(), String> {
+        match layout {
+            Layout::Unsigned(len)
+            | Layout::Signed(len)
+            | Layout::Float(len)
+            | Layout::Enumerated(len, _)
+            | Layout::Bits(len)
+            | Layout::Text(len)
+            | Layout::Octets(len) => {
+                let span = self.take(name, len)?;
+                let value = layout.describe(&self.data[span.clone()]);
+                fields.push(Field::read(name, value, self.data, span));
+            }
+            Layout::Bytes(counts, unit_bits) => {
+                let len = counts
+                    .iter()
+                    .try_fold(unit_bits, |bits, count| {
+                        let count = usize::try_from(value_of(fields, count)).ok()?;
+                        bits.checked_mul(count)
+                    })
+                    .map_or(usize::MAX, |bits| bits.div_ceil(8));
+                let span = self.take(name, len)?;
+                let value = describe_bytes(&self.data[span.clone()]);
+                fields.push(Field::read(name, value, self.data, span));
+            }
+            Layout::Align(multiple) => {
+                let read = self.offset - start;
+                let len = read.next_multiple_of(multiple) - read;
+                if len > 0 {
+                    let span = self.take(name, len)?;
+                    let value = Layout::Bits(len).describe(&self.data[span.clone()]);
+                    fields.push(Field::read(name, value, self.data, span));
+                }
+            }
+            Layout::Record(summary, items) => {
+                let mut children = Vec::new();
+                let result = self.record(items, &mut children);
+                // A record cut short keeps the fields read, unless it ends before them all
+                if !children.is_empty() {
+                    fields.push(summary.apply(Field::record(name, "", children)));
+                }
+                result?;
+            }
+            Layout::List(count, item) => {
+                let count = value_of(fields, count);
+                let mut children = Vec::new();
+                let result = (0..count).try_for_each(|i| {
+                    self.field(&format!("[{i}]"), *item, self.offset, &mut children)
+                });
+                fields.push(Field::record(name, format!("{count} items"), children));
+                result?;
+            }
+            Layout::Remaining(item) => {
+                let mut children = Vec::new();
+                let mut result = Ok(());
+                while result.is_ok() && self.offset < self.data.len() {
+                    let name = format!("[{}]", children.len());
+                    result = self.field(&name, *item, self.offset, &mut children);
+                }
+                let count = children.len();
+                fields.push(Field::record(name, format!("{count} items"), children));
+                result?;
+            }
+            Layout::Sized(summary, items, length, unit_bits) => {
+                let record_start = self.offset;
+                let mut children = Vec::new();
+                let result = self.record(items, &mut children).and_then(|()| {
+                    let stated = usize::try_from(value_of(&children, length))
+                        .ok()
+                        .and_then(|units| units.checked_mul(unit_bits))
+                        .map_or(usize::MAX, |bits| bits / 8);
+                    let read = self.offset - record_start;
+                    if read > stated {
+                        return Err(format!(
+                            "`{name}` runs past the {stated} bytes its `{length}` gives"
+                        ));
+                    }
+                    if read < stated {
+                        let span = self.take(name, stated - read)?;
+                        let value = describe_bytes(&self.data[span.clone()]);
+                        children.push(Field::read(
+                            "record_specific_fields",
+                            value,
+                            self.data,
+                            span,
+                        ));
+                    }
+                    Ok(())
+                });
+                if !children.is_empty() {
+                    fields.push(summary.apply(Field::record(name, "", children)));
+                }
+                result?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The value of the field called `name` among `fields`, such as the count of a list.
+fn value_of(fields: &[Field], name: &str) -> u64 {
+    fields
+        .iter()
+        .find(|field| field.name == name)
+        .and_then(|field| field.raw)
+        .unwrap_or_default()
+}
+
+fn big_endian(bytes: &[u8]) -> Option<u64> {
+    (bytes.len() <= 8).then(|| {
+        bytes
+            .iter()
+            .fold(0, |value, byte| value << 8 | u64::from(*byte))
+    })
+}
+
+/// Shows bytes as text if they are printable, otherwise as hex.
+fn describe_bytes(bytes: &[u8]) -> String {
+    let text = bytes.strip_suffix(&[0]).unwrap_or(bytes);
+
+    if !text.is_empty() && text.iter().all(|c| c.is_ascii_graphic() || *c == b' ') {
+        format!("{:?}", String::from_utf8_lossy(text))
+    } else {
+        bytes
+            .iter()
+            .map(|byte| format!("{byte:02X}"))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// A field of a layout and how it is laid out.
+type Item = (&'static str, Layout);
+
+/// How a field is laid out in a PDU.
+#[derive(Debug, Clone, Copy)]
+enum Layout {
+    /// An unsigned integer of this many bytes
+    Unsigned(usize),
+    /// A signed integer of this many bytes
+    Signed(usize),
+    /// A floating point number of this many bytes
+    Float(usize),
+    /// A code of this many bytes from an enumeration
+    Enumerated(usize, Enumeration),
+    /// Flags, packed fields or padding of this many bytes, shown in hex
+    Bits(usize),
+    /// Characters, ending at the first null
+    Text(usize),
+    /// Bytes of no further structure
+    Octets(usize),
+    /// Bytes numbering the product of the earlier fields named, in units of this many bits
+    Bytes(&'static [&'static str], usize),
+    /// Padding to a multiple of this many bytes from the start of the record
+    Align(usize),
+    Record(Summary, &'static [Item]),
+    /// As many items as the earlier field named
+    List(&'static str, &'static Self),
+    /// Items up to the end of the PDU
+    Remaining(&'static Self),
+    /// A record stating its own length, in units of this many bits, in the field named;
+    /// any bytes past its fields are its record-specific fields
+    Sized(Summary, &'static [Item], &'static str, usize),
+}
+
+impl Layout {
+    /// Shows the bytes of a single value.
+    fn describe(self, bytes: &[u8]) -> String {
+        let raw = big_endian(bytes).unwrap_or_default();
+
+        let value = match self {
+            Self::Signed(2) => bytes
+                .try_into()
+                .ok()
+                .map(i16::from_be_bytes)
+                .map(|n| n.to_string()),
+            Self::Signed(4) => bytes
+                .try_into()
+                .ok()
+                .map(i32::from_be_bytes)
+                .map(|n| n.to_string()),
+            Self::Float(4) => bytes
+                .try_into()
+                .ok()
+                .map(f32::from_be_bytes)
+                .map(|n| n.to_string()),
+            Self::Float(8) => bytes
+                .try_into()
+                .ok()
+                .map(f64::from_be_bytes)
+                .map(|n| n.to_string()),
+            Self::Enumerated(len, enumeration) => enumeration
+                .name(raw, len)
+                .map(|name| format!("{name} ({raw})")),
+            Self::Bits(len) => Some(format!("0x{raw:0width$X}", width = len * 2)),
+            Self::Text(_) => Some(format!("{:?}", marking(bytes))),
+            Self::Octets(_) => Some(describe_bytes(bytes)),
+            _ => None,
+        };
+
+        value.unwrap_or_else(|| raw.to_string())
+    }
+}
+
+/// How a record is summed up for operators to read at a glance.
+#[derive(Debug, Clone, Copy)]
+enum Summary {
+    None,
+    /// Numbers joined by colons, such as `1:2:3` for an entity ID
+    Identifier,
+    /// Numbers joined by dots, such as `1.1.225.1.1.3.0`
+    EntityType,
+    /// A world coordinate, with where it lies on the earth
+    Location,
+    /// The characters of a marking
+    Marking,
+    /// A datum ID with the datum's value
+    Datum,
+    /// The type of a record that states it
+    RecordType,
+}
+
+impl Summary {
+    fn apply(self, mut field: Field) -> Field {
+        let join = |field: &Field, separator: &str| {
+            field
+                .leaves()
+                .iter()
+                .map(|leaf| leaf.raw.map(|raw| raw.to_string()))
+                .collect::<Option<Vec<_>>>()
+                .map(|values| values.join(separator))
+        };
+        let child = |field: &Field, name: &str| field.child(name).map(|child| child.value.clone());
+
+        let value = match self {
+            Self::None => None,
+            Self::Identifier => join(&field, ":"),
+            Self::EntityType => join(&field, "."),
+            Self::Location => {
+                let coordinate = |name: &str| {
+                    field
+                        .child(name)
+                        .and_then(|child| child.value.parse::<f64>().ok())
+                };
+                let (Some(x), Some(y), Some(z)) =
+                    (coordinate("x"), coordinate("y"), coordinate("z"))
+                else {
+                    return field;
+                };
+
+                let location = Vector::new(x, y, z);
+                if location.magnitude() >= MIN_GEOCENTRIC_RADIUS_M {
+                    let geodetic = Geodetic::from_geocentric(location);
+                    field.mgrs = geodetic.to_mgrs();
+                    field.location = Some(geodetic);
+                }
+
+                Some(format!("{x:.1}, {y:.1}, {z:.1}"))
+            }
+            Self::Marking => child(&field, "characters"),
+            Self::Datum => child(&field, "datum_id")
+                .zip(child(&field, "datum_value"))
+                .map(|(id, value)| format!("{id}: {value}")),
+            Self::RecordType => child(&field, "record_type").map(|kind| format!("type {kind}")),
+        };
+
+        if let Some(value) = value {
+            field.value = value;
+        }
+        field
+    }
+}
+
+macro_rules! enumerations {
+    ($($name:ident),* $(,)?) => {
+        /// The enumerations of `open_dis_rust` whose enumerators name coded fields.
+        #[derive(Debug, Clone, Copy)]
+        enum Enumeration {
+            $($name,)*
+        }
+
+        impl Enumeration {
+            /// Names the enumerator a code of `len` bytes stands for, if it is defined.
+            fn name(self, code: u64, len: usize) -> Option<String> {
+                // Each enumeration reads its own size from the front of the buffer
+                let mut buf = [0; 8];
+                buf[..len].copy_from_slice(&code.to_be_bytes()[8 - len..]);
+                let buf = &mut &buf[..];
+
+                match self {
+                    $(Self::$name => {
+                        let value = enums::$name::deserialize(buf);
+                        // Undefined codes decode as the default enumerator
+                        (value as u64 == code).then(|| format!("{value:?}"))
+                    })*
+                }
+            }
+        }
+    };
+}
+
+enumerations! {
+    AcknowledgeFlag,
+    AcknowledgeResponseFlag,
+    ActionResponseRequestStatus,
+    AggregateStateAggregateState,
+    AggregateStateFormation,
+    Country,
+    DEFirePulseShape,
+    DISAttributeActionCode,
+    DeadReckoningAlgorithm,
+    DesignatorSystemName,
+    DetonationResult,
+    EEAttributeStateIndicator,
+    EmitterName,
+    EmitterSystemFunction,
+    EntityKind,
+    EntityMarkingCharacterSet,
+    EventType,
+    ForceId,
+    GriddedDataConstantGrid,
+    GriddedDataCoordinateSystem,
+    IOActionIOActionPhase,
+    IOActionIOActionType,
+    IOActionIOSimulationSource,
+    IOActionIOWarfareType,
+    IOReportIOReportType,
+    IntercomControlCommand,
+    IntercomControlControlType,
+    IntercomControlTransmitLineState,
+    IsGroupOfGroupedEntityCategory,
+    MinefieldStateProtocolMode,
+    MunitionDescriptorFuse,
+    MunitionDescriptorWarhead,
+    PduType,
+    ProtocolFamily,
+    ProtocolVersion,
+    Reason,
+    ReceiverReceiverState,
+    RecordQueryREventType,
+    RepairCompleteRepair,
+    RepairResponseRepairResult,
+    RequiredReliabilityService,
+    ServiceRequestServiceTypeRequested,
+    SignalTDLType,
+    TransferControlTransferType,
+    TransmitterAntennaPatternType,
+    TransmitterCryptoSystem,
+    TransmitterInputSource,
+    TransmitterTransmitState,
+    UAAcousticEmitterSystemFunction,
+    UAAcousticSystemName,
+    UAPassiveParameterIndex,
+    UAStateChangeUpdateIndicator,
+    VariableParameterRecordType,
+    VariableRecordTypes,
+}
+
+const U8: Layout = Layout::Unsigned(1);
+const U16: Layout = Layout::Unsigned(2);
+const U32: Layout = Layout::Unsigned(4);
+const U64: Layout = Layout::Unsigned(8);
+const I16: Layout = Layout::Signed(2);
+const I32: Layout = Layout::Signed(4);
+const F32: Layout = Layout::Float(4);
+const F64: Layout = Layout::Float(8);
+
+const fn enumerated(len: usize, enumeration: Enumeration) -> Layout {
+    Layout::Enumerated(len, enumeration)
+}
+
+const fn padding(len: usize) -> Layout {
+    Layout::Bits(len)
+}
+
+const HEADER: Layout = Layout::Record(
+    Summary::None,
+    &[
+        (
+            "protocol_version",
+            enumerated(1, Enumeration::ProtocolVersion),
+        ),
+        ("exercise_id", U8),
+        ("pdu_type", enumerated(1, Enumeration::PduType)),
+        (
+            "protocol_family",
+            enumerated(1, Enumeration::ProtocolFamily),
+        ),
+        ("timestamp", U32),
+        ("length", U16),
+        ("pdu_status", Layout::Bits(1)),
+        ("padding", padding(1)),
+    ],
+);
+
+// Records shared between PDUs
+
+const SIMULATION_ADDRESS: Layout = Layout::Record(
+    Summary::Identifier,
+    &[("site_id", U16), ("application_id", U16)],
+);
+
+const ENTITY_ID: Layout = Layout::Record(
+    Summary::Identifier,
+    &[
+        ("site_id", U16),
+        ("application_id", U16),
+        ("entity_id", U16),
+    ],
+);
+
+const EVENT_ID: Layout = Layout::Record(
+    Summary::Identifier,
+    &[
+        ("site_id", U16),
+        ("application_id", U16),
+        ("event_number", U16),
+    ],
+);
+
+const SIMULATION_IDENTIFIER: Layout = Layout::Record(
+    Summary::Identifier,
+    &[
+        ("simulation_address", SIMULATION_ADDRESS),
+        ("reference_number", U16),
+    ],
+);
+
+const OBJECT_ID: Layout = Layout::Record(
+    Summary::Identifier,
+    &[
+        ("simulation_address", SIMULATION_ADDRESS),
+        ("object_number", U16),
+    ],
+);
+
+const MINEFIELD_ID: Layout = Layout::Record(
+    Summary::Identifier,
+    &[
+        ("simulation_address", SIMULATION_ADDRESS),
+        ("minefield_number", U16),
+    ],
+);
+
+const AGGREGATE_ID: Layout = Layout::Record(
+    Summary::Identifier,
+    &[
+        ("site_id", U16),
+        ("application_id", U16),
+        ("aggregate_id", U16),
+    ],
+);
+
+const ENTITY_TYPE: Layout = Layout::Record(
+    Summary::EntityType,
+    &[
+        ("kind", enumerated(1, Enumeration::EntityKind)),
+        ("domain", U8),
+        ("country", enumerated(2, Enumeration::Country)),
+        ("category", U8),
+        ("subcategory", U8),
+        ("specific", U8),
+        ("extra", U8),
+    ],
+);
+
+const RADIO_TYPE: Layout = Layout::Record(
+    Summary::EntityType,
+    &[
+        ("kind", enumerated(1, Enumeration::EntityKind)),
+        ("domain", U8),
+        ("country", enumerated(2, Enumeration::Country)),
+        ("category", U8),
+        ("nomenclature_version", U8),
+        ("nomenclature", U16),
+    ],
+);
+
+const OBJECT_TYPE: Layout = Layout::Record(
+    Summary::EntityType,
+    &[
+        ("domain", U8),
+        ("kind", U8),
+        ("category", U8),
+        ("subcategory", U8),
+    ],
+);
+
+const VECTOR: Layout = Layout::Record(Summary::None, &[("x", F32), ("y", F32), ("z", F32)]);
+
+const WORLD_COORDINATE: Layout =
+    Layout::Record(Summary::Location, &[("x", F64), ("y", F64), ("z", F64)]);
+
+const ORIENTATION: Layout =
+    Layout::Record(Summary::None, &[("psi", F32), ("theta", F32), ("phi", F32)]);
+
+const CLOCK_TIME: Layout = Layout::Record(Summary::None, &[("hour", I32), ("time_past_hour", U32)]);
+
+const POINT: Layout = Layout::Record(Summary::None, &[("x", F32), ("y", F32)]);
+
+const DEAD_RECKONING: Layout = Layout::Record(
+    Summary::None,
+    &[
+        (
+            "dead_reckoning_algorithm",
+            enumerated(1, Enumeration::DeadReckoningAlgorithm),
+        ),
+        ("other_parameters", Layout::Octets(15)),
+        ("linear_acceleration", VECTOR),
+        ("angular_velocity", VECTOR),
+    ],
+);
+
+const ENTITY_MARKING: Layout = Layout::Record(
+    Summary::Marking,
+    &[
+        (
+            "character_set",
+            enumerated(1, Enumeration::EntityMarkingCharacterSet),
+        ),
+        ("characters", Layout::Text(11)),
+    ],
+);
+
+const AGGREGATE_MARKING: Layout = Layout::Record(
+    Summary::Marking,
+    &[
+        (
+            "character_set",
+            enumerated(1, Enumeration::EntityMarkingCharacterSet),
+        ),
+        ("characters", Layout::Text(31)),
+    ],
+);
+
+const VARIABLE_PARAMETER: Layout = Layout::Record(
+    Summary::RecordType,
+    &[
+        (
+            "record_type",
+            enumerated(1, Enumeration::VariableParameterRecordType),
+        ),
+        ("parameters", Layout::Octets(15)),
+    ],
+);
+
+const MUNITION_DESCRIPTOR: Layout = Layout::Record(
+    Summary::None,
+    &[
+        ("munition_type", ENTITY_TYPE),
+        (
+            "warhead",
+            enumerated(2, Enumeration::MunitionDescriptorWarhead),
+        ),
+        ("fuse", enumerated(2, Enumeration::MunitionDescriptorFuse)),
+        ("quantity", U16),
+        ("rate", U16),
+    ],
+);
+
+const SUPPLY_QUANTITY: Layout = Layout::Record(
+    Summary::None,
+    &[("supply_type", ENTITY_TYPE), ("quantity", F32)],
+);
+
+const DATUM_ID: Layout = enumerated(4, Enumeration::VariableRecordTypes);
+
+const FIXED_DATUM: Layout = Layout::Record(
+    Summary::Datum,
+    &[("datum_id", DATUM_ID), ("datum_value", U32)],
+);
+
+const VARIABLE_DATUM: Layout = Layout::Record(
+    Summary::Datum,
+    &[
+        ("datum_id", DATUM_ID),
+        ("datum_length", U32),
+        ("datum_value", Layout::Bytes(&["datum_length"], 1)),
+        ("padding", Layout::Align(DATUM_ALIGNMENT_BYTES)),
+    ],
+);
+
+/// The standard record of a type and a length in bytes, used by several PDUs for records
+/// whose fields depend on their type
+const TYPED_RECORD: Layout = Layout::Sized(
+    Summary::RecordType,
+    &[("record_type", U32), ("record_length", U16)],
+    "record_length",
+    8,
+);
+
+const RECORD_SET: Layout = Layout::Record(
+    Summary::None,
+    &[
+        ("record_id", DATUM_ID),
+        ("record_set_serial_number", U32),
+        ("padding", padding(4)),
+        ("record_length", U16),
+        ("record_count", U16),
+        (
+            "record_values",
+            Layout::Bytes(&["record_length", "record_count"], 1),
+        ),
+        ("padding", Layout::Align(8)),
+    ],
+);
+
+const RECORD_SPECIFICATION: Layout = Layout::Record(
+    Summary::None,
+    &[
+        ("number_of_record_sets", U32),
+        (
+            "record_sets",
+            Layout::List("number_of_record_sets", &RECORD_SET),
+        ),
+    ],
+);
+
+/// The originating and receiving entities that start every SIMAN PDU
+const SIMAN_ENTITIES: [Item; 2] = [
+    ("originating_entity_id", ENTITY_ID),
+    ("receiving_entity_id", ENTITY_ID),
+];
+
+/// The counts and records of fixed and variable datums that end many SIMAN PDUs
+const DATUMS: [Item; 4] = [
+    ("number_of_fixed_datum_records", U32),
+    ("number_of_variable_datum_records", U32),
+    (
+        "fixed_datum_records",
+        Layout::List("number_of_fixed_datum_records", &FIXED_DATUM),
+    ),
+    (
+        "variable_datum_records",
+        Layout::List("number_of_variable_datum_records", &VARIABLE_DATUM),
+    ),
+];
+
+const RELIABILITY: [Item; 2] = [
+    (
+        "required_reliability_service",
+        enumerated(1, Enumeration::RequiredReliabilityService),
+    ),
+    ("padding", padding(3)),
+];
+
+/// Joins the parts of a layout into one.
+macro_rules! items {
+    ($($part:expr),* $(,)?) => {{
+        const PARTS: &[&[Item]] = &[$(&$part),*];
+        const LEN: usize = {
+            let mut len = 0;
+            let mut i = 0;
+            while i < PARTS.len() {
+                len += PARTS[i].len();
+                i += 1;
+            }
+            len
+        };
+        const ITEMS: [Item; LEN] = {
+            let mut items = [("", U8); LEN];
+            let mut n = 0;
+            let mut i = 0;
+            while i < PARTS.len() {
+                let mut j = 0;
+                while j < PARTS[i].len() {
+                    items[n] = PARTS[i][j];
+                    n += 1;
+                    j += 1;
+                }
+                i += 1;
+            }
+            items
+        };
+        &ITEMS
+    }};
+}
+
+// Entity information/interaction
+
+const ENTITY_STATE: &[Item] = &[
+    ("entity_id", ENTITY_ID),
+    ("force_id", enumerated(1, Enumeration::ForceId)),
+    ("number_of_variable_parameters", U8),
+    ("entity_type", ENTITY_TYPE),
+    ("alternative_entity_type", ENTITY_TYPE),
+    ("entity_linear_velocity", VECTOR),
+    ("entity_location", WORLD_COORDINATE),
+    ("entity_orientation", ORIENTATION),
+    ("entity_appearance", Layout::Bits(4)),
+    ("dead_reckoning_parameters", DEAD_RECKONING),
+    ("entity_marking", ENTITY_MARKING),
+    ("entity_capabilities", Layout::Bits(4)),
+    (
+        "variable_parameters",
+        Layout::List("number_of_variable_parameters", &VARIABLE_PARAMETER),
+    ),
+];
+
+const COLLISION: &[Item] = &[
+    ("issuing_entity_id", ENTITY_ID),
+    ("colliding_entity_id", ENTITY_ID),
+    ("event_id", EVENT_ID),
+    ("collision_type", U8),
+    ("padding", padding(1)),
+    ("velocity", VECTOR),
+    ("mass", F32),
+    ("location_wrt_entity", VECTOR),
+];
+
+const COLLISION_ELASTIC: &[Item] = &[
+    ("issuing_entity_id", ENTITY_ID),
+    ("colliding_entity_id", ENTITY_ID),
+    ("event_id", EVENT_ID),
+    ("padding", padding(2)),
+    ("contact_velocity", VECTOR),
+    ("mass", F32),
+    ("location_of_impact", VECTOR),
+    ("collision_intermediate_result_xx", F32),
+    ("collision_intermediate_result_xy", F32),
+    ("collision_intermediate_result_xz", F32),
+    ("collision_intermediate_result_yy", F32),
+    ("collision_intermediate_result_yz", F32),
+    ("collision_intermediate_result_zz", F32),
+    ("unit_surface_normal", VECTOR),
+    ("coefficient_of_restitution", F32),
+];
+
+const ENTITY_STATE_UPDATE: &[Item] = &[
+    ("entity_id", ENTITY_ID),
+    ("padding", padding(1)),
+    ("number_of_variable_parameters", U8),
+    ("entity_linear_velocity", VECTOR),
+    ("entity_location", WORLD_COORDINATE),
+    ("entity_orientation", ORIENTATION),
+    ("entity_appearance", Layout::Bits(4)),
+    (
+        "variable_parameters",
+        Layout::List("number_of_variable_parameters", &VARIABLE_PARAMETER),
+    ),
+];
+
+const ATTRIBUTE_RECORD_SET: Layout = Layout::Record(
+    Summary::None,
+    &[
+        ("entity_id", ENTITY_ID),
+        ("number_of_attribute_records", U16),
+        (
+            "attribute_records",
+            Layout::List("number_of_attribute_records", &TYPED_RECORD),
+        ),
+    ],
+);
+
+const ATTRIBUTE: &[Item] = &[
+    ("originating_simulation_address", SIMULATION_ADDRESS),
+    ("padding", padding(6)),
+    (
+        "attribute_record_pdu_type",
+        enumerated(1, Enumeration::PduType),
+    ),
+    (
+        "attribute_record_protocol_version",
+        enumerated(1, Enumeration::ProtocolVersion),
+    ),
+    ("master_attribute_record_type", U32),
+    (
+        "action_code",
+        enumerated(1, Enumeration::DISAttributeActionCode),
+    ),
+    ("padding", padding(1)),
+    ("number_of_attribute_record_sets", U16),
+    (
+        "attribute_record_sets",
+        Layout::List("number_of_attribute_record_sets", &ATTRIBUTE_RECORD_SET),
+    ),
+];
+
+// Warfare
+
+const FIRE: &[Item] = &[
+    ("firing_entity_id", ENTITY_ID),
+    ("target_entity_id", ENTITY_ID),
+    ("munition_expendable_id", ENTITY_ID),
+    ("event_id", EVENT_ID),
+    ("fire_mission_index", U32),
+    ("location_in_world_coordinates", WORLD_COORDINATE),
+    ("descriptor", MUNITION_DESCRIPTOR),
+    ("velocity", VECTOR),
+    ("range", F32),
+];
+
+const DETONATION: &[Item] = &[
+    ("firing_entity_id", ENTITY_ID),
+    ("target_entity_id", ENTITY_ID),
+    ("exploding_entity_id", ENTITY_ID),
+    ("event_id", EVENT_ID),
+    ("velocity", VECTOR),
+    ("location_in_world_coordinates", WORLD_COORDINATE),
+    ("descriptor", MUNITION_DESCRIPTOR),
+    ("location_in_entity_coordinates", VECTOR),
+    (
+        "detonation_result",
+        enumerated(1, Enumeration::DetonationResult),
+    ),
+    ("number_of_variable_parameters", U8),
+    ("padding", padding(2)),
+    (
+        "variable_parameters",
+        Layout::List("number_of_variable_parameters", &VARIABLE_PARAMETER),
+    ),
+];
+
+const DIRECTED_ENERGY_FIRE: &[Item] = &[
+    ("firing_entity_id", ENTITY_ID),
+    ("event_id", EVENT_ID),
+    ("munition_type", ENTITY_TYPE),
+    ("shot_start_time", CLOCK_TIME),
+    ("cumulative_shot_time", F32),
+    ("aperture_emitter_location", VECTOR),
+    ("aperture_diameter", F32),
+    ("wavelength", F32),
+    ("peak_irradiance", F32),
+    ("pulse_repetition_frequency", F32),
+    ("pulse_width", F32),
+    ("flags", Layout::Bits(2)),
+    ("pulse_shape", enumerated(1, Enumeration::DEFirePulseShape)),
+    ("padding", padding(7)),
+    ("number_of_de_records", U16),
+    (
+        "de_records",
+        Layout::List("number_of_de_records", &TYPED_RECORD),
+    ),
+];
+
+const ENTITY_DAMAGE_STATUS: &[Item] = &[
+    ("damaged_entity_id", ENTITY_ID),
+    ("padding", padding(4)),
+    ("number_of_damage_descriptions", U16),
+    (
+        "damage_descriptions",
+        Layout::List("number_of_damage_descriptions", &TYPED_RECORD),
+    ),
+];
+
+// Logistics
+
+const SERVICE_REQUEST: &[Item] = &[
+    ("receiving_entity_id", ENTITY_ID),
+    ("servicing_entity_id", ENTITY_ID),
+    (
+        "service_type_requested",
+        enumerated(1, Enumeration::ServiceRequestServiceTypeRequested),
+    ),
+    ("number_of_supply_types", U8),
+    ("padding", padding(2)),
+    (
+        "supplies",
+        Layout::List("number_of_supply_types", &SUPPLY_QUANTITY),
+    ),
+];
+
+const RESUPPLY: &[Item] = &[
+    ("receiving_entity_id", ENTITY_ID),
+    ("supplying_entity_id", ENTITY_ID),
+    ("number_of_supply_types", U8),
+    ("padding", padding(3)),
+    (
+        "supplies",
+        Layout::List("number_of_supply_types", &SUPPLY_QUANTITY),
+    ),
+];
+
+const RESUPPLY_CANCEL: &[Item] = &[
+    ("receiving_entity_id", ENTITY_ID),
+    ("supplying_entity_id", ENTITY_ID),
+];
+
+const REPAIR_COMPLETE: &[Item] = &[
+    ("receiving_entity_id", ENTITY_ID),
+    ("repairing_entity_id", ENTITY_ID),
+    ("repair", enumerated(2, Enumeration::RepairCompleteRepair)),
+    ("padding", padding(2)),
+];
+
+const REPAIR_RESPONSE: &[Item] = &[
+    ("receiving_entity_id", ENTITY_ID),
+    ("repairing_entity_id", ENTITY_ID),
+    (
+        "repair_result",
+        enumerated(1, Enumeration::RepairResponseRepairResult),
+    ),
+    ("padding", padding(3)),
+];
+
+// Simulation management, with and without reliability
+
+const REQUEST_ID: Item = ("request_id", U32);
+
+const CREATE_REMOVE_ENTITY: &[Item] = items![SIMAN_ENTITIES, [REQUEST_ID]];
+
+const START_RESUME: &[Item] = items![
+    SIMAN_ENTITIES,
+    [
+        ("real_world_time", CLOCK_TIME),
+        ("simulation_time", CLOCK_TIME),
+        REQUEST_ID,
+    ],
+];
+
+const STOP_FREEZE_REASON: [Item; 3] = [
+    ("real_world_time", CLOCK_TIME),
+    ("reason", enumerated(1, Enumeration::Reason)),
+    ("frozen_behavior", Layout::Bits(1)),
+];
+
+const STOP_FREEZE: &[Item] = items![
+    SIMAN_ENTITIES,
+    STOP_FREEZE_REASON,
+    [("padding", padding(2)), REQUEST_ID],
+];
+
+const ACKNOWLEDGE: &[Item] = items![
+    SIMAN_ENTITIES,
+    [
+        (
+            "acknowledge_flag",
+            enumerated(2, Enumeration::AcknowledgeFlag)
+        ),
+        (
+            "response_flag",
+            enumerated(2, Enumeration::AcknowledgeResponseFlag),
+        ),
+        REQUEST_ID,
+    ],
+];
+
+const ACTION_REQUEST: &[Item] = items![
+    SIMAN_ENTITIES,
+    [REQUEST_ID, ("action_id", DATUM_ID)],
+    DATUMS,
+];
+
+const ACTION_RESPONSE: &[Item] = items![
+    SIMAN_ENTITIES,
+    [
+        REQUEST_ID,
+        (
+            "request_status",
+            enumerated(4, Enumeration::ActionResponseRequestStatus),
+        ),
+    ],
+    DATUMS,
+];
+
+/// Data Query PDUs list the IDs of the datums wanted rather than datums
+const DATUM_IDS: [Item; 4] = [
+    ("number_of_fixed_datum_records", U32),
+    ("number_of_variable_datum_records", U32),
+    (
+        "fixed_datum_ids",
+        Layout::List("number_of_fixed_datum_records", &DATUM_ID),
+    ),
+    (
+        "variable_datum_ids",
+        Layout::List("number_of_variable_datum_records", &DATUM_ID),
+    ),
+];
+
+const DATA_QUERY: &[Item] = items![
+    SIMAN_ENTITIES,
+    [REQUEST_ID, ("time_interval", U32)],
+    DATUM_IDS,
+];
+
+const SET_DATA: &[Item] = items![
+    SIMAN_ENTITIES,
+    [REQUEST_ID, ("padding", padding(4))],
+    DATUMS,
+];
+
+const EVENT_REPORT: &[Item] = items![
+    SIMAN_ENTITIES,
+    [
+        ("event_type", enumerated(4, Enumeration::EventType)),
+        ("padding", padding(4)),
+    ],
+    DATUMS,
+];
+
+const COMMENT: &[Item] = items![SIMAN_ENTITIES, DATUMS];
+
+const CREATE_REMOVE_ENTITY_RELIABLE: &[Item] = items![SIMAN_ENTITIES, RELIABILITY, [REQUEST_ID]];
+
+const START_RESUME_RELIABLE: &[Item] = items![
+    SIMAN_ENTITIES,
+    [
+        ("real_world_time", CLOCK_TIME),
+        ("simulation_time", CLOCK_TIME),
+    ],
+    RELIABILITY,
+    [REQUEST_ID],
+];
+
+const STOP_FREEZE_RELIABLE: &[Item] = items![
+    SIMAN_ENTITIES,
+    STOP_FREEZE_REASON,
+    [
+        (
+            "required_reliability_service",
+            enumerated(1, Enumeration::RequiredReliabilityService),
+        ),
+        ("padding", padding(1)),
+        REQUEST_ID,
+    ],
+];
+
+const ACTION_REQUEST_RELIABLE: &[Item] = items![
+    SIMAN_ENTITIES,
+    RELIABILITY,
+    [REQUEST_ID, ("action_id", DATUM_ID)],
+    DATUMS,
+];
+
+const DATA_QUERY_RELIABLE: &[Item] = items![
+    SIMAN_ENTITIES,
+    RELIABILITY,
+    [REQUEST_ID, ("time_interval", U32)],
+    DATUM_IDS,
+];
+
+const SET_DATA_RELIABLE: &[Item] = items![SIMAN_ENTITIES, RELIABILITY, [REQUEST_ID], DATUMS];
+
+const DATA_RELIABLE: &[Item] = items![SIMAN_ENTITIES, [REQUEST_ID], RELIABILITY, DATUMS];
+
+const RECORD_QUERY_RELIABLE: &[Item] = items![
+    SIMAN_ENTITIES,
+    [
+        REQUEST_ID,
+        (
+            "required_reliability_service",
+            enumerated(1, Enumeration::RequiredReliabilityService),
+        ),
+        ("padding", padding(1)),
+        (
+            "event_type",
+            enumerated(2, Enumeration::RecordQueryREventType)
+        ),
+        ("time", U32),
+        ("number_of_records", U32),
+        ("record_ids", Layout::List("number_of_records", &DATUM_ID)),
+    ],
+];
+
+const SET_RECORD_RELIABLE: &[Item] = items![
+    SIMAN_ENTITIES,
+    [REQUEST_ID],
+    RELIABILITY,
+    [
+        ("padding", padding(4)),
+        ("record_sets", RECORD_SPECIFICATION),
+    ],
+];
+
+// Distributed emission regeneration
+
+const EMITTER_SYSTEM: Layout = Layout::Record(
+    Summary::None,
+    &[
+        ("emitter_name", enumerated(2, Enumeration::EmitterName)),
+        (
+            "function",
+            enumerated(1, Enumeration::EmitterSystemFunction),
+        ),
+        ("emitter_id_number", U8),
+    ],
+);
+
+const TRACK_JAM_TARGET: Layout = Layout::Record(
+    Summary::None,
+    &[
+        ("target_entity_id", ENTITY_ID),
+        ("emitter_number", U8),
+        ("beam_number", U8),
+    ],
+);
+
+const EMISSION_BEAM: Layout = Layout::Sized(
+    Summary::None,
+    &[
+        ("beam_data_length", U8),
+        ("beam_id_number", U8),
+        ("beam_parameter_index", U16),
+        ("frequency", F32),
+        ("frequency_range", F32),
+        ("effective_radiated_power", F32),
+        ("pulse_repetition_frequency", F32),
+        ("pulse_width", F32),
+        ("beam_azimuth_center", F32),
+        ("beam_azimuth_sweep", F32),
+        ("beam_elevation_center", F32),
+        ("beam_elevation_sweep", F32),
+        ("beam_sweep_sync", F32),
+        ("beam_function", U8),
+        ("number_of_targets", U8),
+        ("high_density_track_jam", U8),
+        ("beam_status", Layout::Bits(1)),
+        ("jamming_technique", Layout::Bits(4)),
+        (
+            "track_jam_targets",
+            Layout::List("number_of_targets", &TRACK_JAM_TARGET),
+        ),
+    ],
+    "beam_data_length",
+    32,
+);
+
+const EMISSION_SYSTEM: Layout = Layout::Sized(
+    Summary::None,
+    &[
+        ("system_data_length", U8),
+        ("number_of_beams", U8),
+        ("padding", padding(2)),
+        ("emitter_system", EMITTER_SYSTEM),
+        ("location", VECTOR),
+        ("beams", Layout::List("number_of_beams", &EMISSION_BEAM)),
+    ],
+    "system_data_length",
+    32,
+);
+
+const ELECTROMAGNETIC_EMISSION: &[Item] = &[
+    ("emitting_entity_id", ENTITY_ID),
+    ("event_id", EVENT_ID),
+    (
+        "state_update_indicator",
+        enumerated(1, Enumeration::EEAttributeStateIndicator),
+    ),
+    ("number_of_systems", U8),
+    ("padding", padding(2)),
+    (
+        "systems",
+        Layout::List("number_of_systems", &EMISSION_SYSTEM),
+    ),
+];
+
+const DESIGNATOR: &[Item] = &[
+    ("designating_entity_id", ENTITY_ID),
+    (
+        "code_name",
+        enumerated(2, Enumeration::DesignatorSystemName),
+    ),
+    ("designated_entity_id", ENTITY_ID),
+    ("designator_code", U16),
+    ("designator_power", F32),
+    ("designator_wavelength", F32),
+    ("designator_spot_wrt_designated", VECTOR),
+    ("designator_spot_location", WORLD_COORDINATE),
+    (
+        "dead_reckoning_algorithm",
+        enumerated(1, Enumeration::DeadReckoningAlgorithm),
+    ),
+    ("padding", padding(3)),
+    ("entity_linear_acceleration", VECTOR),
+];
+
+/// IFF layers after the first, whose fields depend on the layer
+const IFF_LAYER: Layout = Layout::Sized(
+    Summary::None,
+    &[
+        ("layer_number", U8),
+        ("layer_specific_information", U8),
+        ("length", U16),
+    ],
+    "length",
+    8,
+);
+
+const IFF: &[Item] = &[
+    ("emitting_entity_id", ENTITY_ID),
+    ("event_id", EVENT_ID),
+    ("relative_antenna_location", VECTOR),
+    (
+        "system_id",
+        Layout::Record(
+            Summary::None,
+            &[
+                ("system_type", U16),
+                ("system_name", U16),
+                ("system_mode", U8),
+                ("change_options", Layout::Bits(1)),
+            ],
+        ),
+    ),
+    ("system_designator", U8),
+    ("system_specific_data", Layout::Bits(1)),
+    (
+        "fundamental_operational_data",
+        Layout::Record(
+            Summary::None,
+            &[
+                ("system_status", Layout::Bits(1)),
+                ("data_field_1", U8),
+                ("information_layers", Layout::Bits(1)),
+                ("data_field_2", U8),
+                ("parameter_1", Layout::Bits(2)),
+                ("parameter_2", Layout::Bits(2)),
+                ("parameter_3", Layout::Bits(2)),
+                ("parameter_4", Layout::Bits(2)),
+                ("parameter_5", Layout::Bits(2)),
+                ("parameter_6", Layout::Bits(2)),
+            ],
+        ),
+    ),
+    ("layers", Layout::Remaining(&IFF_LAYER)),
+];
+
+const ACOUSTIC_BEAM: Layout = Layout::Sized(
+    Summary::None,
+    &[
+        ("beam_data_length", U8),
+        ("beam_id_number", U8),
+        ("padding", padding(2)),
+        ("active_emission_parameter_index", U16),
+        ("scan_pattern", U16),
+        ("beam_center_azimuth", F32),
+        ("azimuthal_beamwidth", F32),
+        ("beam_center_depression_elevation", F32),
+        ("depression_elevation_beamwidth", F32),
+    ],
+    "beam_data_length",
+    32,
+);
+
+const ACOUSTIC_SYSTEM: Layout = Layout::Sized(
+    Summary::None,
+    &[
+        ("system_data_length", U8),
+        ("number_of_beams", U8),
+        ("padding", padding(2)),
+        (
+            "acoustic_emitter_system",
+            Layout::Record(
+                Summary::None,
+                &[
+                    (
+                        "acoustic_system_name",
+                        enumerated(2, Enumeration::UAAcousticSystemName),
+                    ),
+                    (
+                        "acoustic_function",
+                        enumerated(1, Enumeration::UAAcousticEmitterSystemFunction),
+                    ),
+                    ("acoustic_id_number", U8),
+                ],
+            ),
+        ),
+        ("location", VECTOR),
+        ("beams", Layout::List("number_of_beams", &ACOUSTIC_BEAM)),
+    ],
+    "system_data_length",
+    32,
+);
+
+const UNDERWATER_ACOUSTIC: &[Item] = &[
+    ("emitting_entity_id", ENTITY_ID),
+    ("event_id", EVENT_ID),
+    (
+        "state_change_update_indicator",
+        enumerated(1, Enumeration::UAStateChangeUpdateIndicator),
+    ),
+    ("padding", padding(1)),
+    (
+        "passive_parameter_index",
+        enumerated(2, Enumeration::UAPassiveParameterIndex),
+    ),
+    ("propulsion_plant_configuration", Layout::Bits(1)),
+    ("number_of_shafts", U8),
+    ("number_of_apas", U8),
+    ("number_of_ua_emitter_systems", U8),
+    (
+        "shaft_rpms",
+        Layout::List(
+            "number_of_shafts",
+            &Layout::Record(
+                Summary::None,
+                &[
+                    ("current_rpms", I16),
+                    ("ordered_rpms", I16),
+                    ("rpm_rate_of_change", I32),
+                ],
+            ),
+        ),
+    ),
+    (
+        "apa_data",
+        Layout::List(
+            "number_of_apas",
+            &Layout::Record(
+                Summary::None,
+                &[("parameter_index", Layout::Bits(2)), ("value", I16)],
+            ),
+        ),
+    ),
+    (
+        "emitter_systems",
+        Layout::List("number_of_ua_emitter_systems", &ACOUSTIC_SYSTEM),
+    ),
+];
+
+const SUPPLEMENTAL_EMISSION: &[Item] = &[
+    ("originating_entity_id", ENTITY_ID),
+    ("infrared_signature_representation_index", U16),
+    ("acoustic_signature_representation_index", U16),
+    ("radar_cross_section_signature_representation_index", U16),
+    ("number_of_propulsion_systems", U16),
+    ("number_of_vectoring_nozzle_systems", U16),
+    (
+        "propulsion_systems",
+        Layout::List(
+            "number_of_propulsion_systems",
+            &Layout::Record(
+                Summary::None,
+                &[("power_setting", F32), ("engine_rpm", F32)],
+            ),
+        ),
+    ),
+    (
+        "vectoring_nozzle_systems",
+        Layout::List(
+            "number_of_vectoring_nozzle_systems",
+            &Layout::Record(
+                Summary::None,
+                &[
+                    ("horizontal_deflection_angle", F32),
+                    ("vertical_deflection_angle", F32),
+                ],
+            ),
+        ),
+    ),
+];
+
+// Radio communications
+
+const TRANSMITTER: &[Item] = &[
+    ("entity_id", ENTITY_ID),
+    ("radio_id", U16),
+    ("radio_entity_type", RADIO_TYPE),
+    (
+        "transmit_state",
+        enumerated(1, Enumeration::TransmitterTransmitState),
+    ),
+    (
+        "input_source",
+        enumerated(1, Enumeration::TransmitterInputSource),
+    ),
+    ("number_of_variable_transmitter_parameters", U16),
+    ("antenna_location", WORLD_COORDINATE),
+    ("relative_antenna_location", VECTOR),
+    (
+        "antenna_pattern_type",
+        enumerated(2, Enumeration::TransmitterAntennaPatternType),
+    ),
+    ("antenna_pattern_length", U16),
+    ("frequency", U64),
+    ("transmit_frequency_bandwidth", F32),
+    ("power", F32),
+    (
+        "modulation_type",
+        Layout::Record(
+            Summary::None,
+            &[
+                ("spread_spectrum", Layout::Bits(2)),
+                ("major_modulation", U16),
+                ("detail", U16),
+                ("radio_system", U16),
+            ],
+        ),
+    ),
+    (
+        "crypto_system",
+        enumerated(2, Enumeration::TransmitterCryptoSystem),
+    ),
+    ("crypto_key_id", Layout::Bits(2)),
+    ("modulation_parameters_length", U8),
+    ("padding", padding(3)),
+    (
+        "modulation_parameters",
+        Layout::Bytes(&["modulation_parameters_length"], 8),
+    ),
+    (
+        "antenna_pattern",
+        Layout::Bytes(&["antenna_pattern_length"], 8),
+    ),
+    (
+        "variable_transmitter_parameters",
+        Layout::List("number_of_variable_transmitter_parameters", &TYPED_RECORD),
+    ),
+];
+
+/// The encoding, sample rate and data shared by Signal and Intercom Signal PDUs
+const SIGNAL_DATA: [Item; 7] = [
+    ("encoding_scheme", Layout::Bits(2)),
+    ("tdl_type", enumerated(2, Enumeration::SignalTDLType)),
+    ("sample_rate", U32),
+    ("data_length", U16),
+    ("samples", U16),
+    ("data", Layout::Bytes(&["data_length"], 1)),
+    ("padding", Layout::Align(4)),
+];
+
+const SIGNAL: &[Item] = items![[("entity_id", ENTITY_ID), ("radio_id", U16)], SIGNAL_DATA];
+
+const RECEIVER: &[Item] = &[
+    ("entity_id", ENTITY_ID),
+    ("radio_id", U16),
+    (
+        "receiver_state",
+        enumerated(2, Enumeration::ReceiverReceiverState),
+    ),
+    ("padding", padding(2)),
+    ("received_power", F32),
+    ("transmitter_entity_id", ENTITY_ID),
+    ("transmitter_radio_id", U16),
+];
+
+const INTERCOM_SIGNAL: &[Item] = items![
+    [
+        ("intercom_reference_id", ENTITY_ID),
+        ("intercom_number", U16),
+    ],
+    SIGNAL_DATA,
+];
+
+const INTERCOM_CONTROL: &[Item] = &[
+    (
+        "control_type",
+        enumerated(1, Enumeration::IntercomControlControlType),
+    ),
+    ("communications_channel_type", Layout::Bits(1)),
+    ("source_entity_id", ENTITY_ID),
+    ("source_intercom_number", U16),
+    ("source_line_id", U8),
+    ("transmit_priority", U8),
+    (
+        "transmit_line_state",
+        enumerated(1, Enumeration::IntercomControlTransmitLineState),
+    ),
+    (
+        "command",
+        enumerated(1, Enumeration::IntercomControlCommand),
+    ),
+    ("master_entity_id", ENTITY_ID),
+    ("master_intercom_number", U16),
+    ("master_channel_id", U16),
+    ("intercom_parameters_length", U32),
+    (
+        "intercom_parameters",
+        Layout::Bytes(&["intercom_parameters_length"], 8),
+    ),
+];
+
+// Entity management
+
+const AGGREGATE_STATE: &[Item] = &[
+    ("aggregate_id", AGGREGATE_ID),
+    ("force_id", enumerated(1, Enumeration::ForceId)),
+    (
+        "aggregate_state",
+        enumerated(1, Enumeration::AggregateStateAggregateState),
+    ),
+    ("aggregate_type", ENTITY_TYPE),
+    (
+        "formation",
+        enumerated(4, Enumeration::AggregateStateFormation),
+    ),
+    ("aggregate_marking", AGGREGATE_MARKING),
+    ("dimensions", VECTOR),
+    ("orientation", ORIENTATION),
+    ("center_of_mass", WORLD_COORDINATE),
+    ("velocity", VECTOR),
+    ("number_of_dis_aggregates", U16),
+    ("number_of_dis_entities", U16),
+    ("number_of_silent_aggregate_types", U16),
+    ("number_of_silent_entity_types", U16),
+    (
+        "aggregate_ids",
+        Layout::List("number_of_dis_aggregates", &AGGREGATE_ID),
+    ),
+    (
+        "entity_ids",
+        Layout::List("number_of_dis_entities", &ENTITY_ID),
+    ),
+    ("padding", Layout::Align(4)),
+    (
+        "silent_aggregate_systems",
+        Layout::List(
+            "number_of_silent_aggregate_types",
+            &Layout::Record(
+                Summary::None,
+                &[
+                    ("number_of_aggregates", U16),
+                    ("padding", padding(2)),
+                    ("aggregate_type", ENTITY_TYPE),
+                ],
+            ),
+        ),
+    ),
+    (
+        "silent_entity_systems",
+        Layout::List(
+            "number_of_silent_entity_types",
+            &Layout::Record(
+                Summary::None,
+                &[
+                    ("number_of_entities", U16),
+                    ("number_of_appearance_records", U16),
+                    ("entity_type", ENTITY_TYPE),
+                    (
+                        "appearances",
+                        Layout::List("number_of_appearance_records", &Layout::Bits(4)),
+                    ),
+                ],
+            ),
+        ),
+    ),
+    ("number_of_variable_datum_records", U32),
+    (
+        "variable_datum_records",
+        Layout::List("number_of_variable_datum_records", &VARIABLE_DATUM),
+    ),
+];
+
+/// The grouped entity descriptions that follow depend on the category, so are left
+/// undecoded
+const IS_GROUP_OF: &[Item] = &[
+    ("group_entity_id", ENTITY_ID),
+    (
+        "grouped_entity_category",
+        enumerated(1, Enumeration::IsGroupOfGroupedEntityCategory),
+    ),
+    ("number_of_grouped_entities", U8),
+    ("padding", padding(4)),
+    ("latitude", F64),
+    ("longitude", F64),
+];
+
+const TRANSFER_OWNERSHIP: &[Item] = &[
+    ("originating_id", SIMULATION_IDENTIFIER),
+    ("receiving_id", SIMULATION_IDENTIFIER),
+    REQUEST_ID,
+    (
+        "required_reliability_service",
+        enumerated(1, Enumeration::RequiredReliabilityService),
+    ),
+    (
+        "transfer_type",
+        enumerated(1, Enumeration::TransferControlTransferType),
+    ),
+    ("transfer_entity_id", ENTITY_ID),
+    ("record_information", RECORD_SPECIFICATION),
+];
+
+const IS_PART_OF: &[Item] = &[
+    ("originating_entity_id", ENTITY_ID),
+    ("receiving_entity_id", ENTITY_ID),
+    (
+        "relationship",
+        Layout::Record(Summary::None, &[("nature", U16), ("position", U16)]),
+    ),
+    ("part_location", VECTOR),
+    (
+        "named_location",
+        Layout::Record(
+            Summary::None,
+            &[("station_name", U16), ("station_number", U16)],
+        ),
+    ),
+    ("part_entity_type", ENTITY_TYPE),
+];
+
+// Minefield
+
+const MINEFIELD_STATE: &[Item] = &[
+    ("minefield_id", MINEFIELD_ID),
+    ("minefield_sequence", U16),
+    ("force_id", enumerated(1, Enumeration::ForceId)),
+    ("number_of_perimeter_points", U8),
+    ("minefield_type", ENTITY_TYPE),
+    ("number_of_mine_types", U16),
+    ("minefield_location", WORLD_COORDINATE),
+    ("minefield_orientation", ORIENTATION),
+    ("appearance", Layout::Bits(2)),
+    (
+        "protocol_mode",
+        enumerated(2, Enumeration::MinefieldStateProtocolMode),
+    ),
+    (
+        "perimeter_points",
+        Layout::List("number_of_perimeter_points", &POINT),
+    ),
+    (
+        "mine_types",
+        Layout::List("number_of_mine_types", &ENTITY_TYPE),
+    ),
+];
+
+const MINEFIELD_QUERY: &[Item] = &[
+    ("minefield_id", MINEFIELD_ID),
+    ("requesting_entity_id", ENTITY_ID),
+    ("request_id", U8),
+    ("number_of_perimeter_points", U8),
+    ("padding", padding(1)),
+    ("number_of_sensor_types", U8),
+    ("data_filter", Layout::Bits(4)),
+    ("requested_mine_type", ENTITY_TYPE),
+    (
+        "requested_perimeter_points",
+        Layout::List("number_of_perimeter_points", &POINT),
+    ),
+    ("sensor_types", Layout::List("number_of_sensor_types", &U16)),
+];
+
+/// The mine fields after the locations depend on the data filter, so are left undecoded
+const MINEFIELD_DATA: &[Item] = &[
+    ("minefield_id", MINEFIELD_ID),
+    ("requesting_entity_id", ENTITY_ID),
+    ("minefield_sequence_number", U16),
+    ("request_id", U8),
+    ("pdu_sequence_number", U8),
+    ("number_of_pdus", U8),
+    ("number_of_mines", U8),
+    ("number_of_sensor_types", U8),
+    ("padding", padding(1)),
+    ("data_filter", Layout::Bits(4)),
+    ("mine_type", ENTITY_TYPE),
+    ("sensor_types", Layout::List("number_of_sensor_types", &U16)),
+    ("padding", Layout::Align(4)),
+    ("mine_locations", Layout::List("number_of_mines", &VECTOR)),
+];
+
+const MINEFIELD_RESPONSE_NACK: &[Item] = &[
+    ("minefield_id", MINEFIELD_ID),
+    ("requesting_entity_id", ENTITY_ID),
+    ("request_id", U8),
+    ("number_of_missing_pdus", U8),
+    (
+        "missing_pdu_sequence_numbers",
+        Layout::List("number_of_missing_pdus", &U8),
+    ),
+];
+
+// Synthetic environment
+
+/// The environment records that follow are left undecoded
+const ENVIRONMENTAL_PROCESS: &[Item] = &[
+    ("environmental_process_id", OBJECT_ID),
+    ("environment_type", ENTITY_TYPE),
+    ("model_type", U8),
+    ("environment_status", Layout::Bits(1)),
+    ("number_of_environment_records", U16),
+    ("sequence_number", U16),
+];
+
+/// The grid axis descriptors and data that follow are left undecoded
+const GRIDDED_DATA: &[Item] = &[
+    ("environmental_simulation_id", OBJECT_ID),
+    ("field_number", U16),
+    ("pdu_number", U16),
+    ("pdu_total", U16),
+    (
+        "coordinate_system",
+        enumerated(2, Enumeration::GriddedDataCoordinateSystem),
+    ),
+    ("number_of_grid_axes", U8),
+    (
+        "constant_grid",
+        enumerated(1, Enumeration::GriddedDataConstantGrid),
+    ),
+    ("environment_type", ENTITY_TYPE),
+    ("orientation", ORIENTATION),
+    ("sample_time", CLOCK_TIME),
+    ("total_values", U32),
+    ("vector_dimension", U8),
+    ("padding", padding(3)),
+];
+
+const POINT_OBJECT_STATE: &[Item] = &[
+    ("object_id", OBJECT_ID),
+    ("referenced_object_id", OBJECT_ID),
+    ("update_number", U16),
+    ("force_id", enumerated(1, Enumeration::ForceId)),
+    ("modifications", Layout::Bits(1)),
+    ("object_type", OBJECT_TYPE),
+    ("object_location", WORLD_COORDINATE),
+    ("object_orientation", ORIENTATION),
+    ("specific_object_appearance", Layout::Bits(4)),
+    ("general_object_appearance", Layout::Bits(2)),
+    ("padding", padding(2)),
+    ("requester_id", SIMULATION_ADDRESS),
+    ("receiving_id", SIMULATION_ADDRESS),
+    ("padding", padding(4)),
+];
+
+const LINEAR_SEGMENT: Layout = Layout::Record(
+    Summary::None,
+    &[
+        ("segment_number", U8),
+        ("segment_modification", Layout::Bits(1)),
+        ("general_segment_appearance", Layout::Bits(2)),
+        ("specific_segment_appearance", Layout::Bits(4)),
+        ("segment_location", WORLD_COORDINATE),
+        ("segment_orientation", ORIENTATION),
+        ("segment_length", U16),
+        ("segment_width", U16),
+        ("segment_height", U16),
+        ("segment_depth", U16),
+        ("padding", padding(4)),
+    ],
+);
+
+const LINEAR_OBJECT_STATE: &[Item] = &[
+    ("object_id", OBJECT_ID),
+    ("referenced_object_id", OBJECT_ID),
+    ("update_number", U16),
+    ("force_id", enumerated(1, Enumeration::ForceId)),
+    ("number_of_segments", U8),
+    ("requester_id", SIMULATION_ADDRESS),
+    ("receiving_id", SIMULATION_ADDRESS),
+    ("object_type", OBJECT_TYPE),
+    (
+        "linear_segment_parameters",
+        Layout::List("number_of_segments", &LINEAR_SEGMENT),
+    ),
+];
+
+const AREAL_OBJECT_STATE: &[Item] = &[
+    ("object_id", OBJECT_ID),
+    ("referenced_object_id", OBJECT_ID),
+    ("update_number", U16),
+    ("force_id", enumerated(1, Enumeration::ForceId)),
+    ("modifications", Layout::Bits(1)),
+    ("object_type", OBJECT_TYPE),
+    ("specific_object_appearance", Layout::Bits(4)),
+    ("general_object_appearance", Layout::Bits(2)),
+    ("number_of_points", U16),
+    ("requester_id", SIMULATION_ADDRESS),
+    ("receiving_id", SIMULATION_ADDRESS),
+    (
+        "object_locations",
+        Layout::List("number_of_points", &WORLD_COORDINATE),
+    ),
+];
+
+// Information operations
+
+const IO_RECORDS: [Item; 2] = [
+    ("number_of_io_records", U16),
+    (
+        "io_records",
+        Layout::List("number_of_io_records", &TYPED_RECORD),
+    ),
+];
+
+const INFORMATION_OPERATIONS_ACTION: &[Item] = items![
+    [
+        ("originating_simulation_id", SIMULATION_IDENTIFIER),
+        ("receiving_simulation_id", SIMULATION_IDENTIFIER),
+        REQUEST_ID,
+        (
+            "io_warfare_type",
+            enumerated(2, Enumeration::IOActionIOWarfareType),
+        ),
+        (
+            "io_simulation_source",
+            enumerated(2, Enumeration::IOActionIOSimulationSource),
+        ),
+        (
+            "io_action_type",
+            enumerated(2, Enumeration::IOActionIOActionType),
+        ),
+        (
+            "io_action_phase",
+            enumerated(2, Enumeration::IOActionIOActionPhase),
+        ),
+        ("padding", padding(4)),
+        ("io_attacker_entity_id", ENTITY_ID),
+        ("io_primary_target_entity_id", ENTITY_ID),
+        ("padding", padding(2)),
+    ],
+    IO_RECORDS,
+];
+
+const INFORMATION_OPERATIONS_REPORT: &[Item] = items![
+    [
+        ("originating_simulation_id", SIMULATION_IDENTIFIER),
+        (
+            "io_simulation_source",
+            enumerated(2, Enumeration::IOActionIOSimulationSource),
+        ),
+        (
+            "io_report_type",
+            enumerated(1, Enumeration::IOReportIOReportType),
+        ),
+        ("padding", padding(1)),
+        ("io_attacker_entity_id", ENTITY_ID),
+        ("io_primary_target_entity_id", ENTITY_ID),
+        ("padding", padding(4)),
+    ],
+    IO_RECORDS,
+];
+
+/// The layout of the body of each PDU type the inspector decodes.
+const fn body(pdu_type: PduType) -> Option<&'static [Item]> {
+    Some(match pdu_type {
+        PduType::EntityState => ENTITY_STATE,
+        PduType::Fire => FIRE,
+        PduType::Detonation => DETONATION,
+        PduType::Collision => COLLISION,
+        PduType::ServiceRequest => SERVICE_REQUEST,
+        PduType::ResupplyOffer | PduType::ResupplyReceived => RESUPPLY,
+        PduType::ResupplyCancel => RESUPPLY_CANCEL,
+        PduType::RepairComplete => REPAIR_COMPLETE,
+        PduType::RepairResponse => REPAIR_RESPONSE,
+        PduType::CreateEntity | PduType::RemoveEntity => CREATE_REMOVE_ENTITY,
+        PduType::StartResume => START_RESUME,
+        PduType::StopFreeze => STOP_FREEZE,
+        PduType::Acknowledge | PduType::AcknowledgeReliable => ACKNOWLEDGE,
+        PduType::ActionRequest => ACTION_REQUEST,
+        PduType::ActionResponse | PduType::ActionResponseReliable => ACTION_RESPONSE,
+        PduType::DataQuery => DATA_QUERY,
+        PduType::SetData | PduType::Data => SET_DATA,
+        PduType::EventReport | PduType::EventReportReliable => EVENT_REPORT,
+        PduType::Comment | PduType::CommentReliable => COMMENT,
+        PduType::ElectromagneticEmission => ELECTROMAGNETIC_EMISSION,
+        PduType::Designator => DESIGNATOR,
+        PduType::Transmitter => TRANSMITTER,
+        PduType::Signal => SIGNAL,
+        PduType::Receiver => RECEIVER,
+        PduType::IFF => IFF,
+        PduType::UnderwaterAcoustic => UNDERWATER_ACOUSTIC,
+        PduType::SupplementalEmission => SUPPLEMENTAL_EMISSION,
+        PduType::IntercomSignal => INTERCOM_SIGNAL,
+        PduType::IntercomControl => INTERCOM_CONTROL,
+        PduType::AggregateState => AGGREGATE_STATE,
+        PduType::IsGroupOf => IS_GROUP_OF,
+        PduType::TransferOwnership => TRANSFER_OWNERSHIP,
+        PduType::IsPartOf => IS_PART_OF,
+        PduType::MinefieldState => MINEFIELD_STATE,
+        PduType::MinefieldQuery => MINEFIELD_QUERY,
+        PduType::MinefieldData => MINEFIELD_DATA,
+        PduType::MinefieldResponseNack => MINEFIELD_RESPONSE_NACK,
+        PduType::EnvironmentalProcess => ENVIRONMENTAL_PROCESS,
+        PduType::GriddedData => GRIDDED_DATA,
+        PduType::PointObjectState => POINT_OBJECT_STATE,
+        PduType::LinearObjectState => LINEAR_OBJECT_STATE,
+        PduType::ArealObjectState => AREAL_OBJECT_STATE,
+        PduType::CreateEntityReliable | PduType::RemoveEntityReliable => {
+            CREATE_REMOVE_ENTITY_RELIABLE
+        }
+        PduType::StartResumeReliable => START_RESUME_RELIABLE,
+        PduType::StopFreezeReliable => STOP_FREEZE_RELIABLE,
+        PduType::ActionRequestReliable => ACTION_REQUEST_RELIABLE,
+        PduType::DataQueryReliable => DATA_QUERY_RELIABLE,
+        PduType::SetDataReliable => SET_DATA_RELIABLE,
+        PduType::DataReliable => DATA_RELIABLE,
+        PduType::RecordQueryReliable => RECORD_QUERY_RELIABLE,
+        PduType::SetRecordReliable => SET_RECORD_RELIABLE,
+        PduType::CollisionElastic => COLLISION_ELASTIC,
+        PduType::EntityStateUpdate => ENTITY_STATE_UPDATE,
+        PduType::DirectedEnergyFire => DIRECTED_ENERGY_FIRE,
+        PduType::EntityDamageStatus => ENTITY_DAMAGE_STATUS,
+        PduType::InformationOperationsAction => INFORMATION_OPERATIONS_ACTION,
+        PduType::InformationOperationsReport => INFORMATION_OPERATIONS_REPORT,
+        PduType::Attribute => ATTRIBUTE,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A zeroed PDU of `len` bytes whose header gives its type and length.
+    fn pdu(pdu_type: PduType, len: usize) -> Vec<u8> {
+        let mut data = vec![0; len];
+        data[PDU_TYPE_OFFSET] = pdu_type as u8;
+        data[LENGTH_OFFSET..LENGTH_OFFSET + 2]
+            .copy_from_slice(&u16::try_from(len).unwrap().to_be_bytes());
+        data
+    }
+
+    fn find<'a>(fields: &'a [Field], path: &[&str]) -> &'a Field {
+        let (name, rest) = path.split_first().unwrap();
+        let field = fields.iter().find(|field| field.name == *name).unwrap();
+        if rest.is_empty() {
+            field
+        } else {
+            find(&field.children, rest)
+        }
+    }
+
+    #[test]
+    fn pdus_of_their_standard_size_decode_whole() {
+        let sizes = [
+            (PduType::EntityState, 144),
+            (PduType::Fire, 96),
+            (PduType::Detonation, 104),
+            (PduType::Collision, 60),
+            (PduType::CollisionElastic, 100),
+            (PduType::EntityStateUpdate, 72),
+            (PduType::ServiceRequest, 28),
+            (PduType::ResupplyOffer, 28),
+            (PduType::ResupplyReceived, 28),
+            (PduType::ResupplyCancel, 24),
+            (PduType::RepairComplete, 28),
+            (PduType::RepairResponse, 28),
+            (PduType::CreateEntity, 28),
+            (PduType::RemoveEntity, 28),
+            (PduType::StartResume, 44),
+            (PduType::StopFreeze, 40),
+            (PduType::Acknowledge, 32),
+            (PduType::ActionRequest, 40),
+            (PduType::ActionResponse, 40),
+            (PduType::DataQuery, 40),
+            (PduType::SetData, 40),
+            (PduType::Data, 40),
+            (PduType::EventReport, 40),
+            (PduType::Comment, 32),
+            (PduType::ElectromagneticEmission, 28),
+            (PduType::Designator, 88),
+            (PduType::Transmitter, 104),
+            (PduType::Signal, 32),
+            (PduType::Receiver, 36),
+            (PduType::IFF, 60),
+            (PduType::UnderwaterAcoustic, 32),
+            (PduType::SupplementalEmission, 28),
+            (PduType::IntercomSignal, 32),
+            (PduType::IntercomControl, 40),
+            (PduType::AggregateState, 136),
+            (PduType::IsGroupOf, 40),
+            (PduType::TransferOwnership, 40),
+            (PduType::IsPartOf, 52),
+            (PduType::MinefieldState, 72),
+            (PduType::MinefieldQuery, 40),
+            (PduType::MinefieldData, 44),
+            (PduType::MinefieldResponseNack, 26),
+            (PduType::EnvironmentalProcess, 32),
+            (PduType::GriddedData, 64),
+            (PduType::PointObjectState, 88),
+            (PduType::LinearObjectState, 40),
+            (PduType::ArealObjectState, 48),
+            (PduType::DirectedEnergyFire, 88),
+            (PduType::EntityDamageStatus, 24),
+            (PduType::InformationOperationsAction, 56),
+            (PduType::InformationOperationsReport, 40),
+            (PduType::Attribute, 32),
+            (PduType::CreateEntityReliable, 32),
+            (PduType::RemoveEntityReliable, 32),
+            (PduType::StartResumeReliable, 48),
+            (PduType::StopFreezeReliable, 40),
+            (PduType::AcknowledgeReliable, 32),
+            (PduType::ActionRequestReliable, 44),
+            (PduType::ActionResponseReliable, 40),
+            (PduType::DataQueryReliable, 44),
+            (PduType::SetDataReliable, 40),
+            (PduType::DataReliable, 40),
+            (PduType::EventReportReliable, 40),
+            (PduType::CommentReliable, 32),
+            (PduType::RecordQueryReliable, 40),
+            (PduType::SetRecordReliable, 40),
+        ];
+
+        for (pdu_type, len) in sizes {
+            let inspection = inspect(&pdu(pdu_type, len));
+
+            assert!(
+                inspection.warnings.is_empty(),
+                "{pdu_type:?}: {:?}",
+                inspection.warnings
+            );
+            assert_eq!(inspection.decoded_len, len, "{pdu_type:?}");
+        }
+    }
+
+    #[test]
+    fn entity_state_fields_are_placed() {
+        let mut data = pdu(PduType::EntityState, 160);
+        data[12..18].copy_from_slice(&[0, 1, 0, 2, 0, 3]);
+        data[19] = 1;
+        data[129..133].copy_from_slice(b"TANK");
+        data[144] = 1;
+
+        let inspection = inspect(&data);
+        assert!(inspection.warnings.is_empty(), "{:?}", inspection.warnings);
+        assert_eq!(inspection.decoded_len, 160);
+
+        assert_eq!(find(&inspection.fields, &["entity_id"]).value, "1:2:3");
+
+        let marking = find(&inspection.fields, &["entity_marking"]);
+        assert_eq!(marking.offset, Some(128));
+        assert_eq!(marking.length, 12);
+        assert_eq!(marking.value, "\"TANK\"");
+
+        let parameter = find(&inspection.fields, &["variable_parameters", "[0]"]);
+        assert_eq!(parameter.offset, Some(144));
+        assert_eq!(parameter.length, 16);
+    }
+
+    #[test]
+    fn short_pdus_keep_the_fields_before_the_end() {
+        let mut data = pdu(PduType::EntityState, 144);
+        data[19] = 1;
+
+        let inspection = inspect(&data);
+        assert_eq!(
+            inspection.warnings,
+            ["The PDU ends 1 bytes short of the end of `record_type`"]
+        );
+        assert_eq!(inspection.decoded_len, 144);
+        assert!(
+            find(&inspection.fields, &["entity_marking"])
+                .offset
+                .is_some()
+        );
+        assert!(
+            find(&inspection.fields, &["variable_parameters"])
+                .children
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn mismatched_lengths_decode_only_the_header() {
+        let mut data = pdu(PduType::EntityState, 144);
+        data[LENGTH_OFFSET..LENGTH_OFFSET + 2].copy_from_slice(&200_u16.to_be_bytes());
+
+        let inspection = inspect(&data);
+        assert_eq!(inspection.warnings.len(), 1);
+        assert!(inspection.warnings[0].contains("only the header is decoded"));
+        assert_eq!(inspection.decoded_len, PDU_HEADER_SIZE);
+        assert_eq!(inspection.fields.len(), 2);
+        assert_eq!(find(&inspection.fields, &["undecoded"]).length, 132);
+    }
+
+    #[test]
+    fn unsupported_pdus_decode_only_the_header() {
+        let inspection = inspect(&pdu(PduType::TimeSpacePositionInformation, 40));
+
+        assert_eq!(inspection.warnings.len(), 1);
+        assert!(inspection.warnings[0].contains("not supported"));
+        assert_eq!(inspection.decoded_len, PDU_HEADER_SIZE);
+    }
+
+    #[test]
+    fn variable_datums_are_padded() {
+        let mut data = pdu(PduType::SetData, 56);
+        data[39] = 1;
+        data[47] = 24;
+        data[48..51].copy_from_slice(b"abc");
+
+        let inspection = inspect(&data);
+        assert!(inspection.warnings.is_empty(), "{:?}", inspection.warnings);
+        assert_eq!(inspection.decoded_len, 56);
+
+        let datum = find(&inspection.fields, &["variable_datum_records", "[0]"]);
+        assert_eq!(datum.value, "0: \"abc\"");
+        assert_eq!(datum.length, 16);
+        let padding = find(&datum.children, &["padding"]);
+        assert_eq!((padding.offset, padding.length), (Some(51), 5));
+    }
+
+    #[test]
+    fn any_pdu_decodes_within_its_data() {
+        fn within(field: &Field, len: usize) {
+            assert!(field.end().is_none_or(|end| end <= len), "{field:?}");
+            field.children.iter().for_each(|child| within(child, len));
+        }
+
+        for code in 0..=u8::MAX {
+            for len in [12, 13, 40, 100, 600] {
+                for fill in [0, 0xFF] {
+                    let mut data = vec![fill; len];
+                    data[PDU_TYPE_OFFSET] = code;
+                    data[LENGTH_OFFSET..LENGTH_OFFSET + 2]
+                        .copy_from_slice(&u16::try_from(len).unwrap().to_be_bytes());
+
+                    let inspection = inspect(&data);
+                    assert!(inspection.decoded_len <= len);
+                    inspection
+                        .fields
+                        .iter()
+                        .for_each(|field| within(field, len));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,138 @@
+//! Offline map backgrounds read from locally stored `GeoJSON` files.
+
+use std::path::Path;
+
+use serde::Serialize;
+use serde_json::Value;
+
+/// A line or polygon ring of longitude and latitude pairs in degrees.
+#[derive(Serialize, Debug, Clone)]
+pub struct MapPath {
+    pub closed: bool,
+    pub points: Vec<[f64; 2]>,
+}
+
+/// The geometry of a `GeoJSON` file flattened for drawing, without its properties.
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct MapBackground {
+    /// Name of the file the background was read from
+    pub name: String,
+    pub paths: Vec<MapPath>,
+    pub points: Vec<[f64; 2]>,
+}
+
+impl MapBackground {
+    /// Reads every geometry in a `GeoJSON` file.
+    ///
+    /// # Errors
+    /// - Returns an error if the file cannot be read, is not `GeoJSON` or contains no geometry
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("unable to read {}: {e}", path.display()))?;
+        let json: Value = serde_json::from_str(&contents)
+            .map_err(|e| format!("{} is not valid JSON: {e}", path.display()))?;
+
+        let mut background = Self {
+            name: path
+                .file_name()
+                .map_or_else(String::new, |name| name.to_string_lossy().into_owned()),
+            ..Self::default()
+        };
+        background
+            .add(&json)
+            .map_err(|e| format!("{} is not valid GeoJSON: {e}", path.display()))?;
+
+        if background.paths.is_empty() && background.points.is_empty() {
+            return Err(format!("{} contains no geometry", path.display()));
+        }
+
+        Ok(background)
+    }
+
+    /// Adds a `GeoJSON` object and everything nested in it.
+    fn add(&mut self, object: &Value) -> Result<(), String> {
+        let member = |name: &str| {
+            object
+                .get(name)
+                .ok_or_else(|| format!("object is missing its {name} member"))
+        };
+
+        match object.get("type").and_then(Value::as_str) {
+            Some("FeatureCollection") => {
+                for feature in array(member("features")?)? {
+                    self.add(feature)?;
+                }
+            }
+            // Features without a location have a null geometry
+            Some("Feature") => match member("geometry")? {
+                Value::Null => {}
+                geometry => self.add(geometry)?,
+            },
+            Some("GeometryCollection") => {
+                for geometry in array(member("geometries")?)? {
+                    self.add(geometry)?;
+                }
+            }
+            Some("Point") => self.points.push(position(member("coordinates")?)?),
+            Some("MultiPoint") => {
+                self.points.extend(positions(member("coordinates")?)?);
+            }
+            Some("LineString") => self.paths.push(MapPath {
+                closed: false,
+                points: positions(member("coordinates")?)?,
+            }),
+            Some("MultiLineString") => {
+                for line in array(member("coordinates")?)? {
+                    self.paths.push(MapPath {
+                        closed: false,
+                        points: positions(line)?,
+                    });
+                }
+            }
+            Some("Polygon") => self.add_polygon(member("coordinates")?)?,
+            Some("MultiPolygon") => {
+                for polygon in array(member("coordinates")?)? {
+                    self.add_polygon(polygon)?;
+                }
+            }
+            Some(other) => return Err(format!("unsupported type {other}")),
+            None => return Err("object has no type".into()),
+        }
+
+        Ok(())
+    }
+
+    /// Adds the outer ring and holes of a polygon.
+    fn add_polygon(&mut self, rings: &Value) -> Result<(), String> {
+        for ring in array(rings)? {
+            self.paths.push(MapPath {
+                closed: true,
+                points: positions(ring)?,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+fn array(value: &Value) -> Result<&Vec<Value>, String> {
+    value
+        .as_array()
+        .ok_or_else(|| format!("expected an array, found {value}"))
+}
+
+/// Reads the longitude and latitude of a position, ignoring any altitude.
+fn position(value: &Value) -> Result<[f64; 2], String> {
+    match array(value)?.as_slice() {
+        [longitude, latitude, ..] => longitude
+            .as_f64()
+            .zip(latitude.as_f64())
+            .map(<[f64; 2]>::from)
+            .ok_or_else(|| format!("invalid position {value}")),
+        _ => Err(format!("invalid position {value}")),
+    }
+}
+
+fn positions(value: &Value) -> Result<Vec<[f64; 2]>, String> {
+    array(value)?.iter().map(position).collect()
+}
@@ -0,0 +1,154 @@
+//! Background tasks that follow the exercise traffic received by the network service.
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::time::{MissedTickBehavior, interval};
+
+use crate::core::{AppState, Datagram, now_ms};
+
+/// Snapshots are published at most this often by default while traffic keeps changing them
+const DEFAULT_PUBLISH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Name of the event carrying notices raised by monitors
+const NOTICE_EVENT: &str = "notice";
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum NoticeLevel {
+    Info,
+    Warning,
+}
+
+/// A change in the exercise that operators should be told about.
+#[derive(Serialize, Debug, Clone)]
+pub struct Notice {
+    pub level: NoticeLevel,
+    pub message: String,
+}
+
+impl Notice {
+    #[must_use]
+    pub const fn info(message: String) -> Self {
+        Self {
+            level: NoticeLevel::Info,
+            message,
+        }
+    }
+
+    #[must_use]
+    pub const fn warning(message: String) -> Self {
+        Self {
+            level: NoticeLevel::Warning,
+            message,
+        }
+    }
+}
+
+/// State built up from received datagrams and published to the frontend as events.
+pub trait Monitor: Send + 'static {
+    /// Name of the event carrying each snapshot
+    const EVENT: &'static str;
+
+    /// Minimum time between published snapshots
+    const PUBLISH_INTERVAL: Duration = DEFAULT_PUBLISH_INTERVAL;
+
+    type Snapshot: Serialize + Clone;
+
+    /// Updates the state from `datagram`, returning whether anything changed.
+    fn observe(&mut self, datagram: &Datagram) -> bool;
+
+    /// Updates the state from a PDU Centurion sent, returning whether anything changed.
+    fn observe_sent(&mut self, _datagram: &Datagram) -> bool {
+        false
+    }
+
+    /// Whether the snapshot changes with time alone, so is published on every tick.
+    fn animated(&self) -> bool {
+        false
+    }
+
+    /// Ages the state to `now_ms` Unix milliseconds on every tick, returning whether
+    /// anything changed and adding anything operators should hear about to `notices`.
+    fn expire(&mut self, _now_ms: u64, _notices: &mut Vec<Notice>) -> bool {
+        false
+    }
+
+    fn snapshot(&self) -> Self::Snapshot;
+}
+
+/// Logs a notice and emits it to the dashboard.
+pub fn notify(app: &AppHandle, notice: Notice) {
+    tracing::info!("{}", notice.message);
+    app.emit(NOTICE_EVENT, notice)
+        .unwrap_or_else(|e| tracing::error!("unable to emit notice event: {e}"));
+}
+
+/// Feeds every datagram heard or sent by the network service to the monitor selected from
+/// the app state and publishes its snapshot whenever traffic or expiry changes it.
+///
+/// Notices raised while expiring are emitted as they occur.
+pub async fn watch<M: Monitor>(app: AppHandle, monitor: fn(&AppState) -> &Mutex<M>) {
+    let state = app.state::<AppState>();
+    let mut traffic = state.network.subscribe();
+    let mut sent = state.network.subscribe_sent();
+
+    let mut publish = interval(M::PUBLISH_INTERVAL);
+    publish.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    let mut changed = false;
+
+    loop {
+        let animated = monitor(&state).lock().is_ok_and(|m| m.animated());
+
+        tokio::select! {
+            received = traffic.recv() => match received {
+                Ok(datagram) => {
+                    if let Ok(mut monitor) = monitor(&state).lock() {
+                        changed |= monitor.observe(&datagram);
+                    }
+                }
+                Err(RecvError::Lagged(missed)) => {
+                    tracing::warn!("{} monitor missed {missed} datagrams", M::EVENT);
+                }
+                Err(RecvError::Closed) => return,
+            },
+            sent_pdu = sent.recv() => match sent_pdu {
+                Ok(datagram) => {
+                    if let Ok(mut monitor) = monitor(&state).lock() {
+                        changed |= monitor.observe_sent(&datagram);
+                    }
+                }
+                Err(RecvError::Lagged(missed)) => {
+                    tracing::warn!("{} monitor missed {missed} sent PDUs", M::EVENT);
+                }
+                Err(RecvError::Closed) => return,
+            },
+            _ = publish.tick() => {
+                let mut notices = Vec::new();
+                let now = now_ms().unwrap_or_default();
+
+                let Ok(snapshot) = monitor(&state).lock().map(|mut m| {
+                    changed |= m.expire(now, &mut notices);
+                    (changed || animated).then(|| m.snapshot())
+                }) else {
+                    tracing::error!("{} monitor lock poisoned", M::EVENT);
+                    return;
+                };
+
+                for notice in notices {
+                    notify(&app, notice);
+                }
+
+                if let Some(snapshot) = snapshot {
+                    app.emit(M::EVENT, snapshot)
+                        .unwrap_or_else(|e| tracing::error!("unable to emit {} event: {e}", M::EVENT));
+                    changed = false;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,457 @@
+//! The long-lived DIS network service shared by commands, monitors and recorders.
+
+use std::collections::VecDeque;
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use bytes::Bytes;
+use open_dis_rust::common::constants::MAX_PDU_SIZE_OCTETS;
+use socket2::{Domain, Protocol, Socket, Type};
+use tauri::async_runtime::{JoinHandle, spawn};
+use tokio::net::UdpSocket;
+use tokio::sync::{Mutex, broadcast};
+use tokio::time::sleep;
+
+use crate::config::Network;
+
+/// Datagrams buffered per subscriber before the slowest one starts missing traffic
+const TRAFFIC_CAPACITY: usize = 4096;
+
+/// Delays before receiving again after the first and after repeated receive errors
+const MIN_RECEIVE_BACKOFF: Duration = Duration::from_millis(10);
+const MAX_RECEIVE_BACKOFF: Duration = Duration::from_secs(5);
+
+/// PDUs remembered by `OwnTraffic` to recognise them when they come back
+const OWN_PDUS: usize = 64;
+
+/// A datagram received from, or sent to, the exercise network.
+#[derive(Debug, Clone)]
+pub struct Datagram {
+    pub data: Bytes,
+    pub source: SocketAddr,
+    /// Time the datagram was received, or sent for datagrams Centurion sent itself
+    pub received_at: SystemTime,
+    /// Whether Centurion sent the datagram replaying a capture rather than on its own behalf
+    pub replayed: bool,
+}
+
+/// Recognises PDUs Centurion sent when multicast loopback or broadcast delivers them back
+/// to the receive socket, so monitors do not mistake them for another application's.
+#[derive(Debug, Default)]
+pub struct OwnTraffic {
+    /// Address of the socket PDUs are sent from
+    local: Option<SocketAddr>,
+    /// Addresses of this host that sent PDUs came back from, which a send socket bound to
+    /// the unspecified address is seen from
+    host_ips: Vec<IpAddr>,
+    recent: VecDeque<Bytes>,
+}
+
+impl OwnTraffic {
+    /// Remembers a PDU Centurion sent.
+    pub fn sent(&mut self, datagram: &Datagram) {
+        self.local = Some(datagram.source);
+
+        if self.recent.len() == OWN_PDUS {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(datagram.data.clone());
+    }
+
+    /// Whether `datagram` was sent by Centurion, judged by it being a copy of a PDU sent
+    /// recently or coming from the send socket's port on one of this host's addresses.
+    pub fn is_own(&mut self, datagram: &Datagram) -> bool {
+        let Some(local) = self.local else {
+            return false;
+        };
+        let source = datagram.source;
+        let from_send_port = source.port() == local.port();
+
+        if self.recent.contains(&datagram.data) {
+            if from_send_port && !self.host_ips.contains(&source.ip()) {
+                self.host_ips.push(source.ip());
+            }
+            return true;
+        }
+
+        // Other hosts may send from the same port number
+        from_send_port && (source.ip() == local.ip() || self.host_ips.contains(&source.ip()))
+    }
+}
+
+/// The sockets bound for one set of network settings.
+struct Bound {
+    settings: Network,
+    send: Arc<UdpSocket>,
+    destination: SocketAddr,
+    listeners: Vec<JoinHandle<()>>,
+}
+
+impl Drop for Bound {
+    fn drop(&mut self) {
+        for listener in &self.listeners {
+            listener.abort();
+        }
+    }
+}
+
+/// Owns the exercise sockets and fans every received datagram out to its subscribers.
+///
+/// The receive socket listens on the scenario's interface port, shares it with other
+/// applications on the host and joins the configured multicast groups. PDUs are sent from
+/// a separate socket on an ephemeral port of the send interface, whose unicast replies are
+/// delivered to subscribers as well. PDUs sent successfully are published separately.
+pub struct NetworkService {
+    bound: Mutex<Option<Bound>>,
+    traffic: broadcast::Sender<Datagram>,
+    sent: broadcast::Sender<Datagram>,
+}
+
+impl Default for NetworkService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NetworkService {
+    #[must_use]
+    pub fn new() -> Self {
+        let (traffic, _) = broadcast::channel(TRAFFIC_CAPACITY);
+        let (sent, _) = broadcast::channel(TRAFFIC_CAPACITY);
+
+        Self {
+            bound: Mutex::new(None),
+            traffic,
+            sent,
+        }
+    }
+
+    /// Binds the sockets for `settings`, replacing those bound for earlier settings.
+    ///
+    /// Does nothing when the service is already running with the same settings.
+    ///
+    /// # Errors
+    /// - Returns an error if an address cannot be parsed or a socket cannot be bound, in
+    ///   which case the service is left stopped
+    pub async fn apply(&self, settings: &Network) -> Result<(), String> {
+        let mut bound = self.bound.lock().await;
+
+        if bound.as_ref().is_some_and(|b| b.settings == *settings) {
+            return Ok(());
+        }
+
+        // Release the previous sockets before binding their replacements
+        *bound = None;
+        *bound = Some(self.bind(settings)?);
+        drop(bound);
+
+        Ok(())
+    }
+
+    fn bind(&self, settings: &Network) -> Result<Bound, String> {
+        let interface = parse_ip("interface", &settings.interface_ip)?;
+        let send_interface = settings
+            .send_interface_ip
+            .as_deref()
+            .map_or(Ok(interface), |ip| parse_ip("send interface", ip))?;
+        let destination_ip = parse_ip("destination", &settings.destination_ip)?;
+
+        if send_interface.is_ipv4() != destination_ip.is_ipv4() {
+            return Err(format!(
+                "send interface {send_interface} and destination {destination_ip} use different IP versions"
+            ));
+        }
+
+        let groups = settings
+            .multicast_groups
+            .iter()
+            .map(|group| parse_ip("multicast group", group))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Most platforms filter datagrams addressed to a group out of sockets bound to a
+        // unicast address, so group members listen on the unspecified address instead
+        let receive_ip = if groups.is_empty() {
+            interface
+        } else {
+            unspecified(interface)
+        };
+
+        let receive_addr = SocketAddr::new(receive_ip, settings.interface_port);
+        let receive = open(receive_addr, settings)
+            .map_err(|e| format!("unable to bind receive socket on {receive_addr}: {e}"))?;
+
+        for group in groups {
+            join(
+                &receive,
+                group,
+                interface,
+                settings.multicast_interface_index,
+            )?;
+            tracing::info!("Joined multicast group {group}");
+        }
+
+        let send = open(SocketAddr::new(send_interface, 0), settings)
+            .map_err(|e| format!("unable to bind send socket on {send_interface}: {e}"))?;
+        configure_multicast(&send, send_interface, settings)
+            .map_err(|e| format!("unable to configure multicast on {send_interface}: {e}"))?;
+
+        let receive =
+            into_tokio(receive).map_err(|e| format!("unable to register receive socket: {e}"))?;
+        let send =
+            Arc::new(into_tokio(send).map_err(|e| format!("unable to register send socket: {e}"))?);
+
+        let listeners = vec![
+            self.listen(Arc::new(receive)),
+            self.listen(Arc::clone(&send)),
+        ];
+
+        tracing::info!(
+            "Network service listening on {receive_addr}, sending from {send_interface} to {}:{}",
+            settings.destination_ip,
+            settings.destination_port
+        );
+
+        Ok(Bound {
+            settings: settings.clone(),
+            send,
+            destination: SocketAddr::new(destination_ip, settings.destination_port),
+            listeners,
+        })
+    }
+
+    /// Returns a receiver for every datagram arriving after this call.
+    #[must_use]
+    pub fn subscribe(&self) -> broadcast::Receiver<Datagram> {
+        self.traffic.subscribe()
+    }
+
+    /// Returns a receiver for every PDU this service sends after this call.
+    #[must_use]
+    pub fn subscribe_sent(&self) -> broadcast::Receiver<Datagram> {
+        self.sent.subscribe()
+    }
+
+    /// Sends a PDU to the scenario's destination address.
+    ///
+    /// # Errors
+    /// - Returns an error if the service has not been started or the send fails
+    pub async fn send(&self, pdu: &[u8]) -> Result<(), String> {
+        self.transmit(pdu, false).await
+    }
+
+    /// Sends a PDU read from a capture to the scenario's destination address, publishing
+    /// it as replayed so monitors can tell it from Centurion's own PDUs.
+    ///
+    /// # Errors
+    /// - Returns an error if the service has not been started or the send fails
+    pub async fn replay(&self, pdu: &[u8]) -> Result<(), String> {
+        self.transmit(pdu, true).await
+    }
+
+    async fn transmit(&self, pdu: &[u8], replayed: bool) -> Result<(), String> {
+        let (socket, destination) = self
+            .bound
+            .lock()
+            .await
+            .as_ref()
+            .map(|bound| (Arc::clone(&bound.send), bound.destination))
+            .ok_or("the network service is not running; load a scenario first")?;
+
+        socket
+            .send_to(pdu, destination)
+            .await
+            .map_err(|e| format!("unable to send to {destination}: {e}"))?;
+
+        if let Ok(source) = socket.local_addr() {
+            // Sending only fails while nothing is subscribed
+            let _ = self.sent.send(Datagram {
+                data: Bytes::copy_from_slice(pdu),
+                source,
+                received_at: SystemTime::now(),
+                replayed,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Forwards every datagram received on `socket` to the subscribers.
+    fn listen(&self, socket: Arc<UdpSocket>) -> JoinHandle<()> {
+        let traffic = self.traffic.clone();
+
+        spawn(async move {
+            let mut buf = vec![0u8; MAX_PDU_SIZE_OCTETS];
+            let mut backoff = Duration::ZERO;
+
+            loop {
+                match socket.recv_from(&mut buf).await {
+                    Ok((len, source)) => {
+                        backoff = Duration::ZERO;
+
+                        // Sending only fails while nothing is subscribed
+                        let _ = traffic.send(Datagram {
+                            data: Bytes::copy_from_slice(&buf[..len]),
+                            source,
+                            received_at: SystemTime::now(),
+                            replayed: false,
+                        });
+                    }
+                    Err(e) => {
+                        // A socket that keeps failing would otherwise spin and flood the log
+                        backoff = (backoff * 2).clamp(MIN_RECEIVE_BACKOFF, MAX_RECEIVE_BACKOFF);
+                        tracing::warn!(
+                            "Network service receive error, retrying in {} ms: {e}",
+                            backoff.as_millis()
+                        );
+                        sleep(backoff).await;
+                    }
+                }
+            }
+        })
+    }
+}
+
+fn parse_ip(name: &str, value: &str) -> Result<IpAddr, String> {
+    value
+        .parse()
+        .map_err(|e| format!("invalid {name} address {value}: {e}"))
+}
+
+const fn unspecified(address: IpAddr) -> IpAddr {
+    match address {
+        IpAddr::V4(_) => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+        IpAddr::V6(_) => IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+    }
+}
+
+/// Binds a UDP socket that may share its port with other applications.
+fn open(address: SocketAddr, settings: &Network) -> io::Result<Socket> {
+    let socket = Socket::new(
+        Domain::for_address(address),
+        Type::DGRAM,
+        Some(Protocol::UDP),
+    )?;
+
+    // Simulators running on the same host commonly hold the DIS port as well
+    socket.set_reuse_address(true)?;
+
+    // IPv6 has no broadcast; multicast to all nodes takes its place
+    if address.is_ipv4() {
+        socket.set_broadcast(settings.enable_broadcast)?;
+    }
+
+    socket.set_nonblocking(true)?;
+    socket.bind(&address.into())?;
+
+    Ok(socket)
+}
+
+fn into_tokio(socket: Socket) -> io::Result<UdpSocket> {
+    UdpSocket::from_std(socket.into())
+}
+
+/// Joins `group` on the receive interface, which IPv6 identifies by index.
+fn join(socket: &Socket, group: IpAddr, interface: IpAddr, index: u32) -> Result<(), String> {
+    if !group.is_multicast() {
+        return Err(format!("{group} is not a multicast group address"));
+    }
+
+    match (group, interface) {
+        (IpAddr::V4(group), IpAddr::V4(interface)) => socket.join_multicast_v4(&group, &interface),
+        (IpAddr::V6(group), IpAddr::V6(_)) => socket.join_multicast_v6(&group, index),
+        _ => {
+            return Err(format!(
+                "multicast group {group} and interface {interface} use different IP versions"
+            ));
+        }
+    }
+    .map_err(|e| format!("unable to join multicast group {group}: {e}"))
+}
+
+/// Applies the scenario's multicast hop limit, loopback and outgoing interface.
+fn configure_multicast(socket: &Socket, interface: IpAddr, settings: &Network) -> io::Result<()> {
+    match interface {
+        IpAddr::V4(interface) => {
+            socket.set_multicast_ttl_v4(settings.multicast_ttl)?;
+            socket.set_multicast_loop_v4(settings.multicast_loopback)?;
+            if !interface.is_unspecified() {
+                socket.set_multicast_if_v4(&interface)?;
+            }
+        }
+        IpAddr::V6(_) => {
+            socket.set_multicast_hops_v6(settings.multicast_ttl)?;
+            socket.set_multicast_loop_v6(settings.multicast_loopback)?;
+            if settings.multicast_interface_index != 0 {
+                socket.set_multicast_if_v6(settings.multicast_interface_index)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn datagram(data: &'static [u8], source: &str) -> Datagram {
+        Datagram {
+            data: Bytes::from_static(data),
+            source: source.parse().expect("a socket address"),
+            received_at: SystemTime::now(),
+            replayed: false,
+        }
+    }
+
+    #[test]
+    fn nothing_is_own_before_sending() {
+        let mut own = OwnTraffic::default();
+        assert!(!own.is_own(&datagram(b"pdu", "10.0.0.2:3000")));
+    }
+
+    #[test]
+    fn copies_of_sent_pdus_are_own() {
+        let mut own = OwnTraffic::default();
+        own.sent(&datagram(b"sent", "0.0.0.0:50000"));
+
+        assert!(own.is_own(&datagram(b"sent", "10.0.0.1:50000")));
+        assert!(!own.is_own(&datagram(b"other", "10.0.0.2:3000")));
+    }
+
+    #[test]
+    fn unspecified_send_socket_learns_host_addresses() {
+        let mut own = OwnTraffic::default();
+        own.sent(&datagram(b"sent", "0.0.0.0:50000"));
+
+        // Another host using the same port number is not mistaken for Centurion
+        assert!(!own.is_own(&datagram(b"other", "10.0.0.2:50000")));
+
+        assert!(own.is_own(&datagram(b"sent", "10.0.0.1:50000")));
+        assert!(own.is_own(&datagram(b"reply", "10.0.0.1:50000")));
+        assert!(!own.is_own(&datagram(b"other", "10.0.0.2:50000")));
+        assert!(!own.is_own(&datagram(b"other", "10.0.0.1:3000")));
+    }
+
+    #[test]
+    fn bound_send_socket_matches_its_address() {
+        let mut own = OwnTraffic::default();
+        own.sent(&datagram(b"sent", "10.0.0.1:50000"));
+
+        assert!(own.is_own(&datagram(b"other", "10.0.0.1:50000")));
+        assert!(!own.is_own(&datagram(b"other", "10.0.0.2:50000")));
+    }
+
+    #[test]
+    fn only_recent_pdus_are_remembered() {
+        let mut own = OwnTraffic::default();
+        own.sent(&datagram(b"first", "10.0.0.1:50000"));
+        for _ in 0..OWN_PDUS {
+            own.sent(&datagram(b"later", "10.0.0.1:50000"));
+        }
+
+        assert!(!own.is_own(&datagram(b"first", "10.0.0.2:3000")));
+        assert!(own.is_own(&datagram(b"later", "10.0.0.2:3000")));
+    }
+}
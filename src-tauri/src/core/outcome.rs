@@ -0,0 +1,55 @@
+use std::fmt;
+
+use open_dis_rust::common::enums::{AcknowledgeResponseFlag, ActionResponseRequestStatus};
+use serde::Serialize;
+
+/// How a participant answered a SIMAN request, reduced from the Acknowledge response flag
+/// or Action Response request status it sent.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    Complied,
+    Refused,
+    Pending,
+    Partial,
+    /// The reply carried "other" or a status Centurion does not interpret
+    Unknown,
+}
+
+impl From<AcknowledgeResponseFlag> for Outcome {
+    fn from(flag: AcknowledgeResponseFlag) -> Self {
+        match flag {
+            AcknowledgeResponseFlag::AbleToComply => Self::Complied,
+            AcknowledgeResponseFlag::UnableToComply => Self::Refused,
+            AcknowledgeResponseFlag::PendingOperatorAction => Self::Pending,
+            AcknowledgeResponseFlag::Other => Self::Unknown,
+        }
+    }
+}
+
+impl From<ActionResponseRequestStatus> for Outcome {
+    fn from(status: ActionResponseRequestStatus) -> Self {
+        match status {
+            ActionResponseRequestStatus::Complete | ActionResponseRequestStatus::RequestDone => {
+                Self::Complied
+            }
+            ActionResponseRequestStatus::RequestRejected
+            | ActionResponseRequestStatus::InvalidTimeParameters
+            | ActionResponseRequestStatus::SimulationTimeExceeded
+            | ActionResponseRequestStatus::JoinExerciseRequestRejected => Self::Refused,
+            // A retransmission request means the action has not been carried out yet
+            ActionResponseRequestStatus::Pending
+            | ActionResponseRequestStatus::Executing
+            | ActionResponseRequestStatus::RetransmitRequestNow
+            | ActionResponseRequestStatus::RetransmitRequestLater => Self::Pending,
+            ActionResponseRequestStatus::PartiallyComplete => Self::Partial,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
@@ -0,0 +1,976 @@
+//! Conversion between capture files and the pcap and pcapng files of Wireshark and tcpdump.
+//!
+//! Exports are pcapng files on a raw IP link, each datagram framed in the IPv4 or IPv6 and
+//! UDP headers it would have carried on the wire. Imports accept classic pcap in either byte
+//! order with microsecond or nanosecond timestamps, and pcapng with any number of sections
+//! and interfaces. Packets may be captured on Ethernet (including VLAN tags), Linux cooked,
+//! BSD loopback or raw IP links; only unfragmented UDP datagrams are extracted.
+
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, ErrorKind, Read, Write};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, UNIX_EPOCH};
+
+use bytes::{BufMut, Bytes, BytesMut};
+use serde::Serialize;
+use tokio::sync::mpsc;
+
+use crate::core::{CaptureReader, CaptureWriter, Datagram};
+
+/// File extensions offered when importing
+pub const PCAP_EXTENSIONS: [&str; 3] = ["pcapng", "pcap", "cap"];
+
+/// File extension of exports
+pub const PCAPNG_EXTENSION: &str = "pcapng";
+
+const SECTION_HEADER: u32 = 0x0A0D_0D0A;
+const INTERFACE_DESCRIPTION: u32 = 0x0000_0001;
+const PACKET: u32 = 0x0000_0002;
+const SIMPLE_PACKET: u32 = 0x0000_0003;
+const ENHANCED_PACKET: u32 = 0x0000_0006;
+const BYTE_ORDER_MAGIC: u32 = 0x1A2B_3C4D;
+
+/// Option of an interface description giving its timestamp resolution
+const IF_TSRESOL: u16 = 9;
+
+/// Blocks larger than this are taken as corruption rather than read into memory
+const MAX_BLOCK_LEN: u32 = 64 * 1024 * 1024;
+
+const LINKTYPE_NULL: u32 = 0;
+const LINKTYPE_ETHERNET: u32 = 1;
+const LINKTYPE_RAW: u32 = 101;
+const LINKTYPE_LOOP: u32 = 108;
+const LINKTYPE_LINUX_SLL: u32 = 113;
+const LINKTYPE_IPV4: u32 = 228;
+const LINKTYPE_IPV6: u32 = 229;
+const LINKTYPE_LINUX_SLL2: u32 = 276;
+
+const ETHERTYPE_IPV4: u16 = 0x0800;
+const ETHERTYPE_IPV6: u16 = 0x86DD;
+const ETHERTYPE_VLAN: [u16; 3] = [0x8100, 0x88A8, 0x9100];
+
+const PROTOCOL_UDP: u8 = 17;
+const UDP_HEADER_LEN: usize = 8;
+
+/// Time to live written into exported IP headers
+const EXPORT_TTL: u8 = 64;
+
+/// Datagrams buffered between the thread reading a pcap file and the capture writer
+const IMPORT_QUEUE: usize = 1024;
+
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct ImportSummary {
+    /// Capture file the datagrams were written to
+    pub file: PathBuf,
+    /// Packets read from the pcap file
+    pub packets: u64,
+    /// UDP datagrams to the chosen ports written to the capture
+    pub datagrams: u64,
+    /// Datagrams to the chosen ports that were cut short by the capture's snapshot length
+    pub truncated: u64,
+}
+
+/// Writes every record of a capture to a pcapng file, addressed from the recorded source to
+/// `destination`. Returns the number of packets written.
+///
+/// # Errors
+/// - Returns an error if the capture cannot be read or the pcapng file cannot be written
+pub fn export_pcapng(
+    capture: &Path,
+    output: &Path,
+    destination: SocketAddr,
+) -> Result<usize, String> {
+    let write_error = |e: io::Error| format!("Unable to write {}: {e}", output.display());
+
+    let mut reader = CaptureReader::open(capture)?;
+    let mut file = BufWriter::new(File::create(output).map_err(write_error)?);
+
+    let mut header = BytesMut::new();
+    put_block(&mut header, SECTION_HEADER, |body| {
+        body.put_u32(BYTE_ORDER_MAGIC);
+        body.put_u16(1);
+        body.put_u16(0);
+        // Section length unknown
+        body.put_i64(-1);
+    });
+    put_block(&mut header, INTERFACE_DESCRIPTION, |body| {
+        body.put_u16(u16::try_from(LINKTYPE_RAW).unwrap_or_default());
+        body.put_u16(0);
+        // No snapshot length limit
+        body.put_u32(0);
+    });
+    file.write_all(&header).map_err(write_error)?;
+
+    for record in 0..reader.len() {
+        let datagram = reader.read(record)?;
+        let packet = frame(datagram.source, destination, &datagram.data)?;
+        let length = u32::try_from(packet.len()).unwrap_or(u32::MAX);
+        let time_us = datagram
+            .received_at
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| {
+                u64::try_from(elapsed.as_micros()).unwrap_or(u64::MAX)
+            });
+
+        let mut block = BytesMut::with_capacity(packet.len() + 32);
+        put_block(&mut block, ENHANCED_PACKET, |body| {
+            body.put_u32(0);
+            body.put_u32(u32::try_from(time_us >> 32).unwrap_or_default());
+            body.put_u32(u32::try_from(time_us & 0xFFFF_FFFF).unwrap_or_default());
+            body.put_u32(length);
+            body.put_u32(length);
+            body.put_slice(&packet);
+        });
+        file.write_all(&block).map_err(write_error)?;
+    }
+
+    file.flush().map_err(write_error)?;
+
+    Ok(reader.len())
+}
+
+/// Appends a pcapng block, padding its body to a multiple of four bytes.
+fn put_block(buffer: &mut BytesMut, block_type: u32, body: impl FnOnce(&mut BytesMut)) {
+    let mut contents = BytesMut::new();
+    body(&mut contents);
+    contents.put_bytes(0, contents.len().next_multiple_of(4) - contents.len());
+
+    let length = u32::try_from(contents.len() + 12).unwrap_or(u32::MAX);
+    buffer.put_u32(block_type);
+    buffer.put_u32(length);
+    buffer.put_slice(&contents);
+    buffer.put_u32(length);
+}
+
+/// Wraps a datagram in UDP and IP headers. Both addresses are written as IPv6 unless both
+/// are IPv4.
+fn frame(source: SocketAddr, destination: SocketAddr, data: &[u8]) -> Result<Vec<u8>, String> {
+    let udp_len = u16::try_from(UDP_HEADER_LEN + data.len())
+        .map_err(|_| format!("A {} byte datagram is too large to export", data.len()))?;
+
+    let mut udp = BytesMut::with_capacity(usize::from(udp_len));
+    udp.put_u16(source.port());
+    udp.put_u16(destination.port());
+    udp.put_u16(udp_len);
+    udp.put_u16(0);
+    udp.put_slice(data);
+
+    let mut packet = BytesMut::new();
+
+    let pseudo_header = match (source.ip(), destination.ip()) {
+        (IpAddr::V4(from), IpAddr::V4(to)) => {
+            let total_len = u16::try_from(20 + udp.len())
+                .map_err(|_| format!("A {} byte datagram is too large to export", data.len()))?;
+
+            let mut ip = BytesMut::with_capacity(20);
+            ip.put_u8(0x45);
+            ip.put_u8(0);
+            ip.put_u16(total_len);
+            ip.put_u16(0);
+            // Don't fragment
+            ip.put_u16(0x4000);
+            ip.put_u8(EXPORT_TTL);
+            ip.put_u8(PROTOCOL_UDP);
+            ip.put_u16(0);
+            ip.put_slice(&from.octets());
+            ip.put_slice(&to.octets());
+            let sum = checksum(&[&ip]);
+            ip[10..12].copy_from_slice(&sum.to_be_bytes());
+            packet.put_slice(&ip);
+
+            let mut pseudo = BytesMut::with_capacity(12);
+            pseudo.put_slice(&from.octets());
+            pseudo.put_slice(&to.octets());
+            pseudo.put_u8(0);
+            pseudo.put_u8(PROTOCOL_UDP);
+            pseudo.put_u16(udp_len);
+            pseudo
+        }
+        (from, to) => {
+            let (from, to) = (ipv6(from), ipv6(to));
+
+            packet.put_u32(0x6000_0000);
+            packet.put_u16(udp_len);
+            packet.put_u8(PROTOCOL_UDP);
+            packet.put_u8(EXPORT_TTL);
+            packet.put_slice(&from.octets());
+            packet.put_slice(&to.octets());
+
+            let mut pseudo = BytesMut::with_capacity(40);
+            pseudo.put_slice(&from.octets());
+            pseudo.put_slice(&to.octets());
+            pseudo.put_u32(u32::from(udp_len));
+            pseudo.put_bytes(0, 3);
+            pseudo.put_u8(PROTOCOL_UDP);
+            pseudo
+        }
+    };
+
+    // A computed checksum of zero is sent as all ones, zero meaning no checksum
+    let sum = match checksum(&[&pseudo_header, &udp]) {
+        0 => 0xFFFF,
+        sum => sum,
+    };
+    udp[6..8].copy_from_slice(&sum.to_be_bytes());
+    packet.put_slice(&udp);
+
+    Ok(packet.to_vec())
+}
+
+const fn ipv6(address: IpAddr) -> Ipv6Addr {
+    match address {
+        IpAddr::V4(address) => address.to_ipv6_mapped(),
+        IpAddr::V6(address) => address,
+    }
+}
+
+/// The Internet checksum of the concatenated chunks, each of an even length but the last.
+fn checksum(chunks: &[&[u8]]) -> u16 {
+    let mut sum = chunks
+        .iter()
+        .flat_map(|chunk| chunk.chunks(2))
+        .map(|pair| {
+            u32::from(u16::from_be_bytes([
+                pair[0],
+                pair.get(1).copied().unwrap_or(0),
+            ]))
+        })
+        .fold(0u32, |sum, word| {
+            let sum = sum + word;
+            (sum & 0xFFFF) + (sum >> 16)
+        });
+
+    while sum > 0xFFFF {
+        sum = (sum & 0xFFFF) + (sum >> 16);
+    }
+
+    !u16::try_from(sum).unwrap_or(u16::MAX)
+}
+
+/// Extracts the UDP datagrams sent to any of `ports` from a pcap or pcapng file into a new
+/// capture file at `output`.
+///
+/// # Errors
+/// - Returns an error if the pcap file cannot be read or the capture cannot be written
+pub async fn import_pcap(
+    input: &Path,
+    output: &Path,
+    ports: Vec<u16>,
+) -> Result<ImportSummary, String> {
+    let mut writer = CaptureWriter::create(output).await?;
+
+    // The pcap file is parsed on a blocking thread and its datagrams written as they arrive
+    let (sender, mut received) = mpsc::channel(IMPORT_QUEUE);
+    let path = input.to_path_buf();
+    let reading = tokio::task::spawn_blocking(move || extract(&path, &ports, &sender));
+
+    let mut written = Ok(());
+    while let Some(datagram) = received.recv().await {
+        written = writer.write(&datagram).await;
+        if written.is_err() {
+            // Dropping the receiver stops the reading thread
+            break;
+        }
+    }
+    drop(received);
+
+    let read = reading
+        .await
+        .map_err(|e| format!("Unable to import {}: {e}", input.display()))?;
+
+    written?;
+    let mut summary = read.map_err(|e| format!("Unable to read {}: {e}", input.display()))?;
+    summary.datagrams = writer.records() as u64;
+    summary.file = writer.path().to_path_buf();
+
+    writer.finish().await?;
+
+    Ok(summary)
+}
+
+/// Sends the datagrams to `ports` found in a pcap or pcapng file, returning the counts of
+/// packets read and datagrams truncated.
+fn extract(
+    path: &Path,
+    ports: &[u16],
+    sender: &mpsc::Sender<Datagram>,
+) -> Result<ImportSummary, String> {
+    let mut file = BufReader::new(File::open(path).map_err(|e| e.to_string())?);
+    let mut summary = ImportSummary::default();
+
+    let mut on_packet = |packet: Packet<'_>| {
+        summary.packets += 1;
+
+        match udp(packet.link_type, packet.data) {
+            Some(Udp::Complete {
+                source,
+                port,
+                payload,
+            }) if ports.contains(&port) => sender
+                .blocking_send(Datagram {
+                    data: Bytes::copy_from_slice(payload),
+                    source,
+                    received_at: UNIX_EPOCH + Duration::from_micros(packet.time_us),
+                    replayed: false,
+                })
+                .is_ok(),
+            Some(Udp::Truncated { port }) if ports.contains(&port) => {
+                summary.truncated += 1;
+                true
+            }
+            _ => true,
+        }
+    };
+
+    let mut magic = [0; 4];
+    file.read_exact(&mut magic)
+        .map_err(|_| "File is empty".to_string())?;
+
+    if u32::from_be_bytes(magic) == SECTION_HEADER {
+        read_pcapng(&mut file, &mut on_packet)?;
+    } else {
+        read_pcap(&mut file, magic, &mut on_packet)?;
+    }
+
+    Ok(summary)
+}
+
+/// A packet as captured, with its link-layer header.
+struct Packet<'a> {
+    time_us: u64,
+    link_type: u32,
+    data: &'a [u8],
+}
+
+#[derive(Clone, Copy)]
+enum Endian {
+    Big,
+    Little,
+}
+
+impl Endian {
+    fn u16(self, bytes: &[u8]) -> u16 {
+        let bytes = [bytes[0], bytes[1]];
+        match self {
+            Self::Big => u16::from_be_bytes(bytes),
+            Self::Little => u16::from_le_bytes(bytes),
+        }
+    }
+
+    fn u32(self, bytes: &[u8]) -> u32 {
+        let bytes = [bytes[0], bytes[1], bytes[2], bytes[3]];
+        match self {
+            Self::Big => u32::from_be_bytes(bytes),
+            Self::Little => u32::from_le_bytes(bytes),
+        }
+    }
+}
+
+/// Reads `len` bytes, or `None` at a clean end of file.
+fn read_exact_or_end(file: &mut impl Read, len: usize) -> Result<Option<Vec<u8>>, String> {
+    let mut buffer = vec![0; len];
+    let mut filled = 0;
+
+    while filled < len {
+        match file.read(&mut buffer[filled..]) {
+            Ok(0) if filled == 0 => return Ok(None),
+            Ok(0) => {
+                // A final packet cut short is common when a capture is interrupted
+                tracing::warn!("Ignoring a packet cut short at the end of the file");
+                return Ok(None);
+            }
+            Ok(read) => filled += read,
+            Err(e) if e.kind() == ErrorKind::Interrupted => {}
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+
+    Ok(Some(buffer))
+}
+
+/// Reads a classic pcap file after its magic number, stopping early if `on_packet` returns
+/// false.
+fn read_pcap(
+    file: &mut impl Read,
+    magic: [u8; 4],
+    on_packet: &mut impl FnMut(Packet<'_>) -> bool,
+) -> Result<(), String> {
+    let (endian, units_per_s) = match magic {
+        [0xA1, 0xB2, 0xC3, 0xD4] => (Endian::Big, 1_000_000),
+        [0xD4, 0xC3, 0xB2, 0xA1] => (Endian::Little, 1_000_000),
+        [0xA1, 0xB2, 0x3C, 0x4D] => (Endian::Big, 1_000_000_000),
+        [0x4D, 0x3C, 0xB2, 0xA1] => (Endian::Little, 1_000_000_000),
+        _ => return Err("Not a pcap or pcapng file".into()),
+    };
+
+    let header = read_exact_or_end(file, 20)?.ok_or("Incomplete pcap header")?;
+    // The upper bits of the link type may carry frame check sequence flags
+    let link_type = endian.u32(&header[16..20]) & 0x0FFF_FFFF;
+
+    while let Some(record) = read_exact_or_end(file, 16)? {
+        let seconds = u64::from(endian.u32(&record[0..4]));
+        let fraction = u64::from(endian.u32(&record[4..8]));
+        let captured = endian.u32(&record[8..12]);
+
+        if captured > MAX_BLOCK_LEN {
+            return Err(format!("Packet of {captured} bytes is too large"));
+        }
+
+        let Some(data) = read_exact_or_end(file, captured as usize)? else {
+            break;
+        };
+
+        let packet = Packet {
+            time_us: seconds * 1_000_000 + fraction * 1_000_000 / units_per_s,
+            link_type,
+            data: &data,
+        };
+
+        if !on_packet(packet) {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Link type and timestamp resolution of a pcapng interface.
+#[derive(Clone, Copy)]
+struct Interface {
+    link_type: u32,
+    units_per_s: u64,
+}
+
+/// Reads a pcapng file after the block type of its first section header, stopping early if
+/// `on_packet` returns false.
+fn read_pcapng(
+    file: &mut impl Read,
+    on_packet: &mut impl FnMut(Packet<'_>) -> bool,
+) -> Result<(), String> {
+    let mut endian = Endian::Big;
+    let mut interfaces: Vec<Interface> = Vec::new();
+    let mut last_time_us = 0;
+    let mut block_type = Some(SECTION_HEADER.to_be_bytes().to_vec());
+
+    while let Some(type_bytes) = block_type {
+        let length_bytes = read_exact_or_end(file, 4)?.ok_or("Incomplete pcapng block")?;
+
+        let block_type_value = if type_bytes == SECTION_HEADER.to_be_bytes() {
+            // Each section states its own byte order
+            let order = read_exact_or_end(file, 4)?.ok_or("Incomplete section header")?;
+            endian = match u32::from_be_bytes([order[0], order[1], order[2], order[3]]) {
+                BYTE_ORDER_MAGIC => Endian::Big,
+                magic if magic.swap_bytes() == BYTE_ORDER_MAGIC => Endian::Little,
+                _ => return Err("Invalid pcapng byte order".into()),
+            };
+            interfaces.clear();
+            SECTION_HEADER
+        } else {
+            endian.u32(&type_bytes)
+        };
+
+        // A section header holds at least its byte order magic, versions and section length
+        let min_length = if block_type_value == SECTION_HEADER {
+            28
+        } else {
+            12
+        };
+
+        let length = endian.u32(&length_bytes);
+        if length < min_length || !length.is_multiple_of(4) || length > MAX_BLOCK_LEN {
+            return Err(format!("Invalid pcapng block length {length}"));
+        }
+
+        // The byte order magic of a section header has already been read
+        let consumed = if block_type_value == SECTION_HEADER {
+            12
+        } else {
+            8
+        };
+        let Some(rest) = read_exact_or_end(file, length as usize - consumed)? else {
+            break;
+        };
+        let body = &rest[..rest.len() - 4];
+
+        match block_type_value {
+            INTERFACE_DESCRIPTION if body.len() >= 8 => {
+                interfaces.push(Interface {
+                    link_type: u32::from(endian.u16(&body[0..2])),
+                    units_per_s: timestamp_resolution(endian, &body[8..]),
+                });
+            }
+            ENHANCED_PACKET | PACKET if body.len() >= 20 => {
+                // The obsolete packet block has a 16-bit interface ID and a drop count
+                let interface = if block_type_value == PACKET {
+                    usize::from(endian.u16(&body[0..2]))
+                } else {
+                    endian.u32(&body[0..4]) as usize
+                };
+                let timestamp =
+                    u64::from(endian.u32(&body[4..8])) << 32 | u64::from(endian.u32(&body[8..12]));
+                let captured = endian.u32(&body[12..16]) as usize;
+                let interface = interfaces
+                    .get(interface)
+                    .ok_or("Packet on an undescribed interface")?;
+
+                last_time_us = to_us(timestamp, interface.units_per_s);
+
+                let packet = Packet {
+                    time_us: last_time_us,
+                    link_type: interface.link_type,
+                    data: body.get(20..20 + captured).ok_or("Invalid packet length")?,
+                };
+                if !on_packet(packet) {
+                    break;
+                }
+            }
+            SIMPLE_PACKET if body.len() >= 4 => {
+                // Simple packets carry no timestamp, so they take the previous packet's
+                let original = endian.u32(&body[0..4]) as usize;
+                let interface = interfaces
+                    .first()
+                    .ok_or("Packet on an undescribed interface")?;
+
+                let packet = Packet {
+                    time_us: last_time_us,
+                    link_type: interface.link_type,
+                    data: &body[4..body.len().min(4 + original)],
+                };
+                if !on_packet(packet) {
+                    break;
+                }
+            }
+            // Name resolution, statistics and other blocks say nothing about the traffic
+            _ => {}
+        }
+
+        block_type = read_exact_or_end(file, 4)?;
+    }
+
+    Ok(())
+}
+
+/// Units per second of an interface's timestamps, from its options.
+fn timestamp_resolution(endian: Endian, mut options: &[u8]) -> u64 {
+    while options.len() >= 4 {
+        let code = endian.u16(&options[0..2]);
+        let length = usize::from(endian.u16(&options[2..4]));
+        let Some(value) = options.get(4..4 + length) else {
+            break;
+        };
+
+        if code == IF_TSRESOL
+            && let Some(&resolution) = value.first()
+        {
+            let exponent = u32::from(resolution & 0x7F);
+            let base: u64 = if resolution & 0x80 == 0 { 10 } else { 2 };
+            return base.checked_pow(exponent).unwrap_or(1_000_000);
+        }
+
+        options = options
+            .get((4 + length).next_multiple_of(4)..)
+            .unwrap_or_default();
+    }
+
+    // Microseconds unless stated otherwise
+    1_000_000
+}
+
+fn to_us(timestamp: u64, units_per_s: u64) -> u64 {
+    let us = u128::from(timestamp) * 1_000_000 / u128::from(units_per_s.max(1));
+    u64::try_from(us).unwrap_or(u64::MAX)
+}
+
+/// A UDP datagram found in a captured packet.
+enum Udp<'a> {
+    Complete {
+        source: SocketAddr,
+        port: u16,
+        payload: &'a [u8],
+    },
+    /// Cut short by the capture's snapshot length
+    Truncated { port: u16 },
+}
+
+/// Finds the UDP datagram in a packet, if it holds an unfragmented one.
+fn udp(link_type: u32, data: &[u8]) -> Option<Udp<'_>> {
+    let ip = match link_type {
+        LINKTYPE_ETHERNET => {
+            let mut offset = 12;
+            let mut ethertype = u16::from_be_bytes([*data.get(offset)?, *data.get(offset + 1)?]);
+
+            while ETHERTYPE_VLAN.contains(&ethertype) {
+                offset += 4;
+                ethertype = u16::from_be_bytes([*data.get(offset)?, *data.get(offset + 1)?]);
+            }
+
+            if ethertype != ETHERTYPE_IPV4 && ethertype != ETHERTYPE_IPV6 {
+                return None;
+            }
+            data.get(offset + 2..)?
+        }
+        LINKTYPE_NULL | LINKTYPE_LOOP => data.get(4..)?,
+        LINKTYPE_LINUX_SLL => data.get(16..)?,
+        LINKTYPE_LINUX_SLL2 => data.get(20..)?,
+        LINKTYPE_RAW | LINKTYPE_IPV4 | LINKTYPE_IPV6 => data,
+        _ => return None,
+    };
+
+    let (source, segment) = match ip.first()? >> 4 {
+        4 => ipv4_payload(ip)?,
+        6 => ipv6_payload(ip)?,
+        _ => return None,
+    };
+
+    let source_port = u16::from_be_bytes([*segment.first()?, *segment.get(1)?]);
+    let port = u16::from_be_bytes([*segment.get(2)?, *segment.get(3)?]);
+    let length = usize::from(u16::from_be_bytes([*segment.get(4)?, *segment.get(5)?]));
+
+    if length < UDP_HEADER_LEN {
+        return None;
+    }
+
+    Some(
+        segment
+            .get(UDP_HEADER_LEN..length)
+            .map_or(Udp::Truncated { port }, |payload| Udp::Complete {
+                source: SocketAddr::new(source, source_port),
+                port,
+                payload,
+            }),
+    )
+}
+
+/// Returns the source address and UDP segment of an unfragmented IPv4 packet.
+fn ipv4_payload(ip: &[u8]) -> Option<(IpAddr, &[u8])> {
+    let header_len = usize::from(ip.first()? & 0x0F) * 4;
+    let fragment = u16::from_be_bytes([*ip.get(6)?, *ip.get(7)?]);
+
+    // Any fragment offset or the more fragments flag
+    if *ip.get(9)? != PROTOCOL_UDP || fragment & 0x3FFF != 0 {
+        return None;
+    }
+
+    let source = Ipv4Addr::new(*ip.get(12)?, *ip.get(13)?, *ip.get(14)?, *ip.get(15)?);
+    // Ethernet pads short frames beyond the end of the packet
+    let total_len = usize::from(u16::from_be_bytes([*ip.get(2)?, *ip.get(3)?]));
+    let payload = ip.get(header_len..total_len.min(ip.len()))?;
+
+    Some((IpAddr::V4(source), payload))
+}
+
+/// Returns the source address and UDP segment of an unfragmented IPv6 packet, skipping any
+/// hop-by-hop, routing or destination options headers.
+fn ipv6_payload(ip: &[u8]) -> Option<(IpAddr, &[u8])> {
+    const HOP_BY_HOP: u8 = 0;
+    const ROUTING: u8 = 43;
+    const DESTINATION_OPTIONS: u8 = 60;
+
+    let source = Ipv6Addr::from(<[u8; 16]>::try_from(ip.get(8..24)?).ok()?);
+    let payload_len = usize::from(u16::from_be_bytes([*ip.get(4)?, *ip.get(5)?]));
+    let mut payload = ip.get(40..(40 + payload_len).min(ip.len()))?;
+    let mut next_header = *ip.get(6)?;
+
+    while matches!(next_header, HOP_BY_HOP | ROUTING | DESTINATION_OPTIONS) {
+        let length = (usize::from(*payload.get(1)?) + 1) * 8;
+        next_header = *payload.first()?;
+        payload = payload.get(length..)?;
+    }
+
+    (next_header == PROTOCOL_UDP).then_some((IpAddr::V6(source), payload))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    const SOURCE: &str = "10.0.0.1:3001";
+    const DESTINATION: &str = "239.1.2.3:3000";
+
+    fn udp_packet(destination: &str, payload: &[u8]) -> Vec<u8> {
+        frame(
+            SOURCE.parse().expect("a source"),
+            destination.parse().expect("a destination"),
+            payload,
+        )
+        .expect("a framed datagram")
+    }
+
+    fn u16_bytes(endian: Endian, value: u16) -> [u8; 2] {
+        match endian {
+            Endian::Big => value.to_be_bytes(),
+            Endian::Little => value.to_le_bytes(),
+        }
+    }
+
+    fn u32_bytes(endian: Endian, value: u32) -> [u8; 4] {
+        match endian {
+            Endian::Big => value.to_be_bytes(),
+            Endian::Little => value.to_le_bytes(),
+        }
+    }
+
+    /// A pcapng block with its body padded to a multiple of four bytes
+    fn block(endian: Endian, block_type: u32, body: &[u8]) -> Vec<u8> {
+        let padded = body.len().next_multiple_of(4);
+        let length = u32::try_from(padded + 12).expect("a block length");
+
+        let mut block = u32_bytes(endian, block_type).to_vec();
+        block.extend_from_slice(&u32_bytes(endian, length));
+        block.extend_from_slice(body);
+        block.resize(8 + padded, 0);
+        block.extend_from_slice(&u32_bytes(endian, length));
+        block
+    }
+
+    fn section_header(endian: Endian) -> Vec<u8> {
+        let mut body = u32_bytes(endian, BYTE_ORDER_MAGIC).to_vec();
+        body.extend_from_slice(&u16_bytes(endian, 1));
+        body.extend_from_slice(&u16_bytes(endian, 0));
+        body.extend_from_slice(&(-1i64).to_be_bytes());
+        block(endian, SECTION_HEADER, &body)
+    }
+
+    /// An interface on a raw IP link, with a timestamp resolution option if `tsresol` is set
+    fn interface(endian: Endian, tsresol: Option<u8>) -> Vec<u8> {
+        let mut body = u16_bytes(endian, u16::try_from(LINKTYPE_RAW).unwrap_or_default()).to_vec();
+        body.extend_from_slice(&[0, 0, 0, 0, 0, 0]);
+
+        if let Some(resolution) = tsresol {
+            body.extend_from_slice(&u16_bytes(endian, IF_TSRESOL));
+            body.extend_from_slice(&u16_bytes(endian, 1));
+            body.extend_from_slice(&[resolution, 0, 0, 0]);
+            // End of options
+            body.extend_from_slice(&[0; 4]);
+        }
+
+        block(endian, INTERFACE_DESCRIPTION, &body)
+    }
+
+    fn enhanced_packet(endian: Endian, timestamp: u64, data: &[u8]) -> Vec<u8> {
+        let length = u32::try_from(data.len()).expect("a packet length");
+
+        let mut body = u32_bytes(endian, 0).to_vec();
+        body.extend_from_slice(&u32_bytes(endian, (timestamp >> 32) as u32));
+        body.extend_from_slice(&u32_bytes(endian, (timestamp & 0xFFFF_FFFF) as u32));
+        body.extend_from_slice(&u32_bytes(endian, length));
+        body.extend_from_slice(&u32_bytes(endian, length));
+        body.extend_from_slice(data);
+        block(endian, ENHANCED_PACKET, &body)
+    }
+
+    /// Packets read from a pcapng file, as their times and link-layer data
+    fn read_all_pcapng(file: &[u8]) -> Result<Vec<(u64, Vec<u8>)>, String> {
+        let mut packets = Vec::new();
+
+        // The first block type has been read to tell pcapng from pcap
+        read_pcapng(&mut Cursor::new(&file[4..]), &mut |packet| {
+            packets.push((packet.time_us, packet.data.to_vec()));
+            true
+        })?;
+
+        Ok(packets)
+    }
+
+    #[test]
+    fn pcapng_in_either_byte_order() {
+        let packet = udp_packet(DESTINATION, b"pdu");
+
+        for endian in [Endian::Little, Endian::Big] {
+            let mut file = section_header(endian);
+            file.extend(interface(endian, None));
+            file.extend(enhanced_packet(endian, 1_500_000, &packet));
+
+            assert_eq!(
+                read_all_pcapng(&file).expect("a valid file"),
+                [(1_500_000, packet.clone())]
+            );
+        }
+    }
+
+    #[test]
+    fn pcapng_nanosecond_resolution() {
+        let packet = udp_packet(DESTINATION, b"pdu");
+
+        let mut file = section_header(Endian::Little);
+        file.extend(interface(Endian::Little, Some(9)));
+        file.extend(enhanced_packet(Endian::Little, 1_500_000_123, &packet));
+
+        assert_eq!(
+            read_all_pcapng(&file).expect("a valid file"),
+            [(1_500_000, packet)]
+        );
+    }
+
+    #[test]
+    fn pcapng_sections_restate_byte_order() {
+        let packet = udp_packet(DESTINATION, b"pdu");
+
+        let mut file = section_header(Endian::Little);
+        file.extend(interface(Endian::Little, None));
+        file.extend(enhanced_packet(Endian::Little, 1, &packet));
+        file.extend(section_header(Endian::Big));
+        file.extend(interface(Endian::Big, Some(9)));
+        file.extend(enhanced_packet(Endian::Big, 2_000, &packet));
+
+        assert_eq!(
+            read_all_pcapng(&file).expect("a valid file"),
+            [(1, packet.clone()), (2, packet)]
+        );
+    }
+
+    #[test]
+    fn pcapng_truncated_block_is_ignored() {
+        let packet = udp_packet(DESTINATION, b"pdu");
+
+        let mut file = section_header(Endian::Little);
+        file.extend(interface(Endian::Little, None));
+        file.extend(enhanced_packet(Endian::Little, 1, &packet));
+        let last = enhanced_packet(Endian::Little, 2, &packet);
+        file.extend(&last[..last.len() - 10]);
+
+        assert_eq!(read_all_pcapng(&file).expect("a valid file"), [(1, packet)]);
+    }
+
+    #[test]
+    fn pcapng_block_shorter_than_its_fields_is_invalid() {
+        // A section header too short for its version and section length
+        let mut file = u32_bytes(Endian::Little, SECTION_HEADER).to_vec();
+        file.extend_from_slice(&u32_bytes(Endian::Little, 12));
+        file.extend_from_slice(&u32_bytes(Endian::Little, BYTE_ORDER_MAGIC));
+        file.extend_from_slice(&u32_bytes(Endian::Little, 12));
+
+        assert_eq!(
+            read_all_pcapng(&file),
+            Err("Invalid pcapng block length 12".into())
+        );
+
+        let mut file = section_header(Endian::Little);
+        file.extend_from_slice(&u32_bytes(Endian::Little, ENHANCED_PACKET));
+        file.extend_from_slice(&u32_bytes(Endian::Little, 8));
+
+        assert_eq!(
+            read_all_pcapng(&file),
+            Err("Invalid pcapng block length 8".into())
+        );
+    }
+
+    #[test]
+    fn pcap_with_nanosecond_timestamps() {
+        let packet = udp_packet(DESTINATION, b"pdu");
+        let length = u32::try_from(packet.len()).expect("a packet length");
+
+        let mut header = vec![0; 20];
+        header[16..20].copy_from_slice(&LINKTYPE_RAW.to_le_bytes());
+        let mut file = header;
+        file.extend_from_slice(&2u32.to_le_bytes());
+        file.extend_from_slice(&250_000_000u32.to_le_bytes());
+        file.extend_from_slice(&length.to_le_bytes());
+        file.extend_from_slice(&length.to_le_bytes());
+        file.extend_from_slice(&packet);
+
+        let mut packets = Vec::new();
+        read_pcap(
+            &mut Cursor::new(file),
+            [0x4D, 0x3C, 0xB2, 0xA1],
+            &mut |packet| {
+                packets.push((packet.time_us, packet.data.to_vec()));
+                true
+            },
+        )
+        .expect("a valid file");
+
+        assert_eq!(packets, [(2_250_000, packet)]);
+    }
+
+    #[test]
+    fn udp_found_behind_link_headers() {
+        let packet = udp_packet(DESTINATION, b"pdu");
+
+        let mut ethernet = vec![0; 12];
+        ethernet.extend_from_slice(&ETHERTYPE_VLAN[0].to_be_bytes());
+        ethernet.extend_from_slice(&[0, 1]);
+        ethernet.extend_from_slice(&ETHERTYPE_IPV4.to_be_bytes());
+        ethernet.extend_from_slice(&packet);
+
+        for (link_type, data) in [(LINKTYPE_RAW, &packet), (LINKTYPE_ETHERNET, &ethernet)] {
+            let Some(Udp::Complete {
+                source,
+                port,
+                payload,
+            }) = udp(link_type, data)
+            else {
+                panic!("no UDP datagram on link type {link_type}");
+            };
+
+            assert_eq!(source, SOURCE.parse().expect("a source"));
+            assert_eq!(port, 3000);
+            assert_eq!(payload, b"pdu");
+        }
+    }
+
+    #[test]
+    fn other_frames_are_skipped() {
+        let packet = udp_packet(DESTINATION, b"pdu");
+
+        // ARP
+        let mut arp = vec![0; 12];
+        arp.extend_from_slice(&[0x08, 0x06]);
+        arp.extend_from_slice(&packet);
+        assert!(udp(LINKTYPE_ETHERNET, &arp).is_none());
+
+        // TCP
+        let mut tcp = packet.clone();
+        tcp[9] = 6;
+        assert!(udp(LINKTYPE_RAW, &tcp).is_none());
+
+        // A fragment
+        let mut fragment = packet.clone();
+        fragment[6] = 0x20;
+        assert!(udp(LINKTYPE_RAW, &fragment).is_none());
+
+        // Cut short by the snapshot length
+        assert!(matches!(
+            udp(LINKTYPE_RAW, &packet[..packet.len() - 1]),
+            Some(Udp::Truncated { port: 3000 })
+        ));
+
+        assert!(udp(LINKTYPE_LINUX_SLL2 + 1, &packet).is_none());
+    }
+
+    #[test]
+    fn only_datagrams_to_the_chosen_ports_are_imported() {
+        let path =
+            std::env::temp_dir().join(format!("centurion-{}-import.pcapng", std::process::id()));
+
+        let mut file = section_header(Endian::Little);
+        file.extend(interface(Endian::Little, None));
+        file.extend(enhanced_packet(
+            Endian::Little,
+            1,
+            &udp_packet(DESTINATION, b"dis"),
+        ));
+        file.extend(enhanced_packet(
+            Endian::Little,
+            2,
+            &udp_packet("239.1.2.3:53", b"dns"),
+        ));
+        let mut tcp = udp_packet(DESTINATION, b"tcp");
+        tcp[9] = 6;
+        file.extend(enhanced_packet(Endian::Little, 3, &tcp));
+        std::fs::write(&path, file).expect("a pcapng file");
+
+        let (sender, mut received) = mpsc::channel(IMPORT_QUEUE);
+        let summary = extract(&path, &[3000], &sender).expect("a readable file");
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(summary.packets, 3);
+        let datagram = received.try_recv().expect("the DIS datagram");
+        assert_eq!(&datagram.data[..], b"dis");
+        assert_eq!(datagram.received_at, UNIX_EPOCH + Duration::from_micros(1));
+        assert!(received.try_recv().is_err());
+    }
+}
@@ -0,0 +1,314 @@
+//! A rolling log of the PDUs received and sent, kept for the inspector.
+
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use bytes::Bytes;
+use open_dis_rust::common::constants::PDU_HEADER_SIZE;
+use serde::Serialize;
+
+use crate::core::{
+    CaptureReader, Datagram, Direction, Filter, Monitor, pdu_type_name, sender, unix_ms,
+};
+
+/// PDUs kept for inspection, and PDUs matching the filter, oldest dropped first
+const CAPACITY: usize = 1000;
+
+/// Rows in each published snapshot, newest first
+const SNAPSHOT_ROWS: usize = 200;
+
+/// Offsets of the exercise ID and PDU type within the PDU header
+const EXERCISE_OFFSET: usize = 1;
+const PDU_TYPE_OFFSET: usize = 2;
+
+/// One PDU in the live log or a capture, as listed for the operator to pick from.
+#[derive(Serialize, Debug, Clone)]
+pub struct PduRow {
+    /// Sequence number in the live log, or record index in a capture
+    pub id: u64,
+    pub direction: Direction,
+    /// Unix milliseconds the PDU was received or sent
+    pub time_ms: u64,
+    /// Simulation address of the sending application, or its socket address if the PDU
+    /// names none
+    pub source: String,
+    pub pdu_type: String,
+    pub exercise_id: u8,
+    pub length: usize,
+}
+
+impl PduRow {
+    #[must_use]
+    pub fn new(id: u64, direction: Direction, datagram: &Datagram) -> Self {
+        let data = &datagram.data;
+        let header = |offset: usize| {
+            data.get(offset)
+                .copied()
+                .filter(|_| data.len() >= PDU_HEADER_SIZE)
+        };
+
+        Self {
+            id,
+            direction,
+            time_ms: unix_ms(datagram.received_at).unwrap_or_default(),
+            source: sender(data)
+                .map_or_else(|| datagram.source.to_string(), |sender| sender.to_string()),
+            pdu_type: header(PDU_TYPE_OFFSET).map_or_else(|| "Not a PDU".into(), pdu_type_name),
+            exercise_id: header(EXERCISE_OFFSET).unwrap_or_default(),
+            length: data.len(),
+        }
+    }
+}
+
+/// A page of the records of the capture open in the inspector.
+#[derive(Serialize, Debug, Clone)]
+pub struct CapturePage {
+    pub file: PathBuf,
+    pub records: usize,
+    /// Records matching the filter, or every record if there is none
+    pub matching: usize,
+    /// Position of the first row among the matching records
+    pub start: usize,
+    pub rows: Vec<PduRow>,
+    pub filter: Option<Filter>,
+}
+
+/// A capture open in the inspector, with the records its filter matches.
+pub struct CaptureBrowser {
+    reader: CaptureReader,
+    filter: Option<Filter>,
+    /// Indices of the records matching the filter, if there is one
+    matches: Option<Vec<usize>>,
+}
+
+impl CaptureBrowser {
+    #[must_use]
+    pub const fn new(reader: CaptureReader) -> Self {
+        Self {
+            reader,
+            filter: None,
+            matches: None,
+        }
+    }
+
+    /// Filters the records listed, reading every record of the capture to find the
+    /// matching ones.
+    ///
+    /// # Errors
+    /// - Returns an error if a record cannot be read
+    pub fn set_filter(&mut self, filter: Option<Filter>) -> Result<(), String> {
+        self.matches = filter
+            .as_ref()
+            .map(|filter| {
+                (0..self.reader.len())
+                    .filter_map(|record| {
+                        self.reader
+                            .read(record)
+                            .map(|datagram| {
+                                // Captures hold received traffic only
+                                filter
+                                    .matches(&datagram.data, Direction::Received)
+                                    .then_some(record)
+                            })
+                            .transpose()
+                    })
+                    .collect::<Result<Vec<_>, String>>()
+            })
+            .transpose()?;
+        self.filter = filter;
+
+        Ok(())
+    }
+
+    /// Returns up to `count` of the matching records from position `start`.
+    ///
+    /// # Errors
+    /// - Returns an error if a record cannot be read
+    pub fn page(&mut self, start: usize, count: usize) -> Result<CapturePage, String> {
+        let matching = self
+            .matches
+            .as_ref()
+            .map_or_else(|| self.reader.len(), Vec::len);
+        let end = start.saturating_add(count).min(matching);
+
+        let rows = (start.min(end)..end)
+            .map(|position| {
+                let record = self
+                    .matches
+                    .as_ref()
+                    .map_or(position, |matches| matches[position]);
+                let datagram = self.reader.read(record)?;
+                Ok(PduRow::new(record as u64, Direction::Received, &datagram))
+            })
+            .collect::<Result<_, String>>()?;
+
+        Ok(CapturePage {
+            file: self.reader.path().to_path_buf(),
+            records: self.reader.len(),
+            matching,
+            start,
+            rows,
+            filter: self.filter.clone(),
+        })
+    }
+
+    /// Reads a record as the datagram that was captured.
+    ///
+    /// # Errors
+    /// - Returns an error if the record does not exist or cannot be read
+    pub fn read(&mut self, record: usize) -> Result<Datagram, String> {
+        self.reader.read(record)
+    }
+}
+
+/// The latest PDUs received and sent, with their bytes.
+#[derive(Default)]
+pub struct PduLog {
+    entries: VecDeque<(PduRow, Bytes)>,
+    /// The latest PDUs matching the filter, kept apart so that other traffic on a busy
+    /// exercise does not push them out
+    matching: VecDeque<(PduRow, Bytes)>,
+    next_id: u64,
+    filter: Option<Filter>,
+}
+
+impl PduLog {
+    /// Limits the rows published to the PDUs matching `filter`, starting from those among
+    /// the latest PDUs logged.
+    pub fn set_filter(&mut self, filter: Option<Filter>) {
+        self.matching = filter.as_ref().map_or_else(VecDeque::new, |filter| {
+            self.entries
+                .iter()
+                .filter(|(row, data)| filter.matches(data, row.direction))
+                .cloned()
+                .collect()
+        });
+        self.filter = filter;
+    }
+
+    /// Returns the bytes of a logged PDU, if it has not been dropped since.
+    #[must_use]
+    pub fn data(&self, id: u64) -> Option<Bytes> {
+        // Both logs hold entries in order of their IDs
+        [&self.entries, &self.matching]
+            .into_iter()
+            .find_map(|entries| {
+                entries
+                    .binary_search_by_key(&id, |(row, _)| row.id)
+                    .ok()
+                    .map(|index| entries[index].1.clone())
+            })
+    }
+
+    fn log(&mut self, direction: Direction, datagram: &Datagram) -> bool {
+        let row = PduRow::new(self.next_id, direction, datagram);
+        let entry = (row, datagram.data.clone());
+        self.next_id += 1;
+
+        if self
+            .filter
+            .as_ref()
+            .is_some_and(|filter| filter.matches(&datagram.data, direction))
+        {
+            push(&mut self.matching, entry.clone());
+        }
+        push(&mut self.entries, entry);
+
+        true
+    }
+}
+
+/// Appends an entry, dropping the oldest once the log is full.
+fn push(entries: &mut VecDeque<(PduRow, Bytes)>, entry: (PduRow, Bytes)) {
+    if entries.len() == CAPACITY {
+        entries.pop_front();
+    }
+    entries.push_back(entry);
+}
+
+impl Monitor for PduLog {
+    const EVENT: &'static str = "pdu-log";
+
+    /// Fast enough to follow the exercise, slow enough for a busy one to stay readable
+    const PUBLISH_INTERVAL: Duration = Duration::from_millis(500);
+
+    type Snapshot = Vec<PduRow>;
+
+    fn observe(&mut self, datagram: &Datagram) -> bool {
+        self.log(Direction::Received, datagram)
+    }
+
+    fn observe_sent(&mut self, datagram: &Datagram) -> bool {
+        self.log(Direction::Sent, datagram)
+    }
+
+    fn snapshot(&self) -> Self::Snapshot {
+        let entries = if self.filter.is_some() {
+            &self.matching
+        } else {
+            &self.entries
+        };
+
+        entries
+            .iter()
+            .rev()
+            .take(SNAPSHOT_ROWS)
+            .map(|(row, _)| row.clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::SystemTime;
+
+    use super::*;
+
+    fn datagram(exercise_id: u8) -> Datagram {
+        let mut data = vec![0; PDU_HEADER_SIZE];
+        data[EXERCISE_OFFSET] = exercise_id;
+        Datagram {
+            data: Bytes::from(data),
+            source: "192.0.2.1:3000".parse().expect("a socket address"),
+            received_at: SystemTime::now(),
+            replayed: false,
+        }
+    }
+
+    fn filter(text: &str) -> Filter {
+        Filter::parse(text).expect("a valid filter")
+    }
+
+    #[test]
+    fn matching_pdus_outlast_other_traffic() {
+        let mut log = PduLog::default();
+        log.set_filter(Some(filter("exercise == 2")));
+
+        log.observe(&datagram(2));
+        for _ in 0..CAPACITY * 2 {
+            log.observe(&datagram(1));
+        }
+
+        let rows = log.snapshot();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].id, 0);
+        assert!(log.data(0).is_some());
+        assert!(log.data(1).is_none());
+    }
+
+    #[test]
+    fn new_filter_starts_from_latest_pdus() {
+        let mut log = PduLog::default();
+        for exercise_id in [1, 2, 1, 2] {
+            log.observe(&datagram(exercise_id));
+        }
+
+        log.set_filter(Some(filter("exercise == 2")));
+        let ids: Vec<_> = log.snapshot().iter().map(|row| row.id).collect();
+        assert_eq!(ids, [3, 1]);
+
+        log.set_filter(None);
+        assert_eq!(log.snapshot().len(), 4);
+    }
+}
@@ -0,0 +1,253 @@
+//! Recording of the exercise traffic to capture files.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::async_runtime::{JoinHandle, spawn};
+use tauri::{AppHandle, Emitter};
+use tokio::sync::broadcast::Receiver;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::oneshot;
+use tokio::time::{Instant, MissedTickBehavior, interval};
+
+use crate::config::RecordingConfig;
+use crate::core::{
+    CAPTURE_EXTENSION, CaptureWriter, Datagram, NetworkService, Notice, notify, now_ms, utc_stamp,
+};
+
+/// Name of the event carrying the recording status
+const STATUS_EVENT: &str = "recording";
+
+/// How often buffered records are written to disk and the status is published
+const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct RecordingStatus {
+    pub recording: bool,
+    /// Capture file being written, or the last one written once stopped
+    pub file: Option<PathBuf>,
+    /// Capture files started by the current or last recording
+    pub files: u32,
+    pub pdus: u64,
+    pub bytes: u64,
+    /// Datagrams received faster than the recorder could write them
+    pub missed: u64,
+    pub started_ms: u64,
+}
+
+struct Active {
+    stop: oneshot::Sender<()>,
+    task: JoinHandle<()>,
+}
+
+/// Records every datagram heard by the network service, moving on to a new capture file
+/// whenever the rollover policy says so.
+///
+/// The recorder subscribes to the same receive path as the monitors, so it is fed by the
+/// listeners directly and never waits on commands in flight.
+#[derive(Default)]
+pub struct Recorder {
+    active: tokio::sync::Mutex<Option<Active>>,
+    status: Arc<Mutex<RecordingStatus>>,
+}
+
+impl Recorder {
+    #[must_use]
+    pub fn status(&self) -> RecordingStatus {
+        self.status
+            .lock()
+            .map(|status| status.clone())
+            .unwrap_or_default()
+    }
+
+    /// Starts recording to a new capture file in `directory`.
+    ///
+    /// # Errors
+    /// - Returns an error if a recording is already running or the capture file cannot be
+    ///   created
+    pub async fn start(
+        &self,
+        app: AppHandle,
+        network: &NetworkService,
+        directory: PathBuf,
+        rollover: RecordingConfig,
+    ) -> Result<RecordingStatus, String> {
+        let mut active = self.active.lock().await;
+
+        if self.status().recording {
+            return Err("Already recording".into());
+        }
+
+        // A recording that stopped on an error has already finished its task
+        if let Some(previous) = active.take() {
+            let _ = previous.task.await;
+        }
+
+        tokio::fs::create_dir_all(&directory)
+            .await
+            .map_err(|e| format!("Unable to create {}: {e}", directory.display()))?;
+
+        let traffic = network.subscribe();
+        let writer = CaptureWriter::create(&next_path(&directory)).await?;
+
+        let status = RecordingStatus {
+            recording: true,
+            file: Some(writer.path().to_path_buf()),
+            files: 1,
+            started_ms: now_ms().unwrap_or_default(),
+            ..RecordingStatus::default()
+        };
+        self.update(|current| *current = status.clone());
+
+        tracing::info!("Recording to {}", writer.path().display());
+
+        let (stop, stopped) = oneshot::channel();
+        let task = spawn(record(
+            app,
+            traffic,
+            writer,
+            directory,
+            rollover,
+            stopped,
+            Arc::clone(&self.status),
+        ));
+
+        *active = Some(Active { stop, task });
+        drop(active);
+
+        Ok(status)
+    }
+
+    /// Stops recording once the records received so far are written.
+    ///
+    /// # Errors
+    /// - Returns an error if nothing is being recorded
+    pub async fn stop(&self) -> Result<RecordingStatus, String> {
+        let active = self.active.lock().await.take().ok_or("Not recording")?;
+
+        // The task may already have stopped on an error, dropping its receiver
+        let _ = active.stop.send(());
+        let _ = active.task.await;
+
+        Ok(self.status())
+    }
+
+    fn update(&self, change: impl FnOnce(&mut RecordingStatus)) {
+        update(&self.status, change);
+    }
+}
+
+fn update(status: &Mutex<RecordingStatus>, change: impl FnOnce(&mut RecordingStatus)) {
+    if let Ok(mut status) = status.lock() {
+        change(&mut status);
+    } else {
+        tracing::error!("Recording status lock poisoned");
+    }
+}
+
+fn publish(app: &AppHandle, status: &Mutex<RecordingStatus>) {
+    let Ok(snapshot) = status.lock().map(|status| status.clone()) else {
+        return;
+    };
+
+    app.emit(STATUS_EVENT, snapshot)
+        .unwrap_or_else(|e| tracing::error!("unable to emit {STATUS_EVENT} event: {e}"));
+}
+
+/// Names a new capture file after the current UTC time, numbering it if a file of that
+/// name already exists.
+fn next_path(directory: &Path) -> PathBuf {
+    let stamp = utc_stamp(now_ms().unwrap_or_default());
+    let path =
+        |suffix: String| directory.join(format!("exercise-{stamp}{suffix}.{CAPTURE_EXTENSION}"));
+
+    let mut candidate = path(String::new());
+    let mut number = 1;
+
+    while candidate.exists() {
+        number += 1;
+        candidate = path(format!("-{number}"));
+    }
+
+    candidate
+}
+
+/// Writes received datagrams until told to stop, then closes the capture file.
+async fn record(
+    app: AppHandle,
+    mut traffic: Receiver<Datagram>,
+    mut writer: CaptureWriter,
+    directory: PathBuf,
+    rollover: RecordingConfig,
+    mut stop: oneshot::Receiver<()>,
+    status: Arc<Mutex<RecordingStatus>>,
+) {
+    let mut flush = interval(FLUSH_INTERVAL);
+    flush.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    let mut file_started = Instant::now();
+
+    let outcome = loop {
+        tokio::select! {
+            _ = &mut stop => break Ok(()),
+            received = traffic.recv() => match received {
+                Ok(datagram) => {
+                    if let Err(e) = writer.write(&datagram).await {
+                        break Err(e);
+                    }
+
+                    update(&status, |status| {
+                        status.pdus += 1;
+                        status.bytes += datagram.data.len() as u64;
+                    });
+
+                    if !rollover.rollover_due(writer.size(), file_started.elapsed()) {
+                        continue;
+                    }
+
+                    // The next file is created first so that traffic is never left unrecorded
+                    let next = match CaptureWriter::create(&next_path(&directory)).await {
+                        Ok(next) => next,
+                        Err(e) => break Err(e),
+                    };
+                    let previous = std::mem::replace(&mut writer, next);
+                    file_started = Instant::now();
+
+                    if let Err(e) = previous.finish().await {
+                        tracing::error!("{e}");
+                    }
+
+                    tracing::info!("Recording rolled over to {}", writer.path().display());
+                    update(&status, |status| {
+                        status.file = Some(writer.path().to_path_buf());
+                        status.files += 1;
+                    });
+                    publish(&app, &status);
+                }
+                Err(RecvError::Lagged(missed)) => {
+                    tracing::warn!("Recorder missed {missed} datagrams");
+                    update(&status, |status| status.missed += missed);
+                }
+                Err(RecvError::Closed) => break Ok(()),
+            },
+            _ = flush.tick() => {
+                if let Err(e) = writer.flush().await {
+                    break Err(e);
+                }
+                publish(&app, &status);
+            }
+        }
+    };
+
+    let outcome = outcome.and(writer.finish().await);
+
+    update(&status, |status| status.recording = false);
+    publish(&app, &status);
+
+    match outcome {
+        Ok(()) => tracing::info!("Recording stopped"),
+        Err(e) => notify(&app, Notice::warning(format!("Recording stopped: {e}"))),
+    }
+}
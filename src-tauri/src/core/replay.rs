@@ -0,0 +1,453 @@
+//! Replay of capture files onto the exercise network.
+
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::async_runtime::{JoinHandle, spawn};
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::mpsc;
+use tokio::time::{Instant, MissedTickBehavior, interval, sleep_until};
+
+use crate::core::{
+    AppState, CaptureReader, Datagram, Direction, Filter, Notice, notify, now_ms, timestamp,
+};
+
+/// Name of the event carrying the replay status
+const STATUS_EVENT: &str = "replay";
+
+/// The status is published at most this often while it changes
+const PUBLISH_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Offsets of the exercise ID and timestamp within the PDU header
+const EXERCISE_OFFSET: usize = 1;
+const TIMESTAMP_OFFSET: usize = 4;
+
+/// How replayed PDUs are timed and rewritten.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct ReplayOptions {
+    /// Playback speed relative to the recording
+    pub speed: f64,
+    /// Whether playback starts over from the first record after the last
+    pub looping: bool,
+    /// Exercise ID written into every replayed PDU instead of the recorded one
+    pub exercise_id: Option<u8>,
+    /// Whether replayed PDUs are stamped with the time they are sent
+    pub restamp: bool,
+}
+
+impl ReplayOptions {
+    pub const MIN_SPEED: f64 = 0.1;
+    pub const MAX_SPEED: f64 = 10.0;
+
+    /// # Errors
+    /// - Returns an error if the speed is outside 0.1x to 10x
+    pub fn validate(&self) -> Result<(), String> {
+        if (Self::MIN_SPEED..=Self::MAX_SPEED).contains(&self.speed) {
+            Ok(())
+        } else {
+            Err(format!(
+                "Replay speed {}x is outside {}x to {}x",
+                self.speed,
+                Self::MIN_SPEED,
+                Self::MAX_SPEED
+            ))
+        }
+    }
+
+    /// Applies the exercise ID and timestamp rewrites to a PDU about to be sent.
+    fn rewrite(&self, pdu: &mut [u8]) {
+        if let (Some(exercise_id), Some(field)) = (self.exercise_id, pdu.get_mut(EXERCISE_OFFSET)) {
+            *field = exercise_id;
+        }
+
+        if self.restamp
+            && let Some(field) = pdu.get_mut(TIMESTAMP_OFFSET..TIMESTAMP_OFFSET + 4)
+        {
+            // Keeps the recorded timestamp's absolute or relative flag
+            let absolute = field[3] & 1 == 1;
+            let now = timestamp(now_ms().unwrap_or_default(), absolute);
+            field.copy_from_slice(&now.to_be_bytes());
+        }
+    }
+}
+
+impl Default for ReplayOptions {
+    fn default() -> Self {
+        Self {
+            speed: 1.0,
+            looping: false,
+            exercise_id: None,
+            restamp: false,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ReplayState {
+    #[default]
+    Closed,
+    Paused,
+    Playing,
+    Finished,
+}
+
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct ReplayStatus {
+    pub state: ReplayState,
+    pub file: Option<PathBuf>,
+    pub records: usize,
+    /// Index of the next record to send
+    pub position: usize,
+    /// Capture time of the next record after the first, in milliseconds
+    pub position_ms: u64,
+    /// Capture time of the last record after the first, in milliseconds
+    pub duration_ms: u64,
+    /// PDUs sent since the capture was opened
+    pub sent: u64,
+    pub options: ReplayOptions,
+    /// Records the filter rejects are skipped
+    pub filter: Option<Filter>,
+}
+
+/// Requests from the operator to the replay task.
+#[derive(Debug, Clone)]
+pub enum Control {
+    Play,
+    Pause,
+    /// Moves to the first record at or after this many milliseconds into the capture
+    Seek(u64),
+    Options(ReplayOptions),
+    Filter(Option<Filter>),
+}
+
+struct Session {
+    controls: mpsc::UnboundedSender<Control>,
+    task: JoinHandle<()>,
+}
+
+impl Session {
+    async fn close(self) {
+        // The task finishes once its control channel closes
+        drop(self.controls);
+        let _ = self.task.await;
+    }
+}
+
+/// Sends the records of one capture file at a time to the scenario's destination, keeping
+/// their original spacing scaled by the chosen speed.
+#[derive(Default)]
+pub struct Player {
+    session: tokio::sync::Mutex<Option<Session>>,
+    status: Arc<Mutex<ReplayStatus>>,
+}
+
+impl Player {
+    #[must_use]
+    pub fn status(&self) -> ReplayStatus {
+        self.status
+            .lock()
+            .map(|status| status.clone())
+            .unwrap_or_default()
+    }
+
+    /// Closes any open capture and opens `reader` paused at its first record, keeping the
+    /// options and filter chosen for the previous capture.
+    ///
+    /// # Errors
+    /// - Returns an error if the capture has no records
+    pub async fn open(
+        &self,
+        app: AppHandle,
+        reader: CaptureReader,
+    ) -> Result<ReplayStatus, String> {
+        if reader.is_empty() {
+            return Err(format!("{} has no records", reader.path().display()));
+        }
+
+        let mut session = self.session.lock().await;
+
+        if let Some(previous) = session.take() {
+            previous.close().await;
+        }
+
+        let start_us = reader.time_us(0).unwrap_or_default();
+        let end_us = reader.time_us(reader.len() - 1).unwrap_or_default();
+        let current = self.status();
+
+        let status = ReplayStatus {
+            state: ReplayState::Paused,
+            file: Some(reader.path().to_path_buf()),
+            records: reader.len(),
+            duration_ms: end_us.saturating_sub(start_us) / 1000,
+            options: current.options,
+            filter: current.filter,
+            ..ReplayStatus::default()
+        };
+        update(&self.status, |current| *current = status.clone());
+
+        let (controls, received) = mpsc::unbounded_channel();
+        let task = spawn(replay(app, reader, received, Arc::clone(&self.status)));
+
+        *session = Some(Session { controls, task });
+        drop(session);
+
+        Ok(status)
+    }
+
+    /// Passes a request to the replay of the open capture.
+    ///
+    /// # Errors
+    /// - Returns an error if no capture is open
+    pub async fn control(&self, control: Control) -> Result<(), String> {
+        self.session
+            .lock()
+            .await
+            .as_ref()
+            .and_then(|session| session.controls.send(control).ok())
+            .ok_or_else(|| "No capture is open for replay".into())
+    }
+
+    /// Changes the options of the open capture's replay, or of the next capture opened.
+    pub async fn set_options(&self, app: &AppHandle, options: ReplayOptions) {
+        let session = self.session.lock().await;
+
+        match session.as_ref() {
+            // The replay task publishes the change itself
+            Some(session) if session.controls.send(Control::Options(options)).is_ok() => {}
+            _ => {
+                update(&self.status, |status| status.options = options);
+                publish(app, &self.status);
+            }
+        }
+    }
+
+    /// Changes the filter of the open capture's replay, or of the next capture opened.
+    pub async fn set_filter(&self, app: &AppHandle, filter: Option<Filter>) {
+        let session = self.session.lock().await;
+
+        match session.as_ref() {
+            // The replay task publishes the change itself
+            Some(session)
+                if session
+                    .controls
+                    .send(Control::Filter(filter.clone()))
+                    .is_ok() => {}
+            _ => {
+                update(&self.status, |status| status.filter = filter);
+                publish(app, &self.status);
+            }
+        }
+    }
+
+    /// Stops replaying and closes the capture.
+    pub async fn close(&self, app: &AppHandle) {
+        let session = self.session.lock().await.take();
+
+        if let Some(session) = session {
+            session.close().await;
+        }
+
+        update(&self.status, |status| {
+            *status = ReplayStatus {
+                options: status.options,
+                filter: status.filter.take(),
+                ..ReplayStatus::default()
+            };
+        });
+        publish(app, &self.status);
+    }
+}
+
+fn update(status: &Mutex<ReplayStatus>, change: impl FnOnce(&mut ReplayStatus)) {
+    if let Ok(mut status) = status.lock() {
+        change(&mut status);
+    } else {
+        tracing::error!("Replay status lock poisoned");
+    }
+}
+
+fn publish(app: &AppHandle, status: &Mutex<ReplayStatus>) {
+    let Ok(snapshot) = status.lock().map(|status| status.clone()) else {
+        return;
+    };
+
+    app.emit(STATUS_EVENT, snapshot)
+        .unwrap_or_else(|e| tracing::error!("unable to emit {STATUS_EVENT} event: {e}"));
+}
+
+/// The wall-clock instant playback last (re)started and the capture time it started from.
+#[derive(Clone, Copy)]
+struct Anchor {
+    at: Instant,
+    capture_us: u64,
+}
+
+impl Anchor {
+    fn now(capture_us: u64) -> Self {
+        Self {
+            at: Instant::now(),
+            capture_us,
+        }
+    }
+
+    /// When a record captured at `capture_us` is due at `speed`.
+    // Gaps between records are far inside the exact range of f64
+    #[allow(clippy::cast_precision_loss)]
+    fn due(self, capture_us: u64, speed: f64) -> Instant {
+        let gap_s = capture_us.saturating_sub(self.capture_us) as f64 / 1e6;
+        self.at + Duration::from_secs_f64(gap_s / speed)
+    }
+}
+
+/// Sends the capture's records as they fall due and follows the operator's requests until
+/// the control channel closes.
+async fn replay(
+    app: AppHandle,
+    mut reader: CaptureReader,
+    mut controls: mpsc::UnboundedReceiver<Control>,
+    status: Arc<Mutex<ReplayStatus>>,
+) {
+    let records = reader.len();
+    let start_us = reader.time_us(0).unwrap_or_default();
+
+    let (mut options, mut filter) = status
+        .lock()
+        .map(|status| (status.options, status.filter.clone()))
+        .unwrap_or_default();
+    let mut state = ReplayState::Paused;
+    let mut position = 0;
+    let mut sent = 0;
+    let mut anchor = Anchor::now(start_us);
+
+    let mut publish_tick = interval(PUBLISH_INTERVAL);
+    publish_tick.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    let mut changed = true;
+
+    loop {
+        let next_us = reader.time_us(position).unwrap_or(start_us);
+        let due = anchor.due(next_us, options.speed);
+
+        tokio::select! {
+            control = controls.recv() => {
+                let Some(control) = control else {
+                    break;
+                };
+
+                match control {
+                    Control::Play => {
+                        if state == ReplayState::Finished {
+                            position = 0;
+                        }
+                        state = ReplayState::Playing;
+                    }
+                    Control::Pause => {
+                        if state == ReplayState::Playing {
+                            state = ReplayState::Paused;
+                        }
+                    }
+                    Control::Seek(offset_ms) => {
+                        let target_us = start_us.saturating_add(offset_ms.saturating_mul(1000));
+                        position = reader.seek_time(target_us).min(records - 1);
+                        if state == ReplayState::Finished {
+                            state = ReplayState::Paused;
+                        }
+                    }
+                    Control::Options(chosen) => options = chosen,
+                    Control::Filter(chosen) => filter = chosen,
+                }
+
+                anchor = Anchor::now(reader.time_us(position).unwrap_or(start_us));
+                changed = true;
+            }
+            () = sleep_until(due), if state == ReplayState::Playing => {
+                let Some((returned, datagram)) = read(reader, position).await else {
+                    notify(&app, Notice::warning("Replay stopped: the capture reader failed".into()));
+                    break;
+                };
+                reader = returned;
+
+                // A record that cannot be sent is tried again once playback resumes
+                match send(&app, datagram, &options, filter.as_ref()).await {
+                    Ok(true) => {
+                        sent += 1;
+                        position += 1;
+                    }
+                    Ok(false) => position += 1,
+                    Err(e) => {
+                        notify(&app, Notice::warning(format!("Replay paused: {e}")));
+                        state = ReplayState::Paused;
+                    }
+                }
+
+                if position == records {
+                    position = 0;
+                    anchor = Anchor::now(start_us);
+
+                    if !options.looping {
+                        state = ReplayState::Finished;
+                    }
+                }
+
+                changed = true;
+            }
+            _ = publish_tick.tick(), if changed => {
+                let position_ms = reader
+                    .time_us(position)
+                    .map_or(0, |time_us| time_us.saturating_sub(start_us) / 1000);
+
+                update(&status, |status| {
+                    status.state = state;
+                    status.position = position;
+                    status.position_ms = position_ms;
+                    status.sent = sent;
+                    status.options = options;
+                    status.filter.clone_from(&filter);
+                });
+                publish(&app, &status);
+                changed = false;
+            }
+        }
+    }
+
+    publish(&app, &status);
+}
+
+/// Reads a record on a blocking thread, handing the reader back with it, or `None` if the
+/// read panicked.
+async fn read(
+    mut reader: CaptureReader,
+    record: usize,
+) -> Option<(CaptureReader, Result<Datagram, String>)> {
+    tokio::task::spawn_blocking(move || {
+        let datagram = reader.read(record);
+        (reader, datagram)
+    })
+    .await
+    .ok()
+}
+
+/// Sends one record, rewritten as the options ask, unless the filter rejects it. Returns
+/// whether the record was sent.
+async fn send(
+    app: &AppHandle,
+    datagram: Result<Datagram, String>,
+    options: &ReplayOptions,
+    filter: Option<&Filter>,
+) -> Result<bool, String> {
+    let datagram = datagram?;
+
+    // Records are matched as they were captured, before any rewriting
+    if filter.is_some_and(|filter| !filter.matches(&datagram.data, Direction::Received)) {
+        return Ok(false);
+    }
+
+    let mut pdu = datagram.data.to_vec();
+    options.rewrite(&mut pdu);
+
+    app.state::<AppState>().network.replay(&pdu).await?;
+    Ok(true)
+}
@@ -0,0 +1,282 @@
+//! The roster of simulation applications heard on the exercise network.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use bytes::BytesMut;
+use open_dis_rust::common::constants::{
+    ALL_APPLIC, ALL_SITES, NO_APPLIC, NO_SITE, PDU_HEADER_SIZE,
+};
+use open_dis_rust::common::enums::{AcknowledgeFlag, AcknowledgeResponseFlag, PduType};
+use open_dis_rust::common::{GenericHeader, Pdu, PduHeader};
+use open_dis_rust::simulation_management::AcknowledgePdu;
+use serde::Serialize;
+
+use crate::config::Timeouts;
+use crate::core::{ApplicationAddress, Datagram, Monitor, Notice, OwnTraffic, unix_ms};
+
+/// Originating, receiving, flag and response fields and the request ID
+pub(crate) const ACKNOWLEDGE_BODY_LEN: usize = 20;
+
+/// The SIMAN state a participant last acknowledged.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportedState {
+    Running,
+    Frozen,
+}
+
+/// A simulation application seen on the exercise network.
+#[derive(Serialize, Debug, Clone)]
+pub struct Participant {
+    pub application: ApplicationAddress,
+    pub first_seen_ms: u64,
+    pub last_seen_ms: u64,
+    pub pdu_count: u64,
+    pub siman_state: Option<ReportedState>,
+    /// Whether nothing has been heard from the application for longer than the timeout
+    pub silent: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct Roster {
+    participants: HashMap<ApplicationAddress, Participant>,
+    timeouts: Timeouts,
+    /// Silent applications heard from again since the last tick
+    resumed: Vec<ApplicationAddress>,
+    own: OwnTraffic,
+}
+
+impl Roster {
+    /// Returns every participant ordered by site and application.
+    #[must_use]
+    pub fn participants(&self) -> Vec<Participant> {
+        let mut participants: Vec<Participant> = self.participants.values().cloned().collect();
+        participants.sort_by_key(|p| (p.application.site_id, p.application.application_id));
+        participants
+    }
+
+    /// Sets how long an application may go without sending anything before it is silent.
+    pub const fn set_timeouts(&mut self, timeouts: Timeouts) {
+        self.timeouts = timeouts;
+    }
+
+    /// Records the application that sent `datagram`, returning whether it was a DIS PDU.
+    ///
+    /// Almost every PDU body starts with an entity or object ID whose site and application
+    /// identify the sender, so that prefix is used regardless of the PDU type.
+    fn record(&mut self, datagram: &Datagram) -> bool {
+        let data = &datagram.data;
+
+        let Some(application) = sender(data) else {
+            return false;
+        };
+
+        let seen_ms = unix_ms(datagram.received_at).unwrap_or_default();
+
+        let participant = self
+            .participants
+            .entry(application)
+            .or_insert_with(|| Participant {
+                application,
+                first_seen_ms: seen_ms,
+                last_seen_ms: seen_ms,
+                pdu_count: 0,
+                siman_state: None,
+                silent: false,
+            });
+
+        participant.last_seen_ms = seen_ms;
+        participant.pdu_count += 1;
+
+        if participant.silent {
+            participant.silent = false;
+            self.resumed.push(application);
+        }
+
+        if let Some(state) = acknowledged_state(data) {
+            participant.siman_state = Some(state);
+        }
+
+        true
+    }
+}
+
+impl Monitor for Roster {
+    const EVENT: &'static str = "roster";
+
+    type Snapshot = Vec<Participant>;
+
+    /// Records the application that sent `datagram` unless Centurion sent it.
+    fn observe(&mut self, datagram: &Datagram) -> bool {
+        // Centurion's own PDUs come back through multicast loopback and broadcast, and
+        // replayed ones are recorded as they are sent
+        !self.own.is_own(datagram) && self.record(datagram)
+    }
+
+    /// Records the applications of a replayed capture, as its PDUs may never come back to
+    /// the receive socket.
+    fn observe_sent(&mut self, datagram: &Datagram) -> bool {
+        self.own.sent(datagram);
+        datagram.replayed && self.record(datagram)
+    }
+
+    /// Marks applications that have sent nothing for longer than the timeout as silent,
+    /// raising a notice when one goes silent or is heard from again.
+    fn expire(&mut self, now_ms: u64, notices: &mut Vec<Notice>) -> bool {
+        let timeout = self.timeouts.timeout_ms();
+        let mut changed = false;
+
+        for application in self.resumed.drain(..) {
+            notices.push(Notice::info(format!(
+                "Application {application} is transmitting again"
+            )));
+        }
+
+        for participant in self.participants.values_mut() {
+            let silent_ms = now_ms.saturating_sub(participant.last_seen_ms);
+
+            if silent_ms >= timeout && !participant.silent {
+                participant.silent = true;
+                changed = true;
+                notices.push(Notice::warning(format!(
+                    "Application {} has sent nothing for {:.1} s",
+                    participant.application,
+                    Duration::from_millis(silent_ms).as_secs_f64()
+                )));
+            }
+        }
+
+        changed
+    }
+
+    fn snapshot(&self) -> Self::Snapshot {
+        self.participants()
+    }
+}
+
+/// DIS protocol versions, from DIS PDU version 1.0 to IEEE 1278.1-2012
+const PROTOCOL_VERSIONS: std::ops::RangeInclusive<u8> = 1..=7;
+
+/// Reads the simulation address that starts the body of a PDU, if `data` is a whole DIS PDU.
+///
+/// Any datagram could otherwise be taken for a participant, so the header must name a
+/// known protocol version and state the length of the datagram.
+pub(crate) fn sender(data: &[u8]) -> Option<ApplicationAddress> {
+    let version = *data.first()?;
+    let length = data.get(8..10).map(|b| u16::from_be_bytes([b[0], b[1]]))?;
+
+    if !PROTOCOL_VERSIONS.contains(&version) || usize::from(length) != data.len() {
+        return None;
+    }
+
+    let field = |offset: usize| {
+        data.get(PDU_HEADER_SIZE + offset..PDU_HEADER_SIZE + offset + 2)
+            .map(|b| u16::from_be_bytes([b[0], b[1]]))
+    };
+
+    let site_id = field(0)?;
+    let application_id = field(2)?;
+
+    let valid = |value: u16, none: u16, all: u16| value != none && value != all;
+
+    (valid(site_id, NO_SITE, ALL_SITES) && valid(application_id, NO_APPLIC, ALL_APPLIC)).then_some(
+        ApplicationAddress {
+            site_id,
+            application_id,
+        },
+    )
+}
+
+/// Returns the state a participant entered if `data` complies with a Start/Resume or
+/// Stop/Freeze request.
+fn acknowledged_state(data: &[u8]) -> Option<ReportedState> {
+    if data.len() < PDU_HEADER_SIZE + ACKNOWLEDGE_BODY_LEN {
+        return None;
+    }
+
+    let mut bytes = BytesMut::from(data);
+    let header = PduHeader::deserialize(&mut bytes);
+
+    // Acknowledge-R shares the body layout of the plain Acknowledge PDU
+    if !matches!(
+        header.pdu_type,
+        PduType::Acknowledge | PduType::AcknowledgeReliable
+    ) {
+        return None;
+    }
+
+    let ack = AcknowledgePdu::deserialize_without_header(&mut bytes, header).ok()?;
+
+    if ack.response_flag != AcknowledgeResponseFlag::AbleToComply {
+        return None;
+    }
+
+    match ack.acknowledge_flag {
+        AcknowledgeFlag::StartResume => Some(ReportedState::Running),
+        AcknowledgeFlag::StopFreeze => Some(ReportedState::Frozen),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::SystemTime;
+
+    use bytes::Bytes;
+
+    use super::*;
+
+    /// An Entity State header and the entity ID 1:2:3 that starts its body
+    const PDU: [u8; 18] = [7, 1, 1, 1, 0, 0, 0, 0, 0, 18, 0, 0, 0, 1, 0, 2, 0, 3];
+
+    fn datagram(source: &str, replayed: bool) -> Datagram {
+        Datagram {
+            data: Bytes::from_static(&PDU),
+            source: source.parse().expect("a socket address"),
+            received_at: SystemTime::now(),
+            replayed,
+        }
+    }
+
+    fn pdu_counts(roster: &Roster) -> Vec<(ApplicationAddress, u64)> {
+        roster
+            .participants()
+            .into_iter()
+            .map(|p| (p.application, p.pdu_count))
+            .collect()
+    }
+
+    const APPLICATION: ApplicationAddress = ApplicationAddress {
+        site_id: 1,
+        application_id: 2,
+    };
+
+    #[test]
+    fn own_pdus_are_not_participants() {
+        let mut roster = Roster::default();
+
+        assert!(!roster.observe_sent(&datagram("0.0.0.0:50000", false)));
+        assert!(!roster.observe(&datagram("10.0.0.1:50000", false)));
+        assert!(roster.participants().is_empty());
+    }
+
+    #[test]
+    fn replayed_applications_are_participants_once() {
+        let mut roster = Roster::default();
+
+        assert!(roster.observe_sent(&datagram("0.0.0.0:50000", true)));
+        // The copy looped back to the receive socket is not counted again
+        assert!(!roster.observe(&datagram("10.0.0.1:50000", false)));
+
+        assert_eq!(pdu_counts(&roster), [(APPLICATION, 1)]);
+    }
+
+    #[test]
+    fn other_applications_are_participants() {
+        let mut roster = Roster::default();
+
+        assert!(roster.observe(&datagram("10.0.0.2:3000", false)));
+        assert_eq!(pdu_counts(&roster), [(APPLICATION, 1)]);
+    }
+}
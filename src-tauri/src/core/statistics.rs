@@ -0,0 +1,407 @@
+//! Counters of the PDUs and bytes exchanged with the exercise network.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Write};
+
+use open_dis_rust::common::constants::{ALL_ENTITIES, NO_ENTITY, PDU_HEADER_SIZE};
+use open_dis_rust::common::enums::PduType;
+use serde::Serialize;
+
+use crate::config::Statistics;
+use crate::core::{
+    ApplicationAddress, Datagram, EntityIdentifier, Monitor, Notice, OwnTraffic, sender, unix_ms,
+};
+
+/// Offsets of the exercise ID and PDU type within the PDU header
+const EXERCISE_OFFSET: usize = 1;
+const PDU_TYPE_OFFSET: usize = 2;
+
+/// Entities whose average rate decays below this many PDUs per second are forgotten
+const IDLE_RATE: f64 = 0.001;
+
+const CSV_HEADER: &str = "category,direction,key,pdus,bytes,pdus_per_s,bytes_per_s,\
+                          average_pdus_per_s,average_bytes_per_s";
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    Received,
+    Sent,
+}
+
+/// Running totals and rates of one class of traffic.
+#[derive(Serialize, Debug, Clone, Copy, Default)]
+pub struct Counter {
+    pub pdus: u64,
+    pub bytes: u64,
+    /// PDUs per second since the previous tick
+    pub pdu_rate: f64,
+    /// Bytes per second since the previous tick
+    pub byte_rate: f64,
+    /// Exponential moving average of the PDU rate
+    pub average_pdu_rate: f64,
+    /// Exponential moving average of the byte rate
+    pub average_byte_rate: f64,
+    #[serde(skip)]
+    window_pdus: u64,
+    #[serde(skip)]
+    window_bytes: u64,
+}
+
+impl Counter {
+    const fn count(&mut self, bytes: u64) {
+        self.pdus += 1;
+        self.bytes += bytes;
+        self.window_pdus += 1;
+        self.window_bytes += bytes;
+    }
+
+    /// Turns the traffic counted since the previous tick into rates, moving the averages
+    /// towards them by `smoothing`.
+    // Counts per tick are far inside the exact range of f64
+    #[allow(clippy::cast_precision_loss)]
+    fn tick(&mut self, elapsed_s: f64, smoothing: f64) {
+        self.pdu_rate = self.window_pdus as f64 / elapsed_s;
+        self.byte_rate = self.window_bytes as f64 / elapsed_s;
+        self.average_pdu_rate =
+            smoothing.mul_add(self.pdu_rate - self.average_pdu_rate, self.average_pdu_rate);
+        self.average_byte_rate = smoothing.mul_add(
+            self.byte_rate - self.average_byte_rate,
+            self.average_byte_rate,
+        );
+        self.window_pdus = 0;
+        self.window_bytes = 0;
+    }
+}
+
+/// The counter of one PDU type, application, exercise or entity in one direction.
+#[derive(Serialize, Debug, Clone)]
+pub struct TrafficRow {
+    pub direction: Direction,
+    pub key: String,
+    pub counter: Counter,
+}
+
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct TrafficSnapshot {
+    /// Unix milliseconds of the first PDU counted, zero before any
+    pub since_ms: u64,
+    pub totals: Vec<TrafficRow>,
+    pub pdu_types: Vec<TrafficRow>,
+    pub applications: Vec<TrafficRow>,
+    pub exercises: Vec<TrafficRow>,
+    /// Entities averaging more PDUs per second than the scenario allows
+    pub chatty_entities: Vec<TrafficRow>,
+}
+
+impl TrafficSnapshot {
+    /// Formats every counter as a CSV row, preceded by a header row.
+    #[must_use]
+    pub fn to_csv(&self) -> String {
+        let categories = [
+            ("total", &self.totals),
+            ("pdu_type", &self.pdu_types),
+            ("application", &self.applications),
+            ("exercise", &self.exercises),
+            ("chatty_entity", &self.chatty_entities),
+        ];
+
+        let mut csv = format!("{CSV_HEADER}\n");
+
+        for (category, rows) in categories {
+            for row in rows {
+                let c = &row.counter;
+
+                // Writing to a String cannot fail
+                let _ = writeln!(
+                    csv,
+                    "{category},{},{},{},{},{:.3},{:.1},{:.3},{:.1}",
+                    row.direction,
+                    row.key,
+                    c.pdus,
+                    c.bytes,
+                    c.pdu_rate,
+                    c.byte_rate,
+                    c.average_pdu_rate,
+                    c.average_byte_rate
+                );
+            }
+        }
+
+        csv
+    }
+}
+
+/// Counts received and sent PDUs by type, application and exercise, and received PDUs by
+/// originating entity to find chatty ones.
+#[derive(Debug, Default)]
+pub struct TrafficStatistics {
+    settings: Statistics,
+    since_ms: u64,
+    last_tick_ms: u64,
+    totals: BTreeMap<Direction, Counter>,
+    pdu_types: BTreeMap<(Direction, u8), Counter>,
+    applications: BTreeMap<(Direction, ApplicationAddress), Counter>,
+    exercises: BTreeMap<(Direction, u8), Counter>,
+    entities: BTreeMap<EntityIdentifier, Counter>,
+    chatty: BTreeSet<EntityIdentifier>,
+    own: OwnTraffic,
+}
+
+impl TrafficStatistics {
+    /// Sets the averaging window and chatty entity rate used from the next tick.
+    pub const fn set_settings(&mut self, settings: Statistics) {
+        self.settings = settings;
+    }
+
+    /// Clears every counter, keeping the settings.
+    pub fn reset(&mut self) {
+        *self = Self {
+            settings: self.settings,
+            own: std::mem::take(&mut self.own),
+            ..Self::default()
+        };
+    }
+
+    #[must_use]
+    pub fn statistics(&self) -> TrafficSnapshot {
+        fn rows<K: Copy>(
+            counters: &BTreeMap<(Direction, K), Counter>,
+            key: impl Fn(K) -> String,
+        ) -> Vec<TrafficRow> {
+            counters
+                .iter()
+                .map(|(&(direction, k), &counter)| TrafficRow {
+                    direction,
+                    key: key(k),
+                    counter,
+                })
+                .collect()
+        }
+
+        TrafficSnapshot {
+            since_ms: self.since_ms,
+            totals: self
+                .totals
+                .iter()
+                .map(|(&direction, &counter)| TrafficRow {
+                    direction,
+                    key: "all".into(),
+                    counter,
+                })
+                .collect(),
+            pdu_types: rows(&self.pdu_types, pdu_type_name),
+            applications: rows(&self.applications, |a| a.to_string()),
+            exercises: rows(&self.exercises, |e| e.to_string()),
+            chatty_entities: self
+                .chatty
+                .iter()
+                .filter_map(|id| {
+                    self.entities.get(id).map(|&counter| TrafficRow {
+                        direction: Direction::Received,
+                        key: id.to_string(),
+                        counter,
+                    })
+                })
+                .collect(),
+        }
+    }
+
+    /// Adds a PDU to every counter it belongs to, returning whether it was a DIS PDU.
+    fn count(&mut self, direction: Direction, datagram: &Datagram) -> bool {
+        let data = &datagram.data;
+
+        if data.len() < PDU_HEADER_SIZE {
+            return false;
+        }
+
+        let (Some(&exercise), Some(&pdu_type)) =
+            (data.get(EXERCISE_OFFSET), data.get(PDU_TYPE_OFFSET))
+        else {
+            return false;
+        };
+
+        if self.totals.is_empty() {
+            self.since_ms = unix_ms(datagram.received_at).unwrap_or_default();
+            self.last_tick_ms = self.since_ms;
+        }
+
+        let bytes = data.len() as u64;
+
+        self.totals.entry(direction).or_default().count(bytes);
+        self.pdu_types
+            .entry((direction, pdu_type))
+            .or_default()
+            .count(bytes);
+        self.exercises
+            .entry((direction, exercise))
+            .or_default()
+            .count(bytes);
+
+        if let Some(application) = sender(data) {
+            self.applications
+                .entry((direction, application))
+                .or_default()
+                .count(bytes);
+        }
+
+        if direction == Direction::Received
+            && let Some(entity) = originator(data)
+        {
+            self.entities.entry(entity).or_default().count(bytes);
+        }
+
+        true
+    }
+}
+
+impl Monitor for TrafficStatistics {
+    const EVENT: &'static str = "traffic";
+
+    type Snapshot = TrafficSnapshot;
+
+    /// Counts a received PDU, unless it is one of Centurion's own coming back through
+    /// multicast loopback or broadcast, which was already counted as sent.
+    fn observe(&mut self, datagram: &Datagram) -> bool {
+        !self.own.is_own(datagram) && self.count(Direction::Received, datagram)
+    }
+
+    fn observe_sent(&mut self, datagram: &Datagram) -> bool {
+        self.own.sent(datagram);
+        self.count(Direction::Sent, datagram)
+    }
+
+    /// Updates the rates on every tick, flagging entities whose average rate rises above
+    /// the scenario's limit.
+    // Ticks are about a second apart, far inside the exact range of f64
+    #[allow(clippy::cast_precision_loss)]
+    fn expire(&mut self, now_ms: u64, notices: &mut Vec<Notice>) -> bool {
+        let elapsed_s = now_ms.saturating_sub(self.last_tick_ms) as f64 / 1000.0;
+
+        if self.totals.is_empty() || elapsed_s <= 0.0 {
+            return false;
+        }
+
+        self.last_tick_ms = now_ms;
+
+        let window = self.settings.average_window_s.max(elapsed_s);
+        let smoothing = 1.0 - (-elapsed_s / window).exp();
+
+        self.totals
+            .values_mut()
+            .chain(self.pdu_types.values_mut())
+            .chain(self.applications.values_mut())
+            .chain(self.exercises.values_mut())
+            .for_each(|counter| counter.tick(elapsed_s, smoothing));
+
+        let limit = self.settings.chatty_entity_rate;
+        let chatty = &mut self.chatty;
+
+        self.entities.retain(|id, counter| {
+            counter.tick(elapsed_s, smoothing);
+
+            if counter.average_pdu_rate > limit {
+                if chatty.insert(*id) {
+                    notices.push(Notice::warning(format!(
+                        "Entity {id} is averaging {:.1} PDUs/s, above the limit of {limit:.1}",
+                        counter.average_pdu_rate
+                    )));
+                }
+            } else {
+                chatty.remove(id);
+            }
+
+            counter.average_pdu_rate >= IDLE_RATE
+        });
+
+        true
+    }
+
+    fn snapshot(&self) -> Self::Snapshot {
+        self.statistics()
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Received => write!(f, "received"),
+            Self::Sent => write!(f, "sent"),
+        }
+    }
+}
+
+/// Names a PDU type code, such as `EntityState (1)`.
+pub(crate) fn pdu_type_name(code: u8) -> String {
+    let pdu_type = PduType::deserialize(&mut &[code][..]);
+
+    if pdu_type as u8 == code {
+        format!("{pdu_type:?} ({code})")
+    } else {
+        format!("Unknown ({code})")
+    }
+}
+
+/// Reads the entity that starts the body of a PDU, which for most PDU types originated it.
+pub(crate) fn originator(data: &[u8]) -> Option<EntityIdentifier> {
+    let application = sender(data)?;
+    let entity_id = data
+        .get(PDU_HEADER_SIZE + 4..PDU_HEADER_SIZE + 6)
+        .map(|b| u16::from_be_bytes([b[0], b[1]]))?;
+
+    (entity_id != NO_ENTITY && entity_id != ALL_ENTITIES).then_some(EntityIdentifier {
+        site_id: application.site_id,
+        application_id: application.application_id,
+        entity_id,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::SystemTime;
+
+    use bytes::Bytes;
+
+    use super::*;
+
+    fn datagram(data: &'static [u8], source: &str) -> Datagram {
+        Datagram {
+            data: Bytes::from_static(data),
+            source: source.parse().expect("a socket address"),
+            received_at: SystemTime::now(),
+            replayed: false,
+        }
+    }
+
+    fn total(statistics: &TrafficStatistics, direction: Direction) -> u64 {
+        statistics
+            .totals
+            .get(&direction)
+            .map_or(0, |counter| counter.pdus)
+    }
+
+    const PDU: &[u8] = &[7, 1, 1, 1, 0, 0, 0, 0, 0, 12, 0, 0];
+    const OTHER_PDU: &[u8] = &[7, 1, 2, 1, 0, 0, 0, 0, 0, 12, 0, 0];
+
+    #[test]
+    fn looped_back_pdus_are_not_received() {
+        let mut statistics = TrafficStatistics::default();
+
+        assert!(statistics.observe_sent(&datagram(PDU, "0.0.0.0:50000")));
+        assert!(!statistics.observe(&datagram(PDU, "10.0.0.1:50000")));
+        assert!(statistics.observe(&datagram(OTHER_PDU, "10.0.0.2:3000")));
+
+        assert_eq!(total(&statistics, Direction::Sent), 1);
+        assert_eq!(total(&statistics, Direction::Received), 1);
+    }
+
+    #[test]
+    fn reset_remembers_own_traffic() {
+        let mut statistics = TrafficStatistics::default();
+        statistics.observe_sent(&datagram(PDU, "0.0.0.0:50000"));
+        statistics.reset();
+
+        assert!(!statistics.observe(&datagram(PDU, "10.0.0.1:50000")));
+        assert_eq!(total(&statistics, Direction::Received), 0);
+    }
+}
@@ -0,0 +1,78 @@
+//! Operator-selectable contents of a Stop/Freeze PDU.
+
+use open_dis_rust::common::enums::{FrozenBehavior, Reason};
+use serde::Deserialize;
+
+/// Mirrors the Stop/Freeze reasons of SISO-REF-010 [UID 67] so they can cross the Tauri
+/// boundary.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StopReason {
+    Other,
+    Recess,
+    Termination,
+    SystemFailure,
+    SecurityViolation,
+    EntityReconstitution,
+    StopForReset,
+    StopForRestart,
+    AbortTrainingReturnToTacticalOperations,
+}
+
+impl From<StopReason> for Reason {
+    fn from(reason: StopReason) -> Self {
+        match reason {
+            StopReason::Other => Self::Other,
+            StopReason::Recess => Self::Recess,
+            StopReason::Termination => Self::Termination,
+            StopReason::SystemFailure => Self::SystemFailure,
+            StopReason::SecurityViolation => Self::SecurityViolation,
+            StopReason::EntityReconstitution => Self::EntityReconstitution,
+            StopReason::StopForReset => Self::StopForReset,
+            StopReason::StopForRestart => Self::StopForRestart,
+            StopReason::AbortTrainingReturnToTacticalOperations => {
+                Self::AbortTrainingReturnToTacticalOperations
+            }
+        }
+    }
+}
+
+/// The reason for a Stop/Freeze and how frozen simulations should behave.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StopFreezeOptions {
+    pub reason: StopReason,
+    /// Keep advancing the simulation clock while frozen
+    #[serde(default)]
+    pub run_simulation_clock: bool,
+    /// Keep transmitting updates for owned entities while frozen
+    #[serde(default)]
+    pub transmit_updates: bool,
+    /// Keep processing updates received from other simulations while frozen
+    #[serde(default)]
+    pub process_updates: bool,
+}
+
+impl StopFreezeOptions {
+    /// Options for a Stop/Freeze with the given reason and every activity halted.
+    #[must_use]
+    pub const fn halt(reason: StopReason) -> Self {
+        Self {
+            reason,
+            run_simulation_clock: false,
+            transmit_updates: false,
+            process_updates: false,
+        }
+    }
+
+    #[must_use]
+    pub fn frozen_behavior(&self) -> FrozenBehavior {
+        let mut behavior = FrozenBehavior::empty();
+        behavior.set(
+            FrozenBehavior::RunSimulationClock,
+            self.run_simulation_clock,
+        );
+        behavior.set(FrozenBehavior::TransmitUpdates, self.transmit_updates);
+        behavior.set(FrozenBehavior::ProcessUpdates, self.process_updates);
+        behavior
+    }
+}
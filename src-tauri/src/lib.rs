@@ -35,26 +35,102 @@ pub fn run() {
 
             let handle = app.handle().clone();
 
+            tauri_spawn(core::watch(handle.clone(), |state| &state.roster));
+            tauri_spawn(core::watch(handle.clone(), |state| &state.conflicts));
+            tauri_spawn(core::watch(handle.clone(), |state| &state.entities));
+            tauri_spawn(core::watch(handle.clone(), |state| &state.traffic));
+            tauri_spawn(core::watch(handle.clone(), |state| &state.pdu_log));
+
             tauri_spawn(async move {
                 let app_config = config::load_config(&handle).await;
 
                 utils::init_logging(&log_dir, app_config.advanced.log_level).unwrap_or_else(|e| {
                     eprintln!("Failed to initialize logging: {e}");
                 });
+
+                let scenario = app_config.scenario_config.clone();
+                let state = handle.state::<core::AppState>();
+
+                if let Ok(mut conflicts) = state.conflicts.lock() {
+                    conflicts.set_originator(app_config.simulation_address.originating_id().ok());
+                }
+
+                // Commands originate their PDUs from the simulation address in the loaded config
+                if let Ok(mut config) = handle.state::<RwLock<AppConfig>>().write() {
+                    *config = app_config;
+                } else {
+                    tracing::error!("AppConfig lock poisoned");
+                }
+
+                if let Err(e) = state.apply_scenario(&scenario).await {
+                    tracing::error!("Unable to start the network service: {e}");
+                }
             });
 
             Ok(())
         })
         .manage(core::AppState {
-            simulation_state: Mutex::new(core::SimulationState::Stopped),
+            simulation_state: Mutex::new(core::SimulationState::default()),
             request_ids: Mutex::new(core::RequestIds::new()),
+            network: core::NetworkService::new(),
+            roster: Mutex::new(core::Roster::default()),
+            conflicts: Mutex::new(core::AddressConflicts::default()),
+            entities: Mutex::new(core::EntityRegistry::default()),
+            traffic: Mutex::new(core::TrafficStatistics::default()),
+            recorder: core::Recorder::default(),
+            player: core::Player::default(),
+            pdu_log: Mutex::new(core::PduLog::default()),
+            browser: Mutex::new(None),
         })
         .manage(RwLock::new(AppConfig::default()))
         .invoke_handler(tauri::generate_handler![
             cmd::config::get_config,
             cmd::config::save_config,
             cmd::config::load_scenario_config,
+            cmd::config::get_simulation_address,
+            cmd::config::get_display_config,
+            cmd::config::save_display_config,
+            cmd::map::load_map_background,
+            cmd::map::get_map_background,
+            cmd::map::clear_map_background,
             cmd::transmit::send_siman_pdu,
+            cmd::transmit::get_simulation_state,
+            cmd::transmit::list_actions,
+            cmd::transmit::send_action_request,
+            cmd::transmit::create_entity,
+            cmd::transmit::remove_entity,
+            cmd::transmit::set_data,
+            cmd::transmit::query_data,
+            cmd::monitor::get_roster,
+            cmd::monitor::get_entities,
+            cmd::monitor::get_traffic_statistics,
+            cmd::monitor::reset_traffic_statistics,
+            cmd::monitor::export_traffic_statistics,
+            cmd::recording::start_recording,
+            cmd::recording::stop_recording,
+            cmd::recording::get_recording_status,
+            cmd::replay::open_replay,
+            cmd::replay::play_replay,
+            cmd::replay::pause_replay,
+            cmd::replay::seek_replay,
+            cmd::replay::set_replay_options,
+            cmd::replay::set_replay_filter,
+            cmd::replay::close_replay,
+            cmd::replay::get_replay_status,
+            cmd::pcap::export_capture_pcapng,
+            cmd::pcap::import_capture_pcap,
+            cmd::inspector::get_pdu_log,
+            cmd::inspector::inspect_logged_pdu,
+            cmd::inspector::open_capture_browser,
+            cmd::inspector::get_capture_rows,
+            cmd::inspector::inspect_capture_pdu,
+            cmd::inspector::close_capture_browser,
+            cmd::inspector::set_live_filter,
+            cmd::inspector::set_capture_filter,
+            cmd::filter::check_filter,
+            cmd::filter::list_filters,
+            cmd::filter::save_filter,
+            cmd::filter::delete_filter,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,200 @@
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+use crate::dashboard::{Notification, NotificationLevel};
+use crate::datum_editor::{DatumEditor, DatumRow, build_datums};
+use crate::forms::parse_u32;
+use crate::models::{Acknowledgement, CommandTarget, DatumSet};
+use crate::target_selector::TargetSelector;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "core"], catch)]
+    async fn invoke(cmd: &str, args: JsValue) -> Result<JsValue, JsValue>;
+}
+
+#[derive(Clone, PartialEq, Eq, Deserialize)]
+struct ActionOption {
+    id: u32,
+    name: String,
+}
+
+#[derive(Serialize)]
+struct ActionRequestArgs {
+    action: u32,
+    target: CommandTarget,
+    datums: DatumSet,
+}
+
+/// Value of the action selector that switches to a free-form action ID
+const CUSTOM_ACTION: &str = "custom";
+
+#[derive(Properties, PartialEq)]
+pub struct ActionRequestProps {
+    pub on_notify: Callback<Notification>,
+    pub on_acknowledgement: Callback<Acknowledgement>,
+}
+
+/// A generic Action Request form covering the full action catalogue and simulator-specific
+/// action IDs, with an optional datum payload.
+#[function_component(ActionRequest)]
+pub fn action_request(props: &ActionRequestProps) -> Html {
+    let catalogue = use_state(Vec::<ActionOption>::new);
+    let selected = use_state(String::new);
+    let custom_id = use_state(String::new);
+    let target = use_state(|| CommandTarget::All);
+    let rows = use_state(Vec::<DatumRow>::new);
+
+    {
+        let catalogue = catalogue.clone();
+        let selected = selected.clone();
+        let notify = props.on_notify.clone();
+
+        use_effect_with((), move |()| {
+            spawn_local(async move {
+                let result = invoke("list_actions", JsValue::NULL)
+                    .await
+                    .map_err(|err| err.as_string().unwrap_or_else(|| "unknown error".into()))
+                    .and_then(|value| {
+                        serde_wasm_bindgen::from_value::<Vec<ActionOption>>(value)
+                            .map_err(|e| format!("Unable to read action catalogue: {e}"))
+                    });
+
+                match result {
+                    Ok(actions) => {
+                        if let Some(first) = actions.first() {
+                            selected.set(first.id.to_string());
+                        }
+                        catalogue.set(actions);
+                    }
+                    Err(message) => notify.emit(Notification {
+                        message,
+                        level: NotificationLevel::Error,
+                    }),
+                }
+            });
+        });
+    }
+
+    let on_select = {
+        let selected = selected.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            selected.set(select.value());
+        })
+    };
+
+    let on_custom = {
+        let custom_id = custom_id.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            custom_id.set(input.value());
+        })
+    };
+
+    let on_target = {
+        let target = target.clone();
+        Callback::from(move |selected: CommandTarget| target.set(selected))
+    };
+
+    let on_send = {
+        let notify = props.on_notify.clone();
+        let on_acknowledgement = props.on_acknowledgement.clone();
+        let selected = selected.clone();
+        let custom_id = custom_id.clone();
+        let rows = rows.clone();
+
+        Callback::from(move |_| {
+            let notify = notify.clone();
+            let on_acknowledgement = on_acknowledgement.clone();
+
+            let action = if *selected == CUSTOM_ACTION {
+                parse_u32(&custom_id)
+            } else {
+                parse_u32(&selected)
+            };
+
+            let args = action.and_then(|action| {
+                build_datums(&rows).map(|datums| ActionRequestArgs {
+                    action,
+                    target: *target,
+                    datums,
+                })
+            });
+
+            let args = match args {
+                Ok(args) => args,
+                Err(message) => {
+                    notify.emit(Notification {
+                        message,
+                        level: NotificationLevel::Error,
+                    });
+                    return;
+                }
+            };
+
+            spawn_local(async move {
+                let result = invoke(
+                    "send_action_request",
+                    serde_wasm_bindgen::to_value(&args).unwrap_or_default(),
+                )
+                .await
+                .map_err(|err| err.as_string().unwrap_or_else(|| "unknown error".into()))
+                .and_then(|value| {
+                    serde_wasm_bindgen::from_value::<Acknowledgement>(value)
+                        .map_err(|e| format!("Unable to read Action Response: {e}"))
+                });
+
+                match result {
+                    Ok(ack) => on_acknowledgement.emit(ack),
+                    Err(message) => notify.emit(Notification {
+                        message,
+                        level: NotificationLevel::Error,
+                    }),
+                }
+            });
+        })
+    };
+
+    let options = catalogue.iter().map(|action| {
+        let value = action.id.to_string();
+        let is_selected = *selected == value;
+        html! {
+            <option selected={is_selected} value={value}>
+                {format!("{} - {}", action.id, action.name)}
+            </option>
+        }
+    });
+
+    html! {
+        <section class="panel">
+            <h2>{"Action Request"}</h2>
+            <TargetSelector on_change={on_target} />
+            <div class="form-grid">
+                <label>
+                    {"Action"}
+                    <select onchange={on_select}>
+                        {for options}
+                        <option selected={*selected == CUSTOM_ACTION} value={CUSTOM_ACTION}>
+                            {"Custom action ID"}
+                        </option>
+                    </select>
+                </label>
+                if *selected == CUSTOM_ACTION {
+                    <label>
+                        {"Action ID"}
+                        <input placeholder="decimal or 0x" value={(*custom_id).clone()}
+                            oninput={on_custom} />
+                    </label>
+                }
+            </div>
+            <DatumEditor rows={rows.clone()} />
+            <div class="controls">
+                <button class="primary" onclick={on_send}>{"Send"}</button>
+            </div>
+        </section>
+    }
+}
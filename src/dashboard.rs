@@ -1,18 +1,52 @@
 use std::fmt;
 
+use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::spawn_local;
 use yew::prelude::*;
 
+use crate::action_request::ActionRequest;
+use crate::data_exchange::DataExchange;
+use crate::entity_management::EntityManagement;
+use crate::entity_table::EntityTable;
+use crate::events::subscribe;
+use crate::inspector::Inspector;
+use crate::models::{
+    Acknowledgement, CommandTarget, DisplayConfig, EntityIdentifier, Outcome, ParticipantReply,
+    Schedule, SimulationState, SimulationStatus, StopFreezeOptions,
+};
+use crate::recording::Recording;
+use crate::replay::Replay;
+use crate::roster::Roster;
+use crate::schedule::ScheduleInput;
+use crate::stop_freeze::StopFreezeDialog;
+use crate::tactical_map::TacticalMap;
+use crate::target_selector::TargetSelector;
+use crate::traffic::TrafficStatistics;
+
 #[wasm_bindgen]
 extern "C" {
     #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "core"], catch)]
     async fn invoke(cmd: &str, args: JsValue) -> Result<JsValue, JsValue>;
 }
 
+#[derive(Serialize)]
+struct SimanArgs {
+    command: &'static str,
+    target: CommandTarget,
+    schedule: Option<Schedule>,
+    freeze: Option<StopFreezeOptions>,
+}
+
+#[derive(Serialize)]
+struct DisplayArgs {
+    display: DisplayConfig,
+}
+
 #[allow(dead_code)]
-#[derive(Clone, PartialEq, Eq)]
-enum NotificationLevel {
+#[derive(Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NotificationLevel {
     Info,
     Warning,
     Error,
@@ -28,10 +62,11 @@ impl fmt::Display for NotificationLevel {
     }
 }
 
-#[derive(Clone, PartialEq, Eq)]
-struct Notification {
-    message: String,
-    level: NotificationLevel,
+/// A message for the operator, raised by the dashboard or sent by the backend as a notice.
+#[derive(Clone, PartialEq, Eq, Deserialize)]
+pub struct Notification {
+    pub message: String,
+    pub level: NotificationLevel,
 }
 
 #[derive(Clone, PartialEq, Eq)]
@@ -55,6 +90,43 @@ impl Reducible for Notifications {
     }
 }
 
+/// The latest acknowledgement received for each command target.
+#[derive(Clone, PartialEq, Eq)]
+struct Acknowledgements(Vec<Acknowledgement>);
+
+impl Reducible for Acknowledgements {
+    type Action = Acknowledgement;
+
+    fn reduce(self: std::rc::Rc<Self>, ack: Self::Action) -> std::rc::Rc<Self> {
+        let mut list = self.0.clone();
+        list.retain(|existing| existing.target != ack.target);
+        list.push(ack);
+        Self(list).into()
+    }
+}
+
+fn participant_notification(command: &str, participant: &ParticipantReply) -> Notification {
+    let level = match participant.outcome {
+        Outcome::Complied => NotificationLevel::Info,
+        Outcome::Pending | Outcome::Partial | Outcome::Unknown => NotificationLevel::Warning,
+        Outcome::Refused => NotificationLevel::Error,
+    };
+
+    let datums = if participant.datums.fixed.is_empty() && participant.datums.variable.is_empty() {
+        String::new()
+    } else {
+        format!(" [{}]", participant.datums)
+    };
+
+    Notification {
+        message: format!(
+            "{command}: {} {} ({}) in {} ms{datums}",
+            participant.entity, participant.outcome, participant.status, participant.elapsed_ms
+        ),
+        level,
+    }
+}
+
 #[function_component(Dashboard)]
 pub fn dashboard() -> Html {
     let notifications = use_reducer(|| Notifications(Vec::new()));
@@ -67,48 +139,311 @@ pub fn dashboard() -> Html {
         })
     };
 
-    let send_siman_pdu = {
+    let simulation_address = use_state(|| None::<EntityIdentifier>);
+
+    {
         let notify = append_notification.clone();
+        let simulation_address = simulation_address.clone();
 
-        Callback::from(move |command: &'static str| {
-            let notify = notify.clone();
+        use_effect_with((), move |()| {
+            // Published again with no address once the conflict has ended
+            subscribe("address-conflict", {
+                let notify = notify.clone();
+                Callback::from(move |address: Option<EntityIdentifier>| {
+                    if let Some(address) = address {
+                        notify.emit(Notification {
+                            message: format!(
+                                "Another simulation manager is sending SIMAN PDUs from {address}"
+                            ),
+                            level: NotificationLevel::Warning,
+                        });
+                    }
+                })
+            });
+
+            // Timed-out entities and silent applications
+            subscribe("notice", notify.clone());
 
             spawn_local(async move {
-                let payload = serde_json::json!({"command": command});
+                let result = invoke("get_simulation_address", JsValue::NULL)
+                    .await
+                    .map_err(|err| err.as_string().unwrap_or_else(|| "unknown error".into()))
+                    .and_then(|value| {
+                        serde_wasm_bindgen::from_value::<EntityIdentifier>(value)
+                            .map_err(|e| format!("Unable to read simulation address: {e}"))
+                    });
 
-                let result = invoke(
-                    "send_siman_pdu",
-                    serde_wasm_bindgen::to_value(&payload).unwrap_or_default(),
+                match result {
+                    Ok(address) => simulation_address.set(Some(address)),
+                    Err(message) => notify.emit(Notification {
+                        message,
+                        level: NotificationLevel::Error,
+                    }),
+                }
+            });
+        });
+    }
+
+    let display = use_state(DisplayConfig::default);
+
+    {
+        let notify = append_notification.clone();
+        let display = display.clone();
+
+        use_effect_with((), move |()| {
+            spawn_local(async move {
+                let result = invoke("get_display_config", JsValue::NULL)
+                    .await
+                    .map_err(|err| err.as_string().unwrap_or_else(|| "unknown error".into()))
+                    .and_then(|value| {
+                        serde_wasm_bindgen::from_value::<DisplayConfig>(value)
+                            .map_err(|e| format!("Unable to read display preferences: {e}"))
+                    });
+
+                match result {
+                    Ok(config) => display.set(config),
+                    Err(message) => notify.emit(Notification {
+                        message,
+                        level: NotificationLevel::Error,
+                    }),
+                }
+            });
+        });
+    }
+
+    // Applied immediately and persisted in the background
+    let on_display = {
+        let notify = append_notification.clone();
+        let display = display.clone();
+
+        Callback::from(move |config: DisplayConfig| {
+            display.set(config);
+
+            let notify = notify.clone();
+            spawn_local(async move {
+                let args = DisplayArgs { display: config };
+
+                if let Err(err) = invoke(
+                    "save_display_config",
+                    serde_wasm_bindgen::to_value(&args).unwrap_or_default(),
                 )
-                .await;
+                .await
+                {
+                    notify.emit(Notification {
+                        message: err.as_string().unwrap_or_else(|| "unknown error".into()),
+                        level: NotificationLevel::Error,
+                    });
+                }
+            });
+        })
+    };
+
+    let simulation = use_state(SimulationStatus::default);
+
+    {
+        let notify = append_notification.clone();
+        let simulation = simulation.clone();
+
+        use_effect_with((), move |()| {
+            {
+                let simulation = simulation.clone();
+                subscribe(
+                    "simulation-state",
+                    Callback::from(move |latest: SimulationStatus| simulation.set(latest)),
+                );
+            }
+
+            spawn_local(async move {
+                let result = invoke("get_simulation_state", JsValue::NULL)
+                    .await
+                    .map_err(|err| err.as_string().unwrap_or_else(|| "unknown error".into()))
+                    .and_then(|value| {
+                        serde_wasm_bindgen::from_value::<SimulationStatus>(value)
+                            .map_err(|e| format!("Unable to read exercise state: {e}"))
+                    });
+
                 match result {
-                    Ok(_) => {
-                        // noop
-                    }
-                    Err(err) => {
-                        notify.emit(Notification {
-                            message: err.as_string().unwrap_or_else(|| "unknown error".into()),
-                            level: NotificationLevel::Error,
-                        });
-                    }
+                    Ok(latest) => simulation.set(latest),
+                    Err(message) => notify.emit(Notification {
+                        message,
+                        level: NotificationLevel::Error,
+                    }),
                 }
             });
+        });
+    }
+
+    let target = use_state(|| CommandTarget::All);
+    let schedule = use_state(|| None::<Schedule>);
+    let acknowledgements = use_reducer(|| Acknowledgements(Vec::new()));
+
+    let record_acknowledgement = {
+        let notify = append_notification.clone();
+        let acknowledgements = acknowledgements.clone();
+
+        Callback::from(move |ack: Acknowledgement| {
+            let command = ack.command.to_uppercase();
+
+            match ack.participants.as_deref() {
+                None => notify.emit(Notification {
+                    message: format!("{command} sent unacknowledged to {}", ack.target),
+                    level: NotificationLevel::Info,
+                }),
+                Some(participants) => {
+                    for participant in participants {
+                        notify.emit(participant_notification(&command, participant));
+                    }
+                }
+            }
+
+            acknowledgements.dispatch(ack);
         })
     };
 
+    let send_siman_pdu = {
+        let notify = append_notification.clone();
+        let target = target.clone();
+        let schedule = schedule.clone();
+        let record_acknowledgement = record_acknowledgement.clone();
+
+        Callback::from(
+            move |(command, freeze): (&'static str, Option<StopFreezeOptions>)| {
+                let notify = notify.clone();
+                let record_acknowledgement = record_acknowledgement.clone();
+                let args = SimanArgs {
+                    command,
+                    target: *target,
+                    schedule: *schedule,
+                    freeze,
+                };
+
+                spawn_local(async move {
+                    let result = invoke(
+                        "send_siman_pdu",
+                        serde_wasm_bindgen::to_value(&args).unwrap_or_default(),
+                    )
+                    .await
+                    .map_err(|err| err.as_string().unwrap_or_else(|| "unknown error".into()))
+                    .and_then(|value| {
+                        serde_wasm_bindgen::from_value::<Acknowledgement>(value)
+                            .map_err(|e| format!("Unable to read {command} response: {e}"))
+                    });
+
+                    match result {
+                        Ok(ack) => record_acknowledgement.emit(ack),
+                        Err(message) => notify.emit(Notification {
+                            message,
+                            level: NotificationLevel::Error,
+                        }),
+                    }
+                });
+            },
+        )
+    };
+
     let send_command = |cmd: &'static str| {
         let send = send_siman_pdu.clone();
         let notify = append_notification.clone();
+        let target = target.clone();
 
         Callback::from(move |_| {
-            send.emit(cmd);
+            send.emit((cmd, None));
             notify.emit(Notification {
-                message: format!("{} command sent", cmd.to_uppercase()),
+                message: format!("{} command sent to {}", cmd.to_uppercase(), *target),
                 level: NotificationLevel::Info,
             });
         })
     };
 
+    let stop_freeze_open = use_state(|| false);
+
+    let open_stop_freeze = {
+        let stop_freeze_open = stop_freeze_open.clone();
+        Callback::from(move |_| stop_freeze_open.set(true))
+    };
+
+    let close_stop_freeze = {
+        let stop_freeze_open = stop_freeze_open.clone();
+        Callback::from(move |()| stop_freeze_open.set(false))
+    };
+
+    let send_stop_freeze = {
+        let send = send_siman_pdu.clone();
+        let notify = append_notification.clone();
+        let target = target.clone();
+        let stop_freeze_open = stop_freeze_open.clone();
+
+        Callback::from(move |options: StopFreezeOptions| {
+            stop_freeze_open.set(false);
+            send.emit(("stop_freeze", Some(options)));
+            notify.emit(Notification {
+                message: format!(
+                    "STOP_FREEZE ({:?}) command sent to {}",
+                    options.reason, *target
+                ),
+                level: NotificationLevel::Info,
+            });
+        })
+    };
+
+    let on_target = {
+        let target = target.clone();
+        Callback::from(move |selected: CommandTarget| target.set(selected))
+    };
+
+    let on_schedule = Callback::from(move |selected: Option<Schedule>| schedule.set(selected));
+
+    // Commands the exercise cannot take from its current state are disabled. Commands to part
+    // of the exercise leave its state alone, so they are always allowed.
+    let blocked = |command: &str| {
+        *target == CommandTarget::All && !simulation.commands.iter().any(|valid| valid == command)
+    };
+    let exercise_state = match simulation.state {
+        SimulationState::Uninitialized => "Uninitialized",
+        SimulationState::Initialized => "Initialized",
+        SimulationState::Running => "Running",
+        SimulationState::Paused => "Paused",
+        SimulationState::Stopped => "Stopped",
+    };
+
+    let acknowledgement_rows = acknowledgements.0.iter().flat_map(|ack| {
+        let header = |span: usize| {
+            html! {
+                <>
+                    <td rowspan={span.to_string()}>{ack.target.to_string()}</td>
+                    <td rowspan={span.to_string()}>{ack.command.to_uppercase()}</td>
+                    <td rowspan={span.to_string()}>{ack.request_id}</td>
+                </>
+            }
+        };
+
+        match ack.participants.as_deref() {
+            None | Some([]) => vec![html! {
+                <tr>
+                    {header(1)}
+                    <td colspan="4">{"unacknowledged"}</td>
+                </tr>
+            }],
+            Some(participants) => participants
+                .iter()
+                .enumerate()
+                .map(|(index, participant)| {
+                    html! {
+                        <tr>
+                            if index == 0 {
+                                {header(participants.len())}
+                            }
+                            <td>{participant.entity.to_string()}</td>
+                            <td>{participant.outcome.to_string()}</td>
+                            <td>{participant.status.clone()}</td>
+                            <td>{format!("{} ms", participant.elapsed_ms)}</td>
+                        </tr>
+                    }
+                })
+                .collect(),
+        }
+    });
+
     html! {
         <body>
             <header>
@@ -116,19 +451,74 @@ pub fn dashboard() -> Html {
                 <span style="margin-left:1rem; color:var{--muted}">
                     {"Exercise ID: "} <strong>{"001"}</strong>
                 </span>
+                <span style="margin-left:1rem; color:var{--muted}">
+                    {"Exercise State: "} <strong>{exercise_state}</strong>
+                </span>
+                <span style="margin-left:1rem; color:var{--muted}">
+                    {"Simulation Address: "}
+                    <strong>
+                        {simulation_address.map_or_else(|| "invalid".to_string(), |a| a.to_string())}
+                    </strong>
+                </span>
             </header>
             <main>
                 <section class="panel wide">
                     <h2>{"Global Controls"}</h2>
+                    <TargetSelector on_change={on_target.clone()} value={Some(*target)} />
+                    <ScheduleInput on_change={on_schedule} />
                     <div class="controls">
-                        <button class="primary" onclick={send_command("initialize")}>{"Initialize"}</button>
-                        <button class="success" onclick={send_command("startup")}>{"Operate"}</button>
-                        <button class="warning" onclick={send_command("standby")}>{"Pause"}</button>
-                        <button class="danger" onclick={send_command("terminate")}>{"Shutdown"}</button>
-                        <button onclick={send_command("reset")}>{"Restart"}</button>
+                        <button class="primary" disabled={blocked("initialize")}
+                            onclick={send_command("initialize")}>{"Initialize"}</button>
+                        <button class="success" disabled={blocked("startup")}
+                            onclick={send_command("startup")}>{"Operate"}</button>
+                        <button class="warning" disabled={blocked("standby")}
+                            onclick={send_command("standby")}>{"Pause"}</button>
+                        <button class="danger" disabled={blocked("terminate")}
+                            onclick={send_command("terminate")}>{"Shutdown"}</button>
+                        <button disabled={blocked("reset")}
+                            onclick={send_command("reset")}>{"Restart"}</button>
+                        <button class="warning" disabled={blocked("stop_freeze")}
+                            onclick={open_stop_freeze}>{"Stop/Freeze…"}</button>
                     </div>
+                    if *stop_freeze_open {
+                        <StopFreezeDialog on_send={send_stop_freeze} on_cancel={close_stop_freeze} />
+                    }
+                    if !acknowledgements.0.is_empty() {
+                        <table class="data-table">
+                            <thead>
+                                <tr>
+                                    <th>{"Target"}</th>
+                                    <th>{"Command"}</th>
+                                    <th>{"Request"}</th>
+                                    <th>{"Participant"}</th>
+                                    <th>{"Outcome"}</th>
+                                    <th>{"Status"}</th>
+                                    <th>{"Latency"}</th>
+                                </tr>
+                            </thead>
+                            <tbody>{for acknowledgement_rows}</tbody>
+                        </table>
+                    }
                 </section>
 
+                <Roster on_notify={append_notification.clone()} on_select={on_target} />
+                <EntityTable
+                    on_notify={append_notification.clone()}
+                    display={*display}
+                    {on_display}
+                />
+                <TacticalMap on_notify={append_notification.clone()} display={*display} />
+                <TrafficStatistics on_notify={append_notification.clone()} />
+                <Recording on_notify={append_notification.clone()} />
+                <Replay on_notify={append_notification.clone()} />
+                <Inspector on_notify={append_notification.clone()} display={*display} />
+                <ActionRequest
+                    on_notify={append_notification.clone()}
+                    on_acknowledgement={record_acknowledgement.clone()}
+                />
+                <EntityManagement on_notify={append_notification.clone()} />
+                <DataExchange on_notify={append_notification.clone()} />
+
                 <section class="panel wide">
                     <h2>{"Notifications"}</h2>
                     <ul class="alerts">
@@ -0,0 +1,202 @@
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use crate::dashboard::{Notification, NotificationLevel};
+use crate::datum_editor::{DatumEditor, DatumKind, DatumRow, build_datums, build_ids};
+use crate::forms::{format_hex, on_u16_input};
+use crate::models::{DataReply, DatumIds, DatumSet, EntityIdentifier};
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "core"], catch)]
+    async fn invoke(cmd: &str, args: JsValue) -> Result<JsValue, JsValue>;
+}
+
+#[derive(Serialize)]
+struct SetDataArgs {
+    entity: EntityIdentifier,
+    datums: DatumSet,
+}
+
+#[derive(Serialize)]
+struct QueryDataArgs {
+    entity: EntityIdentifier,
+    datums: DatumIds,
+}
+
+/// Renders the printable ASCII characters of a variable datum, replacing the rest with dots.
+fn format_ascii(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|b| {
+            if b.is_ascii_graphic() || *b == b' ' {
+                char::from(*b)
+            } else {
+                '.'
+            }
+        })
+        .collect()
+}
+
+#[derive(Properties, PartialEq)]
+pub struct DataExchangeProps {
+    pub on_notify: Callback<Notification>,
+}
+
+#[function_component(DataExchange)]
+pub fn data_exchange(props: &DataExchangeProps) -> Html {
+    let site_id = use_state(|| 1u16);
+    let application_id = use_state(|| 1u16);
+    let entity_id = use_state(|| 1u16);
+    let rows = use_state(|| vec![DatumRow::new(DatumKind::Fixed)]);
+    let reply = use_state(|| None::<DataReply>);
+
+    let entity = EntityIdentifier {
+        site_id: *site_id,
+        application_id: *application_id,
+        entity_id: *entity_id,
+    };
+
+    let send = {
+        let notify = props.on_notify.clone();
+        let rows = rows.clone();
+        let reply = reply.clone();
+
+        Callback::from(move |cmd: &'static str| {
+            let notify = notify.clone();
+            let reply = reply.clone();
+
+            let args = if cmd == "set_data" {
+                build_datums(&rows).map(|datums| {
+                    serde_wasm_bindgen::to_value(&SetDataArgs { entity, datums })
+                        .unwrap_or_default()
+                })
+            } else {
+                build_ids(&rows).map(|datums| {
+                    serde_wasm_bindgen::to_value(&QueryDataArgs { entity, datums })
+                        .unwrap_or_default()
+                })
+            };
+
+            let args = match args {
+                Ok(args) => args,
+                Err(message) => {
+                    notify.emit(Notification {
+                        message,
+                        level: NotificationLevel::Error,
+                    });
+                    return;
+                }
+            };
+
+            spawn_local(async move {
+                let result = invoke(cmd, args)
+                    .await
+                    .map_err(|err| err.as_string().unwrap_or_else(|| "unknown error".into()))
+                    .and_then(|value| {
+                        serde_wasm_bindgen::from_value::<Option<DataReply>>(value)
+                            .map_err(|e| format!("Unable to read Data PDU response: {e}"))
+                    });
+
+                match result {
+                    Ok(None) => notify.emit(Notification {
+                        message: format!(
+                            "Unacknowledged {} request sent to {entity}",
+                            if cmd == "set_data" {
+                                "Set Data"
+                            } else {
+                                "Data Query"
+                            }
+                        ),
+                        level: NotificationLevel::Info,
+                    }),
+                    Ok(Some(data)) => {
+                        notify.emit(Notification {
+                            message: format!(
+                                "Data received from {} for request {}",
+                                data.originating_entity, data.request_id
+                            ),
+                            level: NotificationLevel::Info,
+                        });
+                        reply.set(Some(data));
+                    }
+                    Err(message) => notify.emit(Notification {
+                        message,
+                        level: NotificationLevel::Error,
+                    }),
+                }
+            });
+        })
+    };
+
+    let reply_view = reply.as_ref().map_or_else(
+        || html! {},
+        |data| {
+            let fixed = data.datums.fixed.iter().map(|datum| {
+                html! {
+                    <tr>
+                        <td>{datum.datum_id}</td>
+                        <td>{"Fixed"}</td>
+                        <td>{format!("{} (0x{:08X})", datum.value, datum.value)}</td>
+                    </tr>
+                }
+            });
+            let variable = data.datums.variable.iter().map(|datum| {
+                html! {
+                    <tr>
+                        <td>{datum.datum_id}</td>
+                        <td>{format!("Variable ({} bits)", datum.length_bits)}</td>
+                        <td>
+                            <code>{format_hex(&datum.value)}</code>
+                            {" "}
+                            <span class="label">{format_ascii(&datum.value)}</span>
+                        </td>
+                    </tr>
+                }
+            });
+
+            html! {
+                <table class="data-table">
+                    <thead>
+                        <tr><th>{"Datum ID"}</th><th>{"Kind"}</th><th>{"Value"}</th></tr>
+                    </thead>
+                    <tbody>
+                        {for fixed}
+                        {for variable}
+                    </tbody>
+                </table>
+            }
+        },
+    );
+
+    html! {
+        <section class="panel">
+            <h2>{"Data Exchange"}</h2>
+            <div class="form-grid">
+                <label>
+                    {"Site"}
+                    <input type="number" min="1" max="65534" value={site_id.to_string()}
+                        oninput={on_u16_input(site_id.clone())} />
+                </label>
+                <label>
+                    {"Application"}
+                    <input type="number" min="1" max="65534" value={application_id.to_string()}
+                        oninput={on_u16_input(application_id.clone())} />
+                </label>
+                <label>
+                    {"Entity"}
+                    <input type="number" min="0" max="65535" value={entity_id.to_string()}
+                        oninput={on_u16_input(entity_id.clone())} />
+                </label>
+            </div>
+            <DatumEditor rows={rows.clone()} />
+            <div class="controls">
+                <button class="primary" onclick={send.reform(|_| "query_data")}>{"Query"}</button>
+                <button class="warning" onclick={send.reform(|_| "set_data")}>{"Set"}</button>
+            </div>
+            {reply_view}
+        </section>
+    }
+}
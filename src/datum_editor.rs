@@ -0,0 +1,139 @@
+//! An editable list of fixed and variable datum records shared by the SIMAN forms.
+
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use crate::forms::{parse_hex, parse_u32};
+use crate::models::{DatumIds, DatumSet, FixedDatum, VariableDatum};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum DatumKind {
+    Fixed,
+    Variable,
+}
+
+/// A datum record as typed into the editor, before validation.
+#[derive(Clone, PartialEq, Eq)]
+pub struct DatumRow {
+    kind: DatumKind,
+    id: String,
+    value: String,
+}
+
+impl DatumRow {
+    pub const fn new(kind: DatumKind) -> Self {
+        Self {
+            kind,
+            id: String::new(),
+            value: String::new(),
+        }
+    }
+}
+
+pub fn build_datums(rows: &[DatumRow]) -> Result<DatumSet, String> {
+    let mut datums = DatumSet::default();
+
+    for row in rows {
+        let datum_id = parse_u32(&row.id)?;
+        match row.kind {
+            DatumKind::Fixed => datums.fixed.push(FixedDatum {
+                datum_id,
+                value: parse_u32(&row.value)?,
+            }),
+            DatumKind::Variable => {
+                let value = parse_hex(&row.value)?;
+                let length_bits = u32::try_from(value.len() * 8)
+                    .map_err(|_| format!("variable datum {datum_id} is too long"))?;
+                datums.variable.push(VariableDatum {
+                    datum_id,
+                    length_bits,
+                    value,
+                });
+            }
+        }
+    }
+
+    Ok(datums)
+}
+
+pub fn build_ids(rows: &[DatumRow]) -> Result<DatumIds, String> {
+    let mut ids = DatumIds::default();
+
+    for row in rows {
+        let datum_id = parse_u32(&row.id)?;
+        match row.kind {
+            DatumKind::Fixed => ids.fixed.push(datum_id),
+            DatumKind::Variable => ids.variable.push(datum_id),
+        }
+    }
+
+    Ok(ids)
+}
+
+#[derive(Properties, PartialEq)]
+pub struct DatumEditorProps {
+    pub rows: UseStateHandle<Vec<DatumRow>>,
+}
+
+#[function_component(DatumEditor)]
+pub fn datum_editor(props: &DatumEditorProps) -> Html {
+    let rows = &props.rows;
+
+    let add_row = |kind: DatumKind| {
+        let rows = rows.clone();
+        Callback::from(move |_| {
+            let mut updated = (*rows).clone();
+            updated.push(DatumRow::new(kind));
+            rows.set(updated);
+        })
+    };
+
+    let row_views = rows.iter().enumerate().map(|(index, row)| {
+        let edit = |set: fn(&mut DatumRow, String)| {
+            let rows = rows.clone();
+            Callback::from(move |e: InputEvent| {
+                let input: HtmlInputElement = e.target_unchecked_into();
+                let mut updated = (*rows).clone();
+                if let Some(row) = updated.get_mut(index) {
+                    set(row, input.value());
+                }
+                rows.set(updated);
+            })
+        };
+
+        let remove = {
+            let rows = rows.clone();
+            Callback::from(move |_| {
+                let mut updated = (*rows).clone();
+                updated.remove(index);
+                rows.set(updated);
+            })
+        };
+
+        let (kind, placeholder) = match row.kind {
+            DatumKind::Fixed => ("Fixed", "value"),
+            DatumKind::Variable => ("Variable", "hex bytes"),
+        };
+
+        html! {
+            <div class="datum-row">
+                <span class="label">{kind}</span>
+                <input placeholder="datum ID" value={row.id.clone()}
+                    oninput={edit(|row, value| row.id = value)} />
+                <input placeholder={placeholder} value={row.value.clone()}
+                    oninput={edit(|row, value| row.value = value)} />
+                <button onclick={remove}>{"Remove"}</button>
+            </div>
+        }
+    });
+
+    html! {
+        <>
+            {for row_views}
+            <div class="controls">
+                <button onclick={add_row(DatumKind::Fixed)}>{"+ Fixed"}</button>
+                <button onclick={add_row(DatumKind::Variable)}>{"+ Variable"}</button>
+            </div>
+        </>
+    }
+}
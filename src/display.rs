@@ -0,0 +1,111 @@
+//! Formats entity locations and orientations according to the user's display preferences.
+
+use web_sys::HtmlSelectElement;
+use yew::prelude::*;
+
+use crate::models::{CoordinateFormat, DisplayConfig, EntityRecord, Field, Geodetic};
+
+/// Formats the entity's dead-reckoned location.
+pub fn format_location(entity: &EntityRecord, format: CoordinateFormat) -> String {
+    let geodetic = &entity.placement.geodetic;
+
+    match format {
+        CoordinateFormat::Geocentric => {
+            let location = entity.extrapolated_location;
+            format!("{:.0}, {:.0}, {:.0} m", location.x, location.y, location.z)
+        }
+        CoordinateFormat::Geodetic => format_geodetic(geodetic),
+        // MGRS does not reach the poles
+        CoordinateFormat::Mgrs => entity
+            .placement
+            .mgrs
+            .clone()
+            .unwrap_or_else(|| format_geodetic(geodetic)),
+    }
+}
+
+/// Formats a world coordinate decoded by the inspector, whose value is already geocentric.
+pub fn format_world_coordinate(field: &Field, format: CoordinateFormat) -> String {
+    match (format, &field.location, &field.mgrs) {
+        (CoordinateFormat::Mgrs, _, Some(mgrs)) => mgrs.clone(),
+        (CoordinateFormat::Geodetic | CoordinateFormat::Mgrs, Some(geodetic), _) => {
+            format_geodetic(geodetic)
+        }
+        _ => field.value.clone(),
+    }
+}
+
+fn format_geodetic(geodetic: &Geodetic) -> String {
+    format!(
+        "{}, {} {:.0} m",
+        hemisphere(geodetic.latitude_deg, 'N', 'S'),
+        hemisphere(geodetic.longitude_deg, 'E', 'W'),
+        geodetic.altitude_m
+    )
+}
+
+/// Formats the entity's dead-reckoned orientation, as raw Euler angles in geocentric mode
+/// and as a local attitude otherwise.
+pub fn format_orientation(entity: &EntityRecord, format: CoordinateFormat) -> String {
+    if format == CoordinateFormat::Geocentric {
+        let euler = entity.extrapolated_orientation;
+        return format!(
+            "ψ {:.1}° θ {:.1}° φ {:.1}°",
+            euler.psi.to_degrees(),
+            euler.theta.to_degrees(),
+            euler.phi.to_degrees()
+        );
+    }
+
+    let attitude = entity.placement.attitude;
+    format!(
+        "{:03.0}° pitch {:.1}° roll {:.1}°",
+        // Rounded first so that headings just short of north read 000 rather than 360
+        attitude.heading.round().rem_euclid(360.0),
+        attitude.pitch,
+        attitude.roll
+    )
+}
+
+fn hemisphere(degrees: f64, positive: char, negative: char) -> String {
+    let letter = if degrees < 0.0 { negative } else { positive };
+    format!("{:.5}°{letter}", degrees.abs())
+}
+
+#[derive(Properties, PartialEq)]
+pub struct CoordinateFormatSelectProps {
+    pub value: DisplayConfig,
+    pub on_change: Callback<DisplayConfig>,
+}
+
+/// Selects the coordinate format of the user's display preferences.
+#[function_component(CoordinateFormatSelect)]
+pub fn coordinate_format_select(props: &CoordinateFormatSelectProps) -> Html {
+    let onchange = {
+        let on_change = props.on_change.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            if let Some((coordinates, _)) = CoordinateFormat::ALL
+                .iter()
+                .find(|(_, label)| *label == select.value())
+            {
+                on_change.emit(DisplayConfig {
+                    coordinates: *coordinates,
+                });
+            }
+        })
+    };
+
+    html! {
+        <label>
+            {"Coordinates"}
+            <select {onchange}>
+                {for CoordinateFormat::ALL.iter().map(|(format, label)| html! {
+                    <option value={*label} selected={*format == props.value.coordinates}>
+                        {*label}
+                    </option>
+                })}
+            </select>
+        </label>
+    }
+}
@@ -0,0 +1,161 @@
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use crate::dashboard::{Notification, NotificationLevel};
+use crate::forms::on_u16_input;
+use crate::models::{ApplicationAddress, EntityIdentifier};
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "core"], catch)]
+    async fn invoke(cmd: &str, args: JsValue) -> Result<JsValue, JsValue>;
+}
+
+#[derive(Serialize)]
+struct CreateEntityArgs {
+    application: ApplicationAddress,
+}
+
+#[derive(Serialize)]
+struct RemoveEntityArgs {
+    entity: EntityIdentifier,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct EntityManagementProps {
+    pub on_notify: Callback<Notification>,
+}
+
+#[function_component(EntityManagement)]
+pub fn entity_management(props: &EntityManagementProps) -> Html {
+    let site_id = use_state(|| 1u16);
+    let application_id = use_state(|| 1u16);
+    let entity_id = use_state(|| 1u16);
+
+    let on_create = {
+        let notify = props.on_notify.clone();
+        let site_id = site_id.clone();
+        let application_id = application_id.clone();
+
+        Callback::from(move |_| {
+            let notify = notify.clone();
+            let application = ApplicationAddress {
+                site_id: *site_id,
+                application_id: *application_id,
+            };
+
+            spawn_local(async move {
+                let args = CreateEntityArgs { application };
+
+                let result = invoke(
+                    "create_entity",
+                    serde_wasm_bindgen::to_value(&args).unwrap_or_default(),
+                )
+                .await;
+
+                let notification = match result
+                    .map(serde_wasm_bindgen::from_value::<Option<EntityIdentifier>>)
+                {
+                    Ok(Ok(Some(entity))) => Notification {
+                        message: format!("Application {application} created entity {entity}"),
+                        level: NotificationLevel::Info,
+                    },
+                    Ok(Ok(None)) => Notification {
+                        message: format!(
+                            "Unacknowledged Create Entity request sent to application {application}"
+                        ),
+                        level: NotificationLevel::Info,
+                    },
+                    Ok(Err(err)) => Notification {
+                        message: format!("Unable to read Create Entity response: {err}"),
+                        level: NotificationLevel::Error,
+                    },
+                    Err(err) => Notification {
+                        message: err.as_string().unwrap_or_else(|| "unknown error".into()),
+                        level: NotificationLevel::Error,
+                    },
+                };
+
+                notify.emit(notification);
+            });
+        })
+    };
+
+    let on_remove = {
+        let notify = props.on_notify.clone();
+        let site_id = site_id.clone();
+        let application_id = application_id.clone();
+        let entity_id = entity_id.clone();
+
+        Callback::from(move |_| {
+            let notify = notify.clone();
+            let entity = EntityIdentifier {
+                site_id: *site_id,
+                application_id: *application_id,
+                entity_id: *entity_id,
+            };
+
+            spawn_local(async move {
+                let args = RemoveEntityArgs { entity };
+
+                let result = invoke(
+                    "remove_entity",
+                    serde_wasm_bindgen::to_value(&args).unwrap_or_default(),
+                )
+                .await;
+
+                let notification = match result
+                    .map(serde_wasm_bindgen::from_value::<Option<EntityIdentifier>>)
+                {
+                    Ok(Ok(Some(removed))) => Notification {
+                        message: format!("Entity {removed} removed"),
+                        level: NotificationLevel::Info,
+                    },
+                    Ok(Ok(None)) => Notification {
+                        message: format!("Unacknowledged Remove Entity request sent to {entity}"),
+                        level: NotificationLevel::Info,
+                    },
+                    Ok(Err(err)) => Notification {
+                        message: format!("Unable to read Remove Entity response: {err}"),
+                        level: NotificationLevel::Error,
+                    },
+                    Err(err) => Notification {
+                        message: err.as_string().unwrap_or_else(|| "unknown error".into()),
+                        level: NotificationLevel::Error,
+                    },
+                };
+
+                notify.emit(notification);
+            });
+        })
+    };
+
+    html! {
+        <section class="panel">
+            <h2>{"Entity Management"}</h2>
+            <div class="form-grid">
+                <label>
+                    {"Site"}
+                    <input type="number" min="1" max="65534" value={site_id.to_string()}
+                        oninput={on_u16_input(site_id.clone())} />
+                </label>
+                <label>
+                    {"Application"}
+                    <input type="number" min="1" max="65534" value={application_id.to_string()}
+                        oninput={on_u16_input(application_id.clone())} />
+                </label>
+                <label>
+                    {"Entity"}
+                    <input type="number" min="1" max="65534" value={entity_id.to_string()}
+                        oninput={on_u16_input(entity_id.clone())} />
+                </label>
+            </div>
+            <div class="controls">
+                <button class="success" onclick={on_create}>{"Create"}</button>
+                <button class="danger" onclick={on_remove}>{"Remove"}</button>
+            </div>
+        </section>
+    }
+}
@@ -0,0 +1,217 @@
+use std::cmp::Ordering;
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use crate::dashboard::{Notification, NotificationLevel};
+use crate::display::{CoordinateFormatSelect, format_location, format_orientation};
+use crate::events::subscribe;
+use crate::forms::format_time;
+use crate::models::{DisplayConfig, EntityRecord};
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "core"], catch)]
+    async fn invoke(cmd: &str, args: JsValue) -> Result<JsValue, JsValue>;
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SortKey {
+    Id,
+    Marking,
+    Force,
+    Type,
+    Speed,
+    DeadReckoning,
+    LastUpdate,
+}
+
+fn speed(entity: &EntityRecord) -> f64 {
+    entity.kinematics.velocity.magnitude()
+}
+
+/// Position error of the last dead-reckoning check, or -1 before the first one
+fn drift(entity: &EntityRecord) -> f64 {
+    entity.dead_reckoning_error.map_or(-1.0, |e| e.position_m)
+}
+
+impl SortKey {
+    fn compare(self, a: &EntityRecord, b: &EntityRecord) -> Ordering {
+        let id = |e: &EntityRecord| (e.id.site_id, e.id.application_id, e.id.entity_id);
+
+        match self {
+            Self::Id => id(a).cmp(&id(b)),
+            Self::Marking => a.marking.cmp(&b.marking),
+            Self::Force => a.force.cmp(&b.force),
+            Self::Type => a.entity_type.to_string().cmp(&b.entity_type.to_string()),
+            Self::Speed => speed(a).total_cmp(&speed(b)),
+            Self::DeadReckoning => drift(a).total_cmp(&drift(b)),
+            Self::LastUpdate => a.last_update_ms.cmp(&b.last_update_ms),
+        }
+    }
+}
+
+/// Returns whether any displayed text field of the entity contains `filter`.
+fn matches(entity: &EntityRecord, filter: &str) -> bool {
+    [
+        entity.id.to_string(),
+        entity.marking.to_lowercase(),
+        entity.force.to_lowercase(),
+        entity.entity_type.to_string(),
+    ]
+    .iter()
+    .any(|field| field.contains(filter))
+}
+
+#[derive(Properties, PartialEq)]
+pub struct EntityTableProps {
+    pub on_notify: Callback<Notification>,
+    pub display: DisplayConfig,
+    pub on_display: Callback<DisplayConfig>,
+}
+
+/// Shows the latest Entity State of every active entity, sortable by column and filtered
+/// by ID, marking, force or entity type.
+///
+/// Locations are dead-reckoned by the backend, and entities whose last extrapolation
+/// drifted beyond the scenario's thresholds are highlighted. Entities that have timed out
+/// are greyed until the backend removes them.
+#[function_component(EntityTable)]
+pub fn entity_table(props: &EntityTableProps) -> Html {
+    let entities = use_state(Vec::<EntityRecord>::new);
+    let filter = use_state(String::new);
+    let sort = use_state(|| (SortKey::Id, true));
+
+    {
+        let notify = props.on_notify.clone();
+        let entities = entities.clone();
+
+        use_effect_with((), move |()| {
+            {
+                let entities = entities.clone();
+                subscribe(
+                    "entities",
+                    Callback::from(move |latest: Vec<EntityRecord>| entities.set(latest)),
+                );
+            }
+
+            spawn_local(async move {
+                let result = invoke("get_entities", JsValue::NULL)
+                    .await
+                    .map_err(|err| err.as_string().unwrap_or_else(|| "unknown error".into()))
+                    .and_then(|value| {
+                        serde_wasm_bindgen::from_value::<Vec<EntityRecord>>(value)
+                            .map_err(|e| format!("Unable to read entities: {e}"))
+                    });
+
+                match result {
+                    Ok(latest) => entities.set(latest),
+                    Err(message) => notify.emit(Notification {
+                        message,
+                        level: NotificationLevel::Error,
+                    }),
+                }
+            });
+        });
+    }
+
+    let on_filter = {
+        let filter = filter.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            filter.set(input.value());
+        })
+    };
+
+    // Clicking the sorted column again reverses the order
+    let header = |key: SortKey, label: &str| {
+        let sort = sort.clone();
+        let (current, ascending) = *sort;
+        let arrow = match (current == key, ascending) {
+            (false, _) => "",
+            (true, true) => " ▲",
+            (true, false) => " ▼",
+        };
+        let onclick = Callback::from(move |_| {
+            sort.set((key, current != key || !ascending));
+        });
+
+        html! { <th class="sortable" {onclick}>{format!("{label}{arrow}")}</th> }
+    };
+
+    let needle = filter.trim().to_lowercase();
+    let mut shown: Vec<&EntityRecord> = entities.iter().filter(|e| matches(e, &needle)).collect();
+
+    let (key, ascending) = *sort;
+    shown.sort_by(|a, b| {
+        let order = key.compare(a, b);
+        if ascending { order } else { order.reverse() }
+    });
+
+    let coordinates = props.display.coordinates;
+
+    let rows = shown.iter().map(|entity| {
+        let (warning, error) = entity.dead_reckoning_error.map_or_else(
+            || (None, "-".to_string()),
+            |e| {
+                (
+                    e.exceeded.then_some("warning"),
+                    format!("{:.2} m, {:.1}°", e.position_m, e.orientation_deg),
+                )
+            },
+        );
+
+        html! {
+            <tr class={classes!(warning, entity.stale.then_some("stale"))}>
+                <td>{entity.id.to_string()}</td>
+                <td>{entity.marking.clone()}</td>
+                <td>{entity.force.clone()}</td>
+                <td>{entity.entity_type.to_string()}</td>
+                <td>{format_location(entity, coordinates)}</td>
+                <td>{format_orientation(entity, coordinates)}</td>
+                <td>{format!("{:.1} m/s", speed(entity))}</td>
+                <td>{entity.kinematics.model.clone()}</td>
+                <td>{error}</td>
+                <td>{format!("0x{:08X}", entity.appearance)}</td>
+                <td>
+                    {format_time(entity.last_update_ms)}
+                    {entity.stale.then_some(" (stale)")}
+                </td>
+            </tr>
+        }
+    });
+
+    html! {
+        <section class="panel wide">
+            <h2>{format!("Entities ({} of {})", shown.len(), entities.len())}</h2>
+            <div class="form-grid">
+                <label>
+                    {"Filter"}
+                    <input type="text" placeholder="ID, marking, force or type"
+                        value={(*filter).clone()} oninput={on_filter} />
+                </label>
+                <CoordinateFormatSelect value={props.display} on_change={props.on_display.clone()} />
+            </div>
+            <table class="data-table">
+                <thead>
+                    <tr>
+                        {header(SortKey::Id, "ID")}
+                        {header(SortKey::Marking, "Marking")}
+                        {header(SortKey::Force, "Force")}
+                        {header(SortKey::Type, "Type")}
+                        <th>{"Location"}</th>
+                        <th>{"Orientation"}</th>
+                        {header(SortKey::Speed, "Speed")}
+                        <th>{"DR model"}</th>
+                        {header(SortKey::DeadReckoning, "DR error")}
+                        <th>{"Appearance"}</th>
+                        {header(SortKey::LastUpdate, "Last update")}
+                    </tr>
+                </thead>
+                <tbody>{for rows}</tbody>
+            </table>
+        </section>
+    }
+}
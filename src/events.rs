@@ -0,0 +1,41 @@
+//! Subscriptions to events emitted by the Tauri backend.
+
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+use yew::Callback;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "event"], catch)]
+    async fn listen(event: &str, handler: &Closure<dyn Fn(JsValue)>) -> Result<JsValue, JsValue>;
+}
+
+/// The envelope Tauri wraps around every event payload.
+#[derive(Deserialize)]
+struct TauriEvent<T> {
+    payload: T,
+}
+
+/// Forwards every payload of `event` to `callback` for the lifetime of the page.
+pub fn subscribe<T: DeserializeOwned + 'static>(event: &'static str, callback: Callback<T>) {
+    let handler =
+        Closure::<dyn Fn(JsValue)>::new(
+            move |value: JsValue| match serde_wasm_bindgen::from_value::<TauriEvent<T>>(value) {
+                Ok(received) => callback.emit(received.payload),
+                Err(e) => {
+                    web_sys::console::error_1(&format!("Malformed {event} event: {e}").into());
+                }
+            },
+        );
+
+    spawn_local(async move {
+        if let Err(e) = listen(event, &handler).await {
+            web_sys::console::error_1(&e);
+        }
+
+        // The dashboard listens until the window closes, so the handler is never released
+        handler.forget();
+    });
+}
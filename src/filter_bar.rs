@@ -0,0 +1,263 @@
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+use crate::dashboard::{Notification, NotificationLevel};
+use crate::models::SavedFilter;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "core"], catch)]
+    async fn invoke(cmd: &str, args: JsValue) -> Result<JsValue, JsValue>;
+}
+
+const PLACEHOLDER: &str = "type == EntityState && site == 3 && force == Opposing";
+
+#[derive(Serialize)]
+struct CheckArgs {
+    filter: String,
+}
+
+#[derive(Serialize)]
+struct SaveArgs {
+    name: String,
+    expression: String,
+}
+
+#[derive(Serialize)]
+struct DeleteArgs {
+    name: String,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct FilterBarProps {
+    pub on_notify: Callback<Notification>,
+    /// The filter in force, if any
+    pub applied: Option<String>,
+    /// Called with a checked filter, or `None` to show everything
+    pub on_apply: Callback<Option<String>>,
+}
+
+/// Calls a command and reads its result.
+// The frontend is single-threaded, so its futures never need to be sent
+#[allow(clippy::future_not_send)]
+async fn call<T: DeserializeOwned>(command: &str, args: JsValue) -> Result<T, String> {
+    invoke(command, args)
+        .await
+        .map_err(|err| err.as_string().unwrap_or_else(|| "unknown error".into()))
+        .and_then(|value| {
+            serde_wasm_bindgen::from_value::<T>(value)
+                .map_err(|e| format!("Unable to read the result of {command}: {e}"))
+        })
+}
+
+/// Fetches the filters saved with the scenario into `saved`.
+fn load(saved: UseStateHandle<Vec<SavedFilter>>, notify: Callback<Notification>) {
+    spawn_local(async move {
+        match call::<Vec<SavedFilter>>("list_filters", JsValue::NULL).await {
+            Ok(filters) => saved.set(filters),
+            Err(message) => notify.emit(Notification {
+                message,
+                level: NotificationLevel::Error,
+            }),
+        }
+    });
+}
+
+/// Edits, applies and saves a filter expression, showing why an expression is invalid
+/// next to it.
+#[function_component(FilterBar)]
+pub fn filter_bar(props: &FilterBarProps) -> Html {
+    let text = use_state(|| props.applied.clone().unwrap_or_default());
+    let name = use_state(String::new);
+    let error = use_state(|| None::<String>);
+    let saved = use_state(Vec::<SavedFilter>::new);
+
+    {
+        let saved = saved.clone();
+        let notify = props.on_notify.clone();
+        use_effect_with((), move |()| load(saved, notify));
+    }
+
+    // Follows filters applied elsewhere, such as the one kept from a previous replay
+    {
+        let text = text.clone();
+        use_effect_with(props.applied.clone(), move |applied| {
+            text.set(applied.clone().unwrap_or_default());
+        });
+    }
+
+    let apply = {
+        let on_apply = props.on_apply.clone();
+        let error = error.clone();
+        Callback::from(move |expression: String| {
+            let on_apply = on_apply.clone();
+            let error = error.clone();
+
+            if expression.trim().is_empty() {
+                error.set(None);
+                on_apply.emit(None);
+                return;
+            }
+
+            spawn_local(async move {
+                let args = serde_wasm_bindgen::to_value(&CheckArgs { filter: expression });
+                match call::<String>("check_filter", args.unwrap_or_default()).await {
+                    Ok(checked) => {
+                        error.set(None);
+                        on_apply.emit(Some(checked));
+                    }
+                    Err(message) => error.set(Some(message)),
+                }
+            });
+        })
+    };
+
+    let on_input = {
+        let text = text.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            text.set(input.value());
+        })
+    };
+
+    let on_key = {
+        let apply = apply.clone();
+        let text = text.clone();
+        Callback::from(move |e: KeyboardEvent| {
+            if e.key() == "Enter" {
+                apply.emit((*text).clone());
+            }
+        })
+    };
+
+    let on_apply = {
+        let apply = apply.clone();
+        let text = text.clone();
+        Callback::from(move |_| apply.emit((*text).clone()))
+    };
+
+    let on_clear = {
+        let apply = apply.clone();
+        let text = text.clone();
+        Callback::from(move |_| {
+            text.set(String::new());
+            apply.emit(String::new());
+        })
+    };
+
+    // Saved filters may have changed in another panel or with a new scenario
+    let on_focus = {
+        let saved = saved.clone();
+        let notify = props.on_notify.clone();
+        Callback::from(move |_| load(saved.clone(), notify.clone()))
+    };
+
+    let on_choose = {
+        let saved = saved.clone();
+        let text = text.clone();
+        let name = name.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            if let Some(filter) = saved.iter().find(|filter| filter.name == select.value()) {
+                text.set(filter.expression.clone());
+                name.set(filter.name.clone());
+                apply.emit(filter.expression.clone());
+            }
+            select.set_value("");
+        })
+    };
+
+    let on_name = {
+        let name = name.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            name.set(input.value());
+        })
+    };
+
+    let on_save = {
+        let notify = props.on_notify.clone();
+        let saved = saved.clone();
+        let error = error.clone();
+        let text = text.clone();
+        let name = name.clone();
+        Callback::from(move |_| {
+            let notify = notify.clone();
+            let saved = saved.clone();
+            let error = error.clone();
+            let args = serde_wasm_bindgen::to_value(&SaveArgs {
+                name: (*name).clone(),
+                expression: (*text).clone(),
+            });
+            spawn_local(async move {
+                match call::<Vec<SavedFilter>>("save_filter", args.unwrap_or_default()).await {
+                    Ok(filters) => {
+                        error.set(None);
+                        saved.set(filters);
+                        notify.emit(Notification {
+                            message: "Filter saved with the scenario".into(),
+                            level: NotificationLevel::Info,
+                        });
+                    }
+                    Err(message) => error.set(Some(message)),
+                }
+            });
+        })
+    };
+
+    let on_delete = {
+        let notify = props.on_notify.clone();
+        let saved = saved.clone();
+        let name = name.clone();
+        Callback::from(move |_| {
+            let notify = notify.clone();
+            let saved = saved.clone();
+            let args = serde_wasm_bindgen::to_value(&DeleteArgs {
+                name: (*name).clone(),
+            });
+            spawn_local(async move {
+                match call::<Vec<SavedFilter>>("delete_filter", args.unwrap_or_default()).await {
+                    Ok(filters) => saved.set(filters),
+                    Err(message) => notify.emit(Notification {
+                        message,
+                        level: NotificationLevel::Error,
+                    }),
+                }
+            });
+        })
+    };
+
+    let is_saved = saved.iter().any(|filter| filter.name == *name);
+
+    html! {
+        <div class="filter-bar">
+            <div class="controls">
+                <input class="filter-expression" type="text" placeholder={PLACEHOLDER}
+                    value={(*text).clone()} oninput={on_input} onkeydown={on_key} />
+                <button onclick={on_apply}>{"Apply"}</button>
+                <button disabled={props.applied.is_none()} onclick={on_clear}>{"Clear"}</button>
+            </div>
+            <div class="controls">
+                <select onfocus={on_focus} onchange={on_choose}>
+                    <option value="" selected=true>{"Saved filters…"}</option>
+                    {for saved.iter().map(|filter| html! {
+                        <option value={filter.name.clone()} title={filter.expression.clone()}>
+                            {filter.name.clone()}
+                        </option>
+                    })}
+                </select>
+                <input type="text" placeholder="Name" value={(*name).clone()} oninput={on_name} />
+                <button disabled={name.trim().is_empty() || text.trim().is_empty()}
+                    onclick={on_save}>{"Save"}</button>
+                <button disabled={!is_saved} onclick={on_delete}>{"Delete"}</button>
+            </div>
+            if let Some(message) = &*error {
+                <p class="alert error">{message.clone()}</p>
+            }
+        </div>
+    }
+}
@@ -0,0 +1,62 @@
+//! Small helpers shared by the dashboard's input forms.
+
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+/// Updates `value` whenever the input holds a valid `u16`.
+pub fn on_u16_input(value: UseStateHandle<u16>) -> Callback<InputEvent> {
+    Callback::from(move |e: InputEvent| {
+        let input: HtmlInputElement = e.target_unchecked_into();
+        if let Ok(parsed) = input.value().parse::<u16>() {
+            value.set(parsed);
+        }
+    })
+}
+
+/// Parses a string of hexadecimal byte pairs, ignoring whitespace.
+pub fn parse_hex(text: &str) -> Result<Vec<u8>, String> {
+    let digits: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+
+    if !digits.is_ascii() {
+        return Err(format!("\"{text}\" contains non-hex characters"));
+    }
+
+    if !digits.len().is_multiple_of(2) {
+        return Err(format!("\"{text}\" has an odd number of hex digits"));
+    }
+
+    (0..digits.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&digits[i..i + 2], 16)
+                .map_err(|_| format!("\"{}\" is not a hex byte", &digits[i..i + 2]))
+        })
+        .collect()
+}
+
+/// Formats bytes as space-separated hexadecimal pairs.
+pub fn format_hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|b| format!("{b:02X}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Formats Unix milliseconds as a local time of day.
+// Unix milliseconds stay far below the 2^53 limit of exact f64 integers
+#[allow(clippy::cast_precision_loss)]
+pub fn format_time(unix_ms: u64) -> String {
+    js_sys::Date::new(&(unix_ms as f64).into())
+        .to_locale_time_string("en-GB")
+        .into()
+}
+
+/// Parses an unsigned integer given either in decimal or with a `0x` prefix in hexadecimal.
+pub fn parse_u32(text: &str) -> Result<u32, String> {
+    let text = text.trim();
+    let parsed = text
+        .strip_prefix("0x")
+        .map_or_else(|| text.parse::<u32>(), |hex| u32::from_str_radix(hex, 16));
+    parsed.map_err(|_| format!("\"{text}\" is not a valid 32-bit value"))
+}
@@ -0,0 +1,516 @@
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use crate::dashboard::{Notification, NotificationLevel};
+use crate::display::format_world_coordinate;
+use crate::events::subscribe;
+use crate::filter_bar::FilterBar;
+use crate::forms::format_time;
+use crate::models::{CapturePage, DisplayConfig, Field, Inspection, PduRow};
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "core"], catch)]
+    async fn invoke(cmd: &str, args: JsValue) -> Result<JsValue, JsValue>;
+}
+
+/// Records listed per page of a capture
+const PAGE_ROWS: usize = 100;
+
+/// Bytes shown on each line of the hex dump
+const DUMP_WIDTH: usize = 16;
+
+#[derive(Serialize)]
+struct LoggedArgs {
+    id: u64,
+}
+
+#[derive(Serialize)]
+struct RecordArgs {
+    record: usize,
+}
+
+#[derive(Serialize)]
+struct OpenArgs {
+    path: Option<String>,
+    filter: Option<String>,
+}
+
+#[derive(Serialize)]
+struct FilterArgs {
+    filter: Option<String>,
+}
+
+#[derive(Serialize)]
+struct PageArgs {
+    start: usize,
+    count: usize,
+}
+
+/// Where the listed PDUs come from.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Source {
+    Live,
+    Capture,
+}
+
+/// Bytes of the PDU covered by the field under the pointer.
+type Span = Option<(usize, usize)>;
+
+#[derive(Properties, PartialEq)]
+pub struct InspectorProps {
+    pub on_notify: Callback<Notification>,
+    pub display: DisplayConfig,
+}
+
+/// Calls a command and reads its result.
+// The frontend is single-threaded, so its futures never need to be sent
+#[allow(clippy::future_not_send)]
+async fn call<T: DeserializeOwned>(command: &str, args: JsValue) -> Result<T, String> {
+    invoke(command, args)
+        .await
+        .map_err(|err| err.as_string().unwrap_or_else(|| "unknown error".into()))
+        .and_then(|value| {
+            serde_wasm_bindgen::from_value::<T>(value)
+                .map_err(|e| format!("Unable to read the result of {command}: {e}"))
+        })
+}
+
+fn field_tree(field: &Field, props: &InspectorProps, on_hover: &Callback<Span>) -> Html {
+    let span = field.offset.map(|offset| (offset, field.length));
+    let onmouseenter = {
+        let on_hover = on_hover.clone();
+        Callback::from(move |_| on_hover.emit(span))
+    };
+    let onmouseleave = {
+        let on_hover = on_hover.clone();
+        Callback::from(move |_| on_hover.emit(None))
+    };
+
+    let value = if field.location.is_some() {
+        format_world_coordinate(field, props.display.coordinates)
+    } else {
+        field.value.clone()
+    };
+    let position = span.map_or_else(
+        || "not in PDU".into(),
+        |(offset, length)| format!("{offset}+{length}"),
+    );
+
+    let label = html! {
+        <span class="field" {onmouseenter} {onmouseleave}>
+            <span class="field-name">{field.name.clone()}</span>
+            <span class="field-value">{value}</span>
+            <span class="field-position">{position}</span>
+        </span>
+    };
+
+    if field.children.is_empty() {
+        html! { <li>{label}</li> }
+    } else {
+        html! {
+            <li>
+                <details>
+                    <summary>{label}</summary>
+                    <ul class="field-tree">
+                        {for field.children.iter().map(|child| field_tree(child, props, on_hover))}
+                    </ul>
+                </details>
+            </li>
+        }
+    }
+}
+
+/// Lays the PDU out 16 bytes to a line, marking the bytes of the hovered field and those
+/// that could not be decoded.
+fn hex_dump(inspection: &Inspection, highlight: Span) -> Html {
+    let class = |index: usize| {
+        let highlighted =
+            highlight.is_some_and(|(offset, length)| (offset..offset + length).contains(&index));
+        classes!(
+            highlighted.then_some("highlight"),
+            (index >= inspection.decoded_len).then_some("undecoded"),
+        )
+    };
+
+    let lines = inspection
+        .data
+        .chunks(DUMP_WIDTH)
+        .enumerate()
+        .map(|(line, bytes)| {
+            let start = line * DUMP_WIDTH;
+            let hex = bytes.iter().enumerate().map(|(i, byte)| {
+                html! { <span class={class(start + i)}>{format!("{byte:02X} ")}</span> }
+            });
+            let text = bytes.iter().enumerate().map(|(i, byte)| {
+                let c = if byte.is_ascii_graphic() {
+                    char::from(*byte)
+                } else {
+                    '.'
+                };
+                html! { <span class={class(start + i)}>{c}</span> }
+            });
+            // Pads short last lines so their text lines up
+            let padding = "   ".repeat(DUMP_WIDTH - bytes.len());
+
+            html! {
+                <div>
+                    <span class="hex-offset">{format!("{start:04X}  ")}</span>
+                    {for hex}
+                    {padding}
+                    {" "}
+                    {for text}
+                </div>
+            }
+        });
+
+    html! { <div class="hex-dump">{for lines}</div> }
+}
+
+/// Shows a received or sent PDU, live or from a capture, as a tree of decoded fields next
+/// to its bytes, highlighting the bytes of the field under the pointer.
+#[function_component(Inspector)]
+pub fn inspector(props: &InspectorProps) -> Html {
+    let source = use_state(|| Source::Live);
+    let live = use_state(Vec::<PduRow>::new);
+    // The live rows shown while the log is paused
+    let frozen = use_state(|| None::<Vec<PduRow>>);
+    let live_filter = use_state(|| None::<String>);
+    let capture = use_state(|| None::<CapturePage>);
+    // Applied to the next capture opened while none is
+    let capture_filter = use_state(|| None::<String>);
+    let selected = use_state(|| None::<(Source, u64)>);
+    let inspection = use_state(|| None::<Inspection>);
+    let highlight = use_state(|| None::<(usize, usize)>);
+
+    {
+        let notify = props.on_notify.clone();
+        let live = live.clone();
+
+        use_effect_with((), move |()| {
+            {
+                let live = live.clone();
+                subscribe(
+                    "pdu-log",
+                    Callback::from(move |latest: Vec<PduRow>| live.set(latest)),
+                );
+            }
+
+            spawn_local(async move {
+                match call::<Vec<PduRow>>("get_pdu_log", JsValue::NULL).await {
+                    Ok(latest) => live.set(latest),
+                    Err(message) => notify.emit(Notification {
+                        message,
+                        level: NotificationLevel::Error,
+                    }),
+                }
+            });
+        });
+    }
+
+    let on_source = |chosen: Source| {
+        let source = source.clone();
+        Callback::from(move |_| source.set(chosen))
+    };
+
+    let on_pause = {
+        let live = live.clone();
+        let frozen = frozen.clone();
+        Callback::from(move |_| {
+            frozen.set(if frozen.is_some() {
+                None
+            } else {
+                Some((*live).clone())
+            });
+        })
+    };
+
+    let on_live_filter = {
+        let notify = props.on_notify.clone();
+        let live = live.clone();
+        let frozen = frozen.clone();
+        let live_filter = live_filter.clone();
+        Callback::from(move |filter: Option<String>| {
+            let notify = notify.clone();
+            let live = live.clone();
+            let frozen = frozen.clone();
+            let live_filter = live_filter.clone();
+            spawn_local(async move {
+                let args = serde_wasm_bindgen::to_value(&FilterArgs {
+                    filter: filter.clone(),
+                });
+                match call::<Vec<PduRow>>("set_live_filter", args.unwrap_or_default()).await {
+                    Ok(rows) => {
+                        // A paused log shows the newly filtered rows, but stays paused
+                        if frozen.is_some() {
+                            frozen.set(Some(rows.clone()));
+                        }
+                        live.set(rows);
+                        live_filter.set(filter);
+                    }
+                    Err(message) => notify.emit(Notification {
+                        message,
+                        level: NotificationLevel::Error,
+                    }),
+                }
+            });
+        })
+    };
+
+    let on_capture_filter = {
+        let notify = props.on_notify.clone();
+        let capture = capture.clone();
+        let capture_filter = capture_filter.clone();
+        Callback::from(move |filter: Option<String>| {
+            if capture.is_none() {
+                capture_filter.set(filter);
+                return;
+            }
+
+            let notify = notify.clone();
+            let capture = capture.clone();
+            let capture_filter = capture_filter.clone();
+            spawn_local(async move {
+                let args = serde_wasm_bindgen::to_value(&FilterArgs {
+                    filter: filter.clone(),
+                });
+                match call::<CapturePage>("set_capture_filter", args.unwrap_or_default()).await {
+                    Ok(page) => {
+                        capture.set(Some(page));
+                        capture_filter.set(filter);
+                    }
+                    Err(message) => notify.emit(Notification {
+                        message,
+                        level: NotificationLevel::Error,
+                    }),
+                }
+            });
+        })
+    };
+
+    let on_open = {
+        let notify = props.on_notify.clone();
+        let capture = capture.clone();
+        let capture_filter = capture_filter.clone();
+        Callback::from(move |_| {
+            let notify = notify.clone();
+            let capture = capture.clone();
+            let filter = (*capture_filter).clone();
+            spawn_local(async move {
+                let args = serde_wasm_bindgen::to_value(&OpenArgs { path: None, filter });
+                match call::<CapturePage>("open_capture_browser", args.unwrap_or_default()).await {
+                    Ok(page) => capture.set(Some(page)),
+                    Err(message) => notify.emit(Notification {
+                        message,
+                        level: NotificationLevel::Error,
+                    }),
+                }
+            });
+        })
+    };
+
+    let on_close = {
+        let notify = props.on_notify.clone();
+        let capture = capture.clone();
+        Callback::from(move |_| {
+            let notify = notify.clone();
+            let capture = capture.clone();
+            spawn_local(async move {
+                match invoke("close_capture_browser", JsValue::NULL).await {
+                    Ok(_) => capture.set(None),
+                    Err(err) => notify.emit(Notification {
+                        message: err.as_string().unwrap_or_else(|| "unknown error".into()),
+                        level: NotificationLevel::Error,
+                    }),
+                }
+            });
+        })
+    };
+
+    let on_page = |start: usize| {
+        let notify = props.on_notify.clone();
+        let capture = capture.clone();
+        Callback::from(move |_| {
+            let notify = notify.clone();
+            let capture = capture.clone();
+            spawn_local(async move {
+                let args = serde_wasm_bindgen::to_value(&PageArgs {
+                    start,
+                    count: PAGE_ROWS,
+                });
+                match call::<CapturePage>("get_capture_rows", args.unwrap_or_default()).await {
+                    Ok(page) => capture.set(Some(page)),
+                    Err(message) => notify.emit(Notification {
+                        message,
+                        level: NotificationLevel::Error,
+                    }),
+                }
+            });
+        })
+    };
+
+    let on_inspect = {
+        let notify = props.on_notify.clone();
+        let selected = selected.clone();
+        let inspection = inspection.clone();
+        let highlight = highlight.clone();
+        Callback::from(move |(from, id): (Source, u64)| {
+            let notify = notify.clone();
+            let selected = selected.clone();
+            let inspection = inspection.clone();
+            highlight.set(None);
+            spawn_local(async move {
+                let result = match from {
+                    Source::Live => {
+                        let args = serde_wasm_bindgen::to_value(&LoggedArgs { id });
+                        call::<Inspection>("inspect_logged_pdu", args.unwrap_or_default()).await
+                    }
+                    Source::Capture => match usize::try_from(id) {
+                        Ok(record) => {
+                            let args = serde_wasm_bindgen::to_value(&RecordArgs { record });
+                            call::<Inspection>("inspect_capture_pdu", args.unwrap_or_default())
+                                .await
+                        }
+                        Err(_) => Err(format!("Record {id} is out of range")),
+                    },
+                };
+
+                match result {
+                    Ok(decoded) => {
+                        selected.set(Some((from, id)));
+                        inspection.set(Some(decoded));
+                    }
+                    Err(message) => notify.emit(Notification {
+                        message,
+                        level: NotificationLevel::Error,
+                    }),
+                }
+            });
+        })
+    };
+
+    let on_hover = {
+        let highlight = highlight.clone();
+        Callback::from(move |span: Span| highlight.set(span))
+    };
+
+    let rows: &[PduRow] = match *source {
+        Source::Live => frozen.as_deref().unwrap_or(&live),
+        Source::Capture => capture.as_ref().map_or(&[], |page| &page.rows),
+    };
+    let row_html = rows.iter().map(|row| {
+        let key = (*source, row.id);
+        let onclick = {
+            let on_inspect = on_inspect.clone();
+            Callback::from(move |_| on_inspect.emit(key))
+        };
+        let time = match *source {
+            Source::Live => format_time(row.time_ms),
+            Source::Capture => format!("#{} {}", row.id + 1, format_time(row.time_ms)),
+        };
+
+        html! {
+            <tr class={classes!("selectable", (*selected == Some(key)).then_some("selected"))} {onclick}>
+                <td>{time}</td>
+                <td>{row.direction.clone()}</td>
+                <td>{row.source.clone()}</td>
+                <td>{row.pdu_type.clone()}</td>
+                <td>{row.exercise_id}</td>
+                <td>{row.length}</td>
+            </tr>
+        }
+    });
+
+    html! {
+        <section class="panel wide">
+            <h2>{"PDU Inspector"}</h2>
+            <div class="controls">
+                <button class={classes!((*source == Source::Live).then_some("primary"))}
+                    onclick={on_source(Source::Live)}>{"Live"}</button>
+                <button class={classes!((*source == Source::Capture).then_some("primary"))}
+                    onclick={on_source(Source::Capture)}>{"Capture"}</button>
+                if *source == Source::Live {
+                    <button onclick={on_pause}>
+                        {if frozen.is_some() { "Resume" } else { "Pause" }}
+                    </button>
+                } else {
+                    <button onclick={on_open}>{"Open capture"}</button>
+                    if let Some(page) = &*capture {
+                        <button disabled={page.start == 0}
+                            onclick={on_page(page.start.saturating_sub(PAGE_ROWS))}>{"Previous"}</button>
+                        <button disabled={page.start + page.rows.len() >= page.matching}
+                            onclick={on_page(page.start + PAGE_ROWS)}>{"Next"}</button>
+                        <button onclick={on_close}>{"Close"}</button>
+                        <span class="label">
+                            {format!(
+                                "{}: rows {} to {} of {}",
+                                page.file,
+                                (page.start + 1).min(page.matching),
+                                page.start + page.rows.len(),
+                                page.matching
+                            )}
+                            if page.filter.is_some() {
+                                {format!(" matching of {} records", page.records)}
+                            }
+                        </span>
+                    }
+                }
+            </div>
+            if *source == Source::Live {
+                <FilterBar on_notify={props.on_notify.clone()}
+                    applied={(*live_filter).clone()} on_apply={on_live_filter} />
+            } else {
+                <FilterBar on_notify={props.on_notify.clone()}
+                    applied={capture.as_ref().map_or_else(|| (*capture_filter).clone(), |page| page.filter.clone())}
+                    on_apply={on_capture_filter} />
+            }
+            <div class="inspector-rows">
+                <table class="data-table">
+                    <thead>
+                        <tr>
+                            <th>{"Time"}</th>
+                            <th>{"Direction"}</th>
+                            <th>{"Source"}</th>
+                            <th>{"PDU type"}</th>
+                            <th>{"Exercise"}</th>
+                            <th>{"Bytes"}</th>
+                        </tr>
+                    </thead>
+                    <tbody>{for row_html}</tbody>
+                </table>
+            </div>
+            if let Some(inspection) = &*inspection {
+                <h3>{format!("{} — {} bytes", inspection.pdu_type, inspection.data.len())}</h3>
+                if !inspection.warnings.is_empty() {
+                    <ul class="alerts">
+                        {for inspection.warnings.iter().map(|warning| html! {
+                            <li class="alert warning">{warning.clone()}</li>
+                        })}
+                    </ul>
+                }
+                <div class="inspector-layout">
+                    <ul class="field-tree">
+                        {for inspection.fields.iter().map(|field| field_tree(field, props, &on_hover))}
+                    </ul>
+                    <div>
+                        {hex_dump(inspection, *highlight)}
+                        if inspection.decoded_len < inspection.data.len() {
+                            <p class="label">
+                                <span class="undecoded">
+                                    {format!(
+                                        "{} trailing bytes",
+                                        inspection.data.len() - inspection.decoded_len
+                                    )}
+                                </span>
+                                {" could not be decoded"}
+                            </p>
+                        }
+                    </div>
+                </div>
+            }
+        </section>
+    }
+}
@@ -1,5 +1,24 @@
+mod action_request;
 mod app;
 mod dashboard;
+mod data_exchange;
+mod datum_editor;
+mod display;
+mod entity_management;
+mod entity_table;
+mod events;
+mod filter_bar;
+mod forms;
+mod inspector;
+mod models;
+mod recording;
+mod replay;
+mod roster;
+mod schedule;
+mod stop_freeze;
+mod tactical_map;
+mod target_selector;
+mod traffic;
 mod welcome;
 
 use app::App;
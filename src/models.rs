@@ -0,0 +1,516 @@
+//! Frontend mirrors of the data types exchanged with the Tauri backend.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::forms::format_hex;
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApplicationAddress {
+    pub site_id: u16,
+    pub application_id: u16,
+}
+
+// Field names follow the DIS entity identifier record and must match the backend
+#[allow(clippy::struct_field_names)]
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntityIdentifier {
+    pub site_id: u16,
+    pub application_id: u16,
+    pub entity_id: u16,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum CommandTarget {
+    All,
+    Site { site_id: u16 },
+    Application(ApplicationAddress),
+    Entity(EntityIdentifier),
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Schedule {
+    pub real_world_ms: Option<u64>,
+    pub simulation_ms: Option<u64>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StopReason {
+    Other,
+    Recess,
+    Termination,
+    SystemFailure,
+    SecurityViolation,
+    EntityReconstitution,
+    StopForReset,
+    StopForRestart,
+    AbortTrainingReturnToTacticalOperations,
+}
+
+impl StopReason {
+    pub const ALL: [(Self, &'static str); 9] = [
+        (Self::Other, "Other"),
+        (Self::Recess, "Recess"),
+        (Self::Termination, "Termination"),
+        (Self::SystemFailure, "System failure"),
+        (Self::SecurityViolation, "Security violation"),
+        (Self::EntityReconstitution, "Entity reconstitution"),
+        (Self::StopForReset, "Stop for reset"),
+        (Self::StopForRestart, "Stop for restart"),
+        (
+            Self::AbortTrainingReturnToTacticalOperations,
+            "Abort training, return to tactical operations",
+        ),
+    ];
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct StopFreezeOptions {
+    pub reason: StopReason,
+    pub run_simulation_clock: bool,
+    pub transmit_updates: bool,
+    pub process_updates: bool,
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SimulationState {
+    #[default]
+    Uninitialized,
+    Initialized,
+    Running,
+    Paused,
+    Stopped,
+}
+
+#[derive(Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct SimulationStatus {
+    pub state: SimulationState,
+    /// Names of the Global Controls commands valid from this state
+    pub commands: Vec<String>,
+}
+
+#[derive(Clone, PartialEq, Eq, Deserialize)]
+pub struct Acknowledgement {
+    pub target: CommandTarget,
+    pub command: String,
+    pub request_id: u32,
+    pub participants: Option<Vec<ParticipantReply>>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    Complied,
+    Refused,
+    Pending,
+    Partial,
+    Unknown,
+}
+
+#[derive(Clone, PartialEq, Eq, Deserialize)]
+pub struct ParticipantReply {
+    pub entity: EntityIdentifier,
+    pub outcome: Outcome,
+    pub status: String,
+    pub datums: DatumSet,
+    pub elapsed_ms: u64,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportedState {
+    Running,
+    Frozen,
+}
+
+#[derive(Clone, PartialEq, Eq, Deserialize)]
+pub struct Participant {
+    pub application: ApplicationAddress,
+    pub first_seen_ms: u64,
+    pub last_seen_ms: u64,
+    pub pdu_count: u64,
+    pub siman_state: Option<ReportedState>,
+    pub silent: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct EntityTypeCode {
+    pub kind: u8,
+    pub domain: u8,
+    pub country: u16,
+    pub category: u8,
+    pub subcategory: u8,
+    pub specific: u8,
+    pub extra: u8,
+}
+
+#[derive(Clone, Copy, PartialEq, Deserialize)]
+pub struct Vector {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Vector {
+    pub fn magnitude(&self) -> f64 {
+        self.z
+            .mul_add(self.z, self.x.mul_add(self.x, self.y * self.y))
+            .sqrt()
+    }
+}
+
+#[derive(Clone, PartialEq, Deserialize)]
+pub struct Kinematics {
+    /// Dead-reckoning model, such as FPW or RVB
+    pub model: String,
+    pub location: Vector,
+    pub velocity: Vector,
+}
+
+#[derive(Clone, Copy, PartialEq, Deserialize)]
+pub struct DeadReckoningError {
+    pub position_m: f64,
+    pub orientation_deg: f64,
+    pub exceeded: bool,
+}
+
+/// DIS Euler angles in radians.
+#[derive(Clone, Copy, PartialEq, Deserialize)]
+pub struct Orientation {
+    pub psi: f64,
+    pub theta: f64,
+    pub phi: f64,
+}
+
+#[derive(Clone, Copy, PartialEq, Deserialize)]
+pub struct Geodetic {
+    pub latitude_deg: f64,
+    pub longitude_deg: f64,
+    pub altitude_m: f64,
+}
+
+#[derive(Clone, Copy, PartialEq, Deserialize)]
+pub struct Attitude {
+    pub heading: f64,
+    pub pitch: f64,
+    pub roll: f64,
+}
+
+#[derive(Clone, PartialEq, Deserialize)]
+pub struct Placement {
+    pub geodetic: Geodetic,
+    pub mgrs: Option<String>,
+    pub attitude: Attitude,
+}
+
+#[derive(Clone, PartialEq, Deserialize)]
+pub struct EntityRecord {
+    pub id: EntityIdentifier,
+    pub force: String,
+    pub entity_type: EntityTypeCode,
+    pub marking: String,
+    pub kinematics: Kinematics,
+    pub extrapolated_location: Vector,
+    pub extrapolated_orientation: Orientation,
+    pub placement: Placement,
+    pub dead_reckoning_error: Option<DeadReckoningError>,
+    pub appearance: u32,
+    pub capabilities: u32,
+    pub last_update_ms: u64,
+    pub stale: bool,
+}
+
+#[derive(Clone, PartialEq, Deserialize)]
+pub struct MapPath {
+    pub closed: bool,
+    /// Longitude and latitude pairs in degrees
+    pub points: Vec<[f64; 2]>,
+}
+
+#[derive(Clone, PartialEq, Deserialize)]
+pub struct MapBackground {
+    pub name: String,
+    pub paths: Vec<MapPath>,
+    pub points: Vec<[f64; 2]>,
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CoordinateFormat {
+    Geocentric,
+    #[default]
+    Geodetic,
+    Mgrs,
+}
+
+impl CoordinateFormat {
+    pub const ALL: [(Self, &'static str); 3] = [
+        (Self::Geodetic, "Latitude/longitude"),
+        (Self::Mgrs, "MGRS"),
+        (Self::Geocentric, "Geocentric (DIS)"),
+    ];
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DisplayConfig {
+    pub coordinates: CoordinateFormat,
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Deserialize)]
+pub struct TrafficCounter {
+    pub pdus: u64,
+    pub bytes: u64,
+    pub pdu_rate: f64,
+    pub byte_rate: f64,
+    pub average_pdu_rate: f64,
+    pub average_byte_rate: f64,
+}
+
+#[derive(Clone, PartialEq, Deserialize)]
+pub struct TrafficRow {
+    /// "received" or "sent"
+    pub direction: String,
+    pub key: String,
+    pub counter: TrafficCounter,
+}
+
+#[derive(Clone, Default, PartialEq, Deserialize)]
+pub struct TrafficSnapshot {
+    pub since_ms: u64,
+    pub totals: Vec<TrafficRow>,
+    pub pdu_types: Vec<TrafficRow>,
+    pub applications: Vec<TrafficRow>,
+    pub exercises: Vec<TrafficRow>,
+    pub chatty_entities: Vec<TrafficRow>,
+}
+
+#[derive(Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct RecordingStatus {
+    pub recording: bool,
+    pub file: Option<String>,
+    pub files: u32,
+    pub pdus: u64,
+    pub bytes: u64,
+    pub missed: u64,
+    pub started_ms: u64,
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FixedDatum {
+    pub datum_id: u32,
+    pub value: u32,
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VariableDatum {
+    pub datum_id: u32,
+    pub length_bits: u32,
+    pub value: Vec<u8>,
+}
+
+#[derive(Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DatumSet {
+    pub fixed: Vec<FixedDatum>,
+    pub variable: Vec<VariableDatum>,
+}
+
+#[derive(Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DatumIds {
+    pub fixed: Vec<u32>,
+    pub variable: Vec<u32>,
+}
+
+#[derive(Clone, PartialEq, Eq, Deserialize)]
+pub struct DataReply {
+    pub originating_entity: EntityIdentifier,
+    pub request_id: u32,
+    pub datums: DatumSet,
+}
+
+impl fmt::Display for ApplicationAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.site_id, self.application_id)
+    }
+}
+
+impl fmt::Display for EntityIdentifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}",
+            self.site_id, self.application_id, self.entity_id
+        )
+    }
+}
+
+impl fmt::Display for CommandTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::All => write!(f, "all simulations"),
+            Self::Site { site_id } => write!(f, "site {site_id}"),
+            Self::Application(address) => write!(f, "application {address}"),
+            Self::Entity(id) => write!(f, "entity {id}"),
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Complied => write!(f, "Complied"),
+            Self::Refused => write!(f, "Refused"),
+            Self::Pending => write!(f, "Pending"),
+            Self::Partial => write!(f, "Partial"),
+            Self::Unknown => write!(f, "Unknown"),
+        }
+    }
+}
+
+/// Formats an entity type as the conventional kind:domain:country:category:subcategory:specific:extra.
+impl fmt::Display for EntityTypeCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}:{}:{}:{}:{}",
+            self.kind,
+            self.domain,
+            self.country,
+            self.category,
+            self.subcategory,
+            self.specific,
+            self.extra
+        )
+    }
+}
+
+impl fmt::Display for ReportedState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Running => write!(f, "Running"),
+            Self::Frozen => write!(f, "Frozen"),
+        }
+    }
+}
+
+/// Lists the datum records as `id=value` pairs, with variable datums in hex.
+impl fmt::Display for DatumSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fixed = self
+            .fixed
+            .iter()
+            .map(|datum| format!("{}={}", datum.datum_id, datum.value));
+        let variable = self
+            .variable
+            .iter()
+            .map(|datum| format!("{}={}", datum.datum_id, format_hex(&datum.value)));
+
+        write!(
+            f,
+            "{}",
+            fixed.chain(variable).collect::<Vec<_>>().join(", ")
+        )
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ReplayOptions {
+    pub speed: f64,
+    pub looping: bool,
+    pub exercise_id: Option<u8>,
+    pub restamp: bool,
+}
+
+impl Default for ReplayOptions {
+    fn default() -> Self {
+        Self {
+            speed: 1.0,
+            looping: false,
+            exercise_id: None,
+            restamp: false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReplayState {
+    #[default]
+    Closed,
+    Paused,
+    Playing,
+    Finished,
+}
+
+#[derive(Clone, Default, PartialEq, Deserialize)]
+pub struct ReplayStatus {
+    pub state: ReplayState,
+    pub file: Option<String>,
+    pub records: usize,
+    pub position: usize,
+    pub position_ms: u64,
+    pub duration_ms: u64,
+    pub sent: u64,
+    pub options: ReplayOptions,
+    pub filter: Option<String>,
+}
+
+#[derive(Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct ImportSummary {
+    pub file: String,
+    pub packets: u64,
+    pub datagrams: u64,
+    pub truncated: u64,
+}
+
+#[derive(Clone, PartialEq, Eq, Deserialize)]
+pub struct PduRow {
+    pub id: u64,
+    /// "received" or "sent"
+    pub direction: String,
+    pub time_ms: u64,
+    pub source: String,
+    pub pdu_type: String,
+    pub exercise_id: u8,
+    pub length: usize,
+}
+
+#[derive(Clone, PartialEq, Eq, Deserialize)]
+pub struct CapturePage {
+    pub file: String,
+    pub records: usize,
+    /// Records matching the filter, or every record if there is none
+    pub matching: usize,
+    pub start: usize,
+    pub rows: Vec<PduRow>,
+    pub filter: Option<String>,
+}
+
+#[derive(Clone, PartialEq, Eq, Deserialize)]
+pub struct SavedFilter {
+    pub name: String,
+    pub expression: String,
+}
+
+#[derive(Clone, PartialEq, Deserialize)]
+pub struct Field {
+    pub name: String,
+    pub value: String,
+    pub offset: Option<usize>,
+    pub length: usize,
+    pub location: Option<Geodetic>,
+    pub mgrs: Option<String>,
+    pub children: Vec<Self>,
+}
+
+#[derive(Clone, PartialEq, Deserialize)]
+pub struct Inspection {
+    pub pdu_type: String,
+    pub data: Vec<u8>,
+    pub fields: Vec<Field>,
+    pub decoded_len: usize,
+    pub warnings: Vec<String>,
+}
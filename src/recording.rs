@@ -0,0 +1,124 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use crate::dashboard::{Notification, NotificationLevel};
+use crate::events::subscribe;
+use crate::forms::format_time;
+use crate::models::RecordingStatus;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "core"], catch)]
+    async fn invoke(cmd: &str, args: JsValue) -> Result<JsValue, JsValue>;
+}
+
+#[derive(Properties, PartialEq)]
+pub struct RecordingProps {
+    pub on_notify: Callback<Notification>,
+}
+
+/// Starts and stops recording the exercise traffic to capture files and shows how much has
+/// been written.
+#[function_component(Recording)]
+pub fn recording(props: &RecordingProps) -> Html {
+    let status = use_state(RecordingStatus::default);
+
+    {
+        let notify = props.on_notify.clone();
+        let status = status.clone();
+
+        use_effect_with((), move |()| {
+            {
+                let status = status.clone();
+                subscribe(
+                    "recording",
+                    Callback::from(move |latest: RecordingStatus| status.set(latest)),
+                );
+            }
+
+            spawn_local(async move {
+                let result = invoke("get_recording_status", JsValue::NULL)
+                    .await
+                    .map_err(|err| err.as_string().unwrap_or_else(|| "unknown error".into()))
+                    .and_then(|value| {
+                        serde_wasm_bindgen::from_value::<RecordingStatus>(value)
+                            .map_err(|e| format!("Unable to read recording status: {e}"))
+                    });
+
+                match result {
+                    Ok(latest) => status.set(latest),
+                    Err(message) => notify.emit(Notification {
+                        message,
+                        level: NotificationLevel::Error,
+                    }),
+                }
+            });
+        });
+    }
+
+    let on_toggle = {
+        let notify = props.on_notify.clone();
+        let status = status.clone();
+        let command = if status.recording {
+            "stop_recording"
+        } else {
+            "start_recording"
+        };
+
+        Callback::from(move |_| {
+            let notify = notify.clone();
+            let status = status.clone();
+            spawn_local(async move {
+                let result = invoke(command, JsValue::NULL)
+                    .await
+                    .map_err(|err| err.as_string().unwrap_or_else(|| "unknown error".into()))
+                    .and_then(|value| {
+                        serde_wasm_bindgen::from_value::<RecordingStatus>(value)
+                            .map_err(|e| format!("Unable to read recording status: {e}"))
+                    });
+
+                match result {
+                    Ok(latest) => status.set(latest),
+                    Err(message) => notify.emit(Notification {
+                        message,
+                        level: NotificationLevel::Error,
+                    }),
+                }
+            });
+        })
+    };
+
+    let (label, class) = if status.recording {
+        ("Stop recording", "danger")
+    } else {
+        ("Start recording", "")
+    };
+
+    html! {
+        <section class="panel">
+            <h2>{"Recording"}</h2>
+            <div class="controls">
+                <button class={class} onclick={on_toggle}>{label}</button>
+            </div>
+            if let Some(file) = &status.file {
+                <dl class="recording-status">
+                    <dt>{if status.recording { "Recording to" } else { "Last file" }}</dt>
+                    <dd>{file.clone()}</dd>
+                    <dt>{"Started"}</dt>
+                    <dd>{format_time(status.started_ms)}</dd>
+                    <dt>{"Files"}</dt>
+                    <dd>{status.files}</dd>
+                    <dt>{"PDUs"}</dt>
+                    <dd>{status.pdus}</dd>
+                    <dt>{"Bytes"}</dt>
+                    <dd>{status.bytes}</dd>
+                    if status.missed > 0 {
+                        <dt>{"Missed"}</dt>
+                        <dd class="warning">{status.missed}</dd>
+                    }
+                </dl>
+            }
+        </section>
+    }
+}
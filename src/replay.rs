@@ -0,0 +1,386 @@
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use crate::dashboard::{Notification, NotificationLevel};
+use crate::events::subscribe;
+use crate::filter_bar::FilterBar;
+use crate::models::{ImportSummary, ReplayOptions, ReplayState, ReplayStatus};
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "core"], catch)]
+    async fn invoke(cmd: &str, args: JsValue) -> Result<JsValue, JsValue>;
+}
+
+const MIN_SPEED: f64 = 0.1;
+const MAX_SPEED: f64 = 10.0;
+
+#[derive(Serialize)]
+struct OpenArgs {
+    path: Option<String>,
+}
+
+#[derive(Serialize)]
+struct SeekArgs {
+    position: u64,
+}
+
+#[derive(Serialize)]
+struct OptionsArgs {
+    options: ReplayOptions,
+}
+
+#[derive(Serialize)]
+struct FilterArgs {
+    filter: Option<String>,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct ReplayProps {
+    pub on_notify: Callback<Notification>,
+}
+
+/// Formats an offset into the capture as minutes, seconds and tenths.
+fn format_offset(ms: u64) -> String {
+    format!("{}:{:02}.{}", ms / 60_000, ms / 1000 % 60, ms / 100 % 10)
+}
+
+/// Opens a capture for replay, asking for the file unless a `path` is given.
+// The frontend is single-threaded, so its futures never need to be sent
+#[allow(clippy::future_not_send)]
+async fn open(path: Option<String>) -> Result<ReplayStatus, String> {
+    let args = serde_wasm_bindgen::to_value(&OpenArgs { path }).unwrap_or_default();
+
+    invoke("open_replay", args)
+        .await
+        .map_err(|err| err.as_string().unwrap_or_else(|| "unknown error".into()))
+        .and_then(|value| {
+            serde_wasm_bindgen::from_value::<ReplayStatus>(value)
+                .map_err(|e| format!("Unable to read replay status: {e}"))
+        })
+}
+
+/// Runs a replay command, reporting any failure. The outcome arrives as a status event.
+fn run(notify: Callback<Notification>, command: &'static str, args: JsValue) {
+    spawn_local(async move {
+        if let Err(err) = invoke(command, args).await {
+            notify.emit(Notification {
+                message: err.as_string().unwrap_or_else(|| "unknown error".into()),
+                level: NotificationLevel::Error,
+            });
+        }
+    });
+}
+
+/// Plays a capture file back onto the exercise network with its original timing, for
+/// rehearsing against a recorded exercise.
+#[function_component(Replay)]
+pub fn replay(props: &ReplayProps) -> Html {
+    let status = use_state(ReplayStatus::default);
+
+    {
+        let notify = props.on_notify.clone();
+        let status = status.clone();
+
+        use_effect_with((), move |()| {
+            {
+                let status = status.clone();
+                subscribe(
+                    "replay",
+                    Callback::from(move |latest: ReplayStatus| status.set(latest)),
+                );
+            }
+
+            spawn_local(async move {
+                let result = invoke("get_replay_status", JsValue::NULL)
+                    .await
+                    .map_err(|err| err.as_string().unwrap_or_else(|| "unknown error".into()))
+                    .and_then(|value| {
+                        serde_wasm_bindgen::from_value::<ReplayStatus>(value)
+                            .map_err(|e| format!("Unable to read replay status: {e}"))
+                    });
+
+                match result {
+                    Ok(latest) => status.set(latest),
+                    Err(message) => notify.emit(Notification {
+                        message,
+                        level: NotificationLevel::Error,
+                    }),
+                }
+            });
+        });
+    }
+
+    let on_open = {
+        let notify = props.on_notify.clone();
+        let status = status.clone();
+
+        Callback::from(move |_| {
+            let notify = notify.clone();
+            let status = status.clone();
+            spawn_local(async move {
+                match open(None).await {
+                    Ok(latest) => status.set(latest),
+                    Err(message) => notify.emit(Notification {
+                        message,
+                        level: NotificationLevel::Error,
+                    }),
+                }
+            });
+        })
+    };
+
+    // Imported traffic is opened for replay straight away
+    let on_import = {
+        let notify = props.on_notify.clone();
+        let status = status.clone();
+
+        Callback::from(move |_| {
+            let notify = notify.clone();
+            let status = status.clone();
+            spawn_local(async move {
+                let result = invoke("import_capture_pcap", JsValue::NULL)
+                    .await
+                    .map_err(|err| err.as_string().unwrap_or_else(|| "unknown error".into()))
+                    .and_then(|value| {
+                        serde_wasm_bindgen::from_value::<ImportSummary>(value)
+                            .map_err(|e| format!("Unable to read import summary: {e}"))
+                    });
+
+                let summary = match result {
+                    Ok(summary) => summary,
+                    Err(message) => {
+                        notify.emit(Notification {
+                            message,
+                            level: NotificationLevel::Error,
+                        });
+                        return;
+                    }
+                };
+
+                let skipped = if summary.truncated > 0 {
+                    format!(", skipping {} cut short by the capture", summary.truncated)
+                } else {
+                    String::new()
+                };
+                notify.emit(Notification {
+                    message: format!(
+                        "Imported {} DIS datagrams from {} packets{skipped}",
+                        summary.datagrams, summary.packets
+                    ),
+                    level: if summary.truncated > 0 {
+                        NotificationLevel::Warning
+                    } else {
+                        NotificationLevel::Info
+                    },
+                });
+
+                if summary.datagrams == 0 {
+                    return;
+                }
+
+                match open(Some(summary.file)).await {
+                    Ok(latest) => status.set(latest),
+                    Err(message) => notify.emit(Notification {
+                        message,
+                        level: NotificationLevel::Error,
+                    }),
+                }
+            });
+        })
+    };
+
+    let on_export = {
+        let notify = props.on_notify.clone();
+
+        Callback::from(move |_| {
+            let notify = notify.clone();
+            spawn_local(async move {
+                let result = invoke("export_capture_pcapng", JsValue::NULL)
+                    .await
+                    .map_err(|err| err.as_string().unwrap_or_else(|| "unknown error".into()))
+                    .and_then(|value| {
+                        serde_wasm_bindgen::from_value::<usize>(value)
+                            .map_err(|e| format!("Unable to read export result: {e}"))
+                    });
+
+                let notification = match result {
+                    Ok(packets) => Notification {
+                        message: format!("Exported {packets} packets to pcapng"),
+                        level: NotificationLevel::Info,
+                    },
+                    Err(message) => Notification {
+                        message,
+                        level: NotificationLevel::Error,
+                    },
+                };
+                notify.emit(notification);
+            });
+        })
+    };
+
+    let on_command = |command: &'static str| {
+        let notify = props.on_notify.clone();
+        Callback::from(move |_| run(notify.clone(), command, JsValue::NULL))
+    };
+
+    let on_seek = {
+        let notify = props.on_notify.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            if let Ok(position) = input.value().parse::<u64>() {
+                let args = serde_wasm_bindgen::to_value(&SeekArgs { position });
+                run(notify.clone(), "seek_replay", args.unwrap_or_default());
+            }
+        })
+    };
+
+    let on_options = |change: fn(&mut ReplayOptions, &HtmlInputElement) -> bool| {
+        let notify = props.on_notify.clone();
+        let options = status.options;
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let mut options = options;
+
+            if change(&mut options, &input) {
+                let args = serde_wasm_bindgen::to_value(&OptionsArgs { options });
+                run(
+                    notify.clone(),
+                    "set_replay_options",
+                    args.unwrap_or_default(),
+                );
+            } else {
+                notify.emit(Notification {
+                    message: format!("Invalid replay option: {}", input.value()),
+                    level: NotificationLevel::Error,
+                });
+            }
+        })
+    };
+
+    let on_speed = on_options(|options, input| match input.value().parse::<f64>() {
+        Ok(speed) if (MIN_SPEED..=MAX_SPEED).contains(&speed) => {
+            options.speed = speed;
+            true
+        }
+        _ => false,
+    });
+    let on_looping = on_options(|options, input| {
+        options.looping = input.checked();
+        true
+    });
+    let on_exercise = on_options(|options, input| {
+        let value = input.value();
+        if value.trim().is_empty() {
+            options.exercise_id = None;
+            return true;
+        }
+        match value.trim().parse::<u8>() {
+            Ok(exercise_id) if exercise_id > 0 => {
+                options.exercise_id = Some(exercise_id);
+                true
+            }
+            _ => false,
+        }
+    });
+    let on_restamp = on_options(|options, input| {
+        options.restamp = input.checked();
+        true
+    });
+
+    let on_filter = {
+        let notify = props.on_notify.clone();
+        Callback::from(move |filter: Option<String>| {
+            let args = serde_wasm_bindgen::to_value(&FilterArgs { filter });
+            run(
+                notify.clone(),
+                "set_replay_filter",
+                args.unwrap_or_default(),
+            );
+        })
+    };
+
+    let is_open = status.state != ReplayState::Closed;
+    let playing = status.state == ReplayState::Playing;
+    let state = match status.state {
+        ReplayState::Closed => "Closed",
+        ReplayState::Paused => "Paused",
+        ReplayState::Playing => "Playing",
+        ReplayState::Finished => "Finished",
+    };
+    let options = status.options;
+
+    html! {
+        <section class="panel">
+            <h2>{"Replay"}</h2>
+            <div class="controls">
+                <button onclick={on_open}>{"Open capture"}</button>
+                if playing {
+                    <button onclick={on_command("pause_replay")}>{"Pause"}</button>
+                } else {
+                    <button disabled={!is_open} onclick={on_command("play_replay")}>{"Play"}</button>
+                }
+                <button disabled={!is_open} onclick={on_command("close_replay")}>{"Close"}</button>
+            </div>
+            <div class="controls">
+                <button onclick={on_import}>{"Import pcap…"}</button>
+                <button onclick={on_export}>{"Export pcapng…"}</button>
+            </div>
+            if let Some(file) = &status.file {
+                <dl class="recording-status">
+                    <dt>{"File"}</dt>
+                    <dd>{file.clone()}</dd>
+                    <dt>{"State"}</dt>
+                    <dd>{state}</dd>
+                    <dt>{"Position"}</dt>
+                    <dd>
+                        {format!(
+                            "{} / {} (record {} of {})",
+                            format_offset(status.position_ms),
+                            format_offset(status.duration_ms),
+                            status.position + 1,
+                            status.records,
+                        )}
+                    </dd>
+                    <dt>{"Sent"}</dt>
+                    <dd>{status.sent}</dd>
+                    if let Some(filter) = &status.filter {
+                        <dt>{"Filter"}</dt>
+                        <dd>{filter.clone()}</dd>
+                    }
+                </dl>
+                <input class="replay-seek" type="range" min="0"
+                    max={status.duration_ms.to_string()}
+                    value={status.position_ms.to_string()}
+                    onchange={on_seek} />
+            }
+            <div class="form-grid">
+                <label>
+                    {"Speed (0.1x to 10x)"}
+                    <input type="number" min="0.1" max="10" step="0.1"
+                        value={options.speed.to_string()} onchange={on_speed} />
+                </label>
+                <label>
+                    {"Loop"}
+                    <input type="checkbox" checked={options.looping} onchange={on_looping} />
+                </label>
+                <label>
+                    {"Rewrite exercise ID"}
+                    <input type="number" min="1" max="255" placeholder="Recorded"
+                        value={options.exercise_id.map(|id| id.to_string()).unwrap_or_default()}
+                        onchange={on_exercise} />
+                </label>
+                <label>
+                    {"Restamp with current time"}
+                    <input type="checkbox" checked={options.restamp} onchange={on_restamp} />
+                </label>
+            </div>
+            <h3>{"Records to replay"}</h3>
+            <FilterBar on_notify={props.on_notify.clone()}
+                applied={status.filter.clone()} on_apply={on_filter} />
+        </section>
+    }
+}
@@ -0,0 +1,106 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use crate::dashboard::{Notification, NotificationLevel};
+use crate::events::subscribe;
+use crate::forms::format_time;
+use crate::models::{CommandTarget, Participant};
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "core"], catch)]
+    async fn invoke(cmd: &str, args: JsValue) -> Result<JsValue, JsValue>;
+}
+
+#[derive(Properties, PartialEq)]
+pub struct RosterProps {
+    pub on_notify: Callback<Notification>,
+    pub on_select: Callback<CommandTarget>,
+}
+
+/// Lists every simulation application heard on the exercise network, each of which can be
+/// chosen as the target of the global controls.
+#[function_component(Roster)]
+pub fn roster(props: &RosterProps) -> Html {
+    let participants = use_state(Vec::<Participant>::new);
+
+    {
+        let notify = props.on_notify.clone();
+        let participants = participants.clone();
+
+        use_effect_with((), move |()| {
+            {
+                let participants = participants.clone();
+                subscribe(
+                    "roster",
+                    Callback::from(move |roster: Vec<Participant>| participants.set(roster)),
+                );
+            }
+
+            spawn_local(async move {
+                let result = invoke("get_roster", JsValue::NULL)
+                    .await
+                    .map_err(|err| err.as_string().unwrap_or_else(|| "unknown error".into()))
+                    .and_then(|value| {
+                        serde_wasm_bindgen::from_value::<Vec<Participant>>(value)
+                            .map_err(|e| format!("Unable to read roster: {e}"))
+                    });
+
+                match result {
+                    Ok(roster) => participants.set(roster),
+                    Err(message) => notify.emit(Notification {
+                        message,
+                        level: NotificationLevel::Error,
+                    }),
+                }
+            });
+        });
+    }
+
+    let rows = participants.iter().map(|participant| {
+        let application = participant.application;
+        let on_select = props
+            .on_select
+            .reform(move |_| CommandTarget::Application(application));
+
+        html! {
+            <tr class={classes!(participant.silent.then_some("stale"))}>
+                <td>{application.to_string()}</td>
+                <td>{format_time(participant.first_seen_ms)}</td>
+                <td>
+                    {format_time(participant.last_seen_ms)}
+                    {participant.silent.then_some(" (silent)")}
+                </td>
+                <td>{participant.pdu_count}</td>
+                <td>
+                    {participant.siman_state.map_or_else(|| "-".to_string(), |s| s.to_string())}
+                </td>
+                <td><button onclick={on_select}>{"Target"}</button></td>
+            </tr>
+        }
+    });
+
+    html! {
+        <section class="panel wide">
+            <h2>{"Participants"}</h2>
+            if participants.is_empty() {
+                <p>{"No simulation applications heard yet"}</p>
+            } else {
+                <table class="data-table">
+                    <thead>
+                        <tr>
+                            <th>{"Application"}</th>
+                            <th>{"First seen"}</th>
+                            <th>{"Last seen"}</th>
+                            <th>{"PDUs"}</th>
+                            <th>{"SIMAN state"}</th>
+                            <th></th>
+                        </tr>
+                    </thead>
+                    <tbody>{for rows}</tbody>
+                </table>
+            }
+        </section>
+    }
+}
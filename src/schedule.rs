@@ -0,0 +1,77 @@
+use wasm_bindgen::JsValue;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use crate::models::Schedule;
+
+/// Converts a `datetime-local` input value in the operator's time zone to Unix milliseconds.
+// The value is checked to be a finite, non-negative whole number before the cast
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn parse_local_time(value: &str) -> Option<u64> {
+    if value.is_empty() {
+        return None;
+    }
+
+    let ms = js_sys::Date::new(&JsValue::from_str(value)).get_time();
+    (ms.is_finite() && ms >= 0.0).then(|| ms.trunc() as u64)
+}
+
+#[derive(Properties, PartialEq)]
+pub struct ScheduleInputProps {
+    pub on_change: Callback<Option<Schedule>>,
+}
+
+/// Lets the operator pin Start/Resume and Stop/Freeze to an exact real-world time and,
+/// for Start/Resume, an exact simulation time.
+#[function_component(ScheduleInput)]
+pub fn schedule_input(props: &ScheduleInputProps) -> Html {
+    let enabled = use_state(|| false);
+    let real_world = use_state(String::new);
+    let simulation = use_state(String::new);
+
+    {
+        let on_change = props.on_change.clone();
+        let schedule = enabled.then(|| Schedule {
+            real_world_ms: parse_local_time(&real_world),
+            simulation_ms: parse_local_time(&simulation),
+        });
+
+        use_effect_with(schedule, move |schedule| on_change.emit(*schedule));
+    }
+
+    let on_toggle = {
+        let enabled = enabled.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            enabled.set(input.checked());
+        })
+    };
+
+    let on_time = |value: UseStateHandle<String>| {
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            value.set(input.value());
+        })
+    };
+
+    html! {
+        <div class="form-grid">
+            <label>
+                {"Schedule"}
+                <input type="checkbox" checked={*enabled} onchange={on_toggle} />
+            </label>
+            if *enabled {
+                <label>
+                    {"Real-world time"}
+                    <input type="datetime-local" step="1" value={(*real_world).clone()}
+                        oninput={on_time(real_world.clone())} />
+                </label>
+                <label>
+                    {"Simulation time (start only)"}
+                    <input type="datetime-local" step="1" value={(*simulation).clone()}
+                        oninput={on_time(simulation.clone())} />
+                </label>
+            }
+        </div>
+    }
+}
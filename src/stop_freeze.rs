@@ -0,0 +1,90 @@
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+use crate::models::{StopFreezeOptions, StopReason};
+
+#[derive(Properties, PartialEq)]
+pub struct StopFreezeDialogProps {
+    pub on_send: Callback<StopFreezeOptions>,
+    pub on_cancel: Callback<()>,
+}
+
+/// Asks the operator for the reason of a Stop/Freeze and which activities frozen
+/// simulations should keep performing.
+#[function_component(StopFreezeDialog)]
+pub fn stop_freeze_dialog(props: &StopFreezeDialogProps) -> Html {
+    let reason = use_state(|| StopReason::Recess);
+    let run_simulation_clock = use_state(|| false);
+    let transmit_updates = use_state(|| false);
+    let process_updates = use_state(|| false);
+
+    let on_reason = {
+        let reason = reason.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            if let Some((selected, _)) = StopReason::ALL
+                .iter()
+                .find(|(_, label)| *label == select.value())
+            {
+                reason.set(*selected);
+            }
+        })
+    };
+
+    let on_flag = |flag: UseStateHandle<bool>| {
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            flag.set(input.checked());
+        })
+    };
+
+    let on_send = {
+        let on_send = props.on_send.clone();
+        let options = StopFreezeOptions {
+            reason: *reason,
+            run_simulation_clock: *run_simulation_clock,
+            transmit_updates: *transmit_updates,
+            process_updates: *process_updates,
+        };
+        Callback::from(move |_| on_send.emit(options))
+    };
+
+    let on_cancel = props.on_cancel.reform(|_| ());
+
+    html! {
+        <div class="modal-backdrop">
+            <div class="modal">
+                <h1>{"Stop/Freeze"}</h1>
+                <div class="form-grid">
+                    <label>
+                        {"Reason"}
+                        <select onchange={on_reason}>
+                            {for StopReason::ALL.iter().map(|(option, label)| html! {
+                                <option value={*label} selected={*option == *reason}>{*label}</option>
+                            })}
+                        </select>
+                    </label>
+                    <label>
+                        {"Run simulation clock"}
+                        <input type="checkbox" checked={*run_simulation_clock}
+                            onchange={on_flag(run_simulation_clock.clone())} />
+                    </label>
+                    <label>
+                        {"Transmit updates"}
+                        <input type="checkbox" checked={*transmit_updates}
+                            onchange={on_flag(transmit_updates.clone())} />
+                    </label>
+                    <label>
+                        {"Process updates"}
+                        <input type="checkbox" checked={*process_updates}
+                            onchange={on_flag(process_updates.clone())} />
+                    </label>
+                </div>
+                <div class="controls">
+                    <button onclick={on_send}>{"Send"}</button>
+                    <button onclick={on_cancel}>{"Cancel"}</button>
+                </div>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,651 @@
+//! An offline 2D map of the monitored entities, drawn over a latitude/longitude grid and an
+//! optional `GeoJSON` background without fetching any tiles.
+
+use std::fmt::Write;
+use std::rc::Rc;
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{AddEventListenerOptions, Element, WheelEvent};
+use yew::prelude::*;
+
+use crate::dashboard::{Notification, NotificationLevel};
+use crate::display::{format_location, format_orientation};
+use crate::events::subscribe;
+use crate::forms::format_time;
+use crate::models::{DisplayConfig, EntityIdentifier, EntityRecord, MapBackground};
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "core"], catch)]
+    async fn invoke(cmd: &str, args: JsValue) -> Result<JsValue, JsValue>;
+}
+
+/// Size of the drawing in SVG user units, scaled to the panel width
+const WIDTH: f64 = 1000.0;
+const HEIGHT: f64 = 560.0;
+
+/// Length of the heading vector drawn from each entity
+const HEADING_LENGTH: f64 = 24.0;
+
+/// Candidate grid spacings in degrees, coarsest first
+const GRID_STEPS: [f64; 14] = [
+    30.0, 10.0, 5.0, 2.0, 1.0, 0.5, 0.2, 0.1, 0.05, 0.02, 0.01, 0.005, 0.002, 0.001,
+];
+
+/// Grid lines are at least this far apart
+const MIN_GRID_SPACING: f64 = 80.0;
+
+/// Zoom limits in units per degree of latitude, from the whole world to about 0.1 m
+const MIN_SCALE: f64 = 2.5;
+const MAX_SCALE: f64 = 1_000_000.0;
+
+/// Zoom factor of one mouse wheel step
+const ZOOM_STEP: f64 = 1.25;
+
+/// Smallest area a fitted view shows, in degrees
+const MIN_FIT_SPAN: f64 = 0.01;
+
+/// Entities further than this outside the drawing are not drawn
+const CULL_MARGIN: f64 = 50.0;
+
+/// The part of the world shown by the map.
+#[derive(Clone, Copy, PartialEq)]
+struct View {
+    latitude: f64,
+    longitude: f64,
+    /// Units per degree of latitude
+    scale: f64,
+}
+
+impl Default for View {
+    fn default() -> Self {
+        Self {
+            latitude: 0.0,
+            longitude: 0.0,
+            scale: MIN_SCALE,
+        }
+    }
+}
+
+impl View {
+    /// Units per degree of longitude, narrowing towards the poles
+    fn x_scale(&self) -> f64 {
+        self.scale * self.latitude.to_radians().cos().max(0.01)
+    }
+
+    fn project(&self, latitude: f64, longitude: f64) -> (f64, f64) {
+        (
+            (longitude - self.longitude).mul_add(self.x_scale(), WIDTH / 2.0),
+            (latitude - self.latitude).mul_add(-self.scale, HEIGHT / 2.0),
+        )
+    }
+
+    fn unproject(&self, x: f64, y: f64) -> (f64, f64) {
+        (
+            self.latitude - (y - HEIGHT / 2.0) / self.scale,
+            self.longitude + (x - WIDTH / 2.0) / self.x_scale(),
+        )
+    }
+
+    fn centred(latitude: f64, longitude: f64, scale: f64) -> Self {
+        Self {
+            latitude: latitude.clamp(-85.0, 85.0),
+            longitude: (longitude + 180.0).rem_euclid(360.0) - 180.0,
+            scale: scale.clamp(MIN_SCALE, MAX_SCALE),
+        }
+    }
+}
+
+/// A latitude/longitude box in degrees.
+#[derive(Clone, Copy, PartialEq)]
+struct Bounds {
+    south: f64,
+    west: f64,
+    north: f64,
+    east: f64,
+}
+
+impl Bounds {
+    /// Returns the box around every `(latitude, longitude)` pair, if there are any.
+    fn around(positions: impl IntoIterator<Item = (f64, f64)>) -> Option<Self> {
+        positions.into_iter().fold(None, |bounds, (lat, lon)| {
+            Some(bounds.map_or(
+                Self {
+                    south: lat,
+                    west: lon,
+                    north: lat,
+                    east: lon,
+                },
+                |b: Self| Self {
+                    south: b.south.min(lat),
+                    west: b.west.min(lon),
+                    north: b.north.max(lat),
+                    east: b.east.max(lon),
+                },
+            ))
+        })
+    }
+}
+
+enum ViewAction {
+    /// Moves the map by a distance in drawing units
+    Pan {
+        dx: f64,
+        dy: f64,
+    },
+    /// Zooms by `factor` keeping the point under the cursor still
+    Zoom {
+        x: f64,
+        y: f64,
+        factor: f64,
+    },
+    Fit(Bounds),
+}
+
+impl Reducible for View {
+    type Action = ViewAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let view = match action {
+            ViewAction::Pan { dx, dy } => Self::centred(
+                self.latitude + dy / self.scale,
+                self.longitude - dx / self.x_scale(),
+                self.scale,
+            ),
+            ViewAction::Zoom { x, y, factor } => {
+                let (latitude, longitude) = self.unproject(x, y);
+                let zoomed = Self::centred(self.latitude, self.longitude, self.scale * factor);
+
+                Self::centred(
+                    latitude + (y - HEIGHT / 2.0) / zoomed.scale,
+                    longitude - (x - WIDTH / 2.0) / zoomed.x_scale(),
+                    zoomed.scale,
+                )
+            }
+            ViewAction::Fit(bounds) => {
+                let latitude = f64::midpoint(bounds.south, bounds.north);
+                let longitude = f64::midpoint(bounds.west, bounds.east);
+                let cos = latitude.to_radians().cos().max(0.01);
+                let lat_span = (bounds.north - bounds.south).max(MIN_FIT_SPAN);
+                let lon_span = (bounds.east - bounds.west).max(MIN_FIT_SPAN);
+
+                // Leave a margin around the outermost entities
+                let scale = (HEIGHT / lat_span).min(WIDTH / (lon_span * cos)) * 0.8;
+
+                Self::centred(latitude, longitude, scale)
+            }
+        };
+
+        Rc::new(view)
+    }
+}
+
+/// Colour class of a force, grouping the numbered friendly, opposing and neutral forces.
+fn force_class(force: &str) -> &'static str {
+    if force.starts_with("Friendly") {
+        "force-friendly"
+    } else if force.starts_with("Opposing") {
+        "force-opposing"
+    } else if force.starts_with("Neutral") {
+        "force-neutral"
+    } else {
+        "force-other"
+    }
+}
+
+/// Converts a position in the page to drawing units.
+fn to_drawing(svg: &NodeRef, client_x: i32, client_y: i32) -> Option<(f64, f64)> {
+    let rect = svg.cast::<Element>()?.get_bounding_client_rect();
+
+    (rect.width() > 0.0 && rect.height() > 0.0).then(|| {
+        (
+            (f64::from(client_x) - rect.left()) * WIDTH / rect.width(),
+            (f64::from(client_y) - rect.top()) * HEIGHT / rect.height(),
+        )
+    })
+}
+
+/// Builds the SVG path data of the background in degrees, with latitude negated so that a
+/// single transform places it under the current view.
+fn background_path(background: &MapBackground) -> String {
+    let mut data = String::new();
+
+    for path in &background.paths {
+        for (i, [longitude, latitude]) in path.points.iter().enumerate() {
+            let command = if i == 0 { 'M' } else { 'L' };
+            let _ = write!(data, "{command}{longitude},{} ", -latitude);
+        }
+        if path.closed {
+            data.push('Z');
+        }
+    }
+
+    data
+}
+
+/// Formats a grid line label with just enough decimals for the spacing.
+fn grid_label(degrees: f64, step: f64, positive: char, negative: char) -> String {
+    // The spacing is at least 0.001°, so at most three decimals are needed
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let decimals = (-step.log10()).ceil().max(0.0) as usize;
+    let letter = if degrees < 0.0 { negative } else { positive };
+
+    format!("{:.decimals$}°{letter}", degrees.abs())
+}
+
+/// Draws the latitude and longitude lines crossing the view.
+fn grid(view: View) -> (f64, Html) {
+    let step = GRID_STEPS
+        .iter()
+        .copied()
+        .rfind(|step| step * view.scale >= MIN_GRID_SPACING)
+        .unwrap_or(GRID_STEPS[0]);
+
+    let (north, west) = view.unproject(0.0, 0.0);
+    let (south, east) = view.unproject(WIDTH, HEIGHT);
+
+    let lines = |from: f64, to: f64| {
+        let first = (from / step).ceil();
+        let last = (to / step).floor();
+        // Both ends are whole multiples of the spacing within a bounded view
+        #[allow(clippy::cast_possible_truncation)]
+        (first as i64..=last as i64).map(move |i| {
+            #[allow(clippy::cast_precision_loss)]
+            let degrees = i as f64 * step;
+            degrees
+        })
+    };
+
+    let parallels = lines(south.max(-90.0), north.min(90.0)).map(|latitude| {
+        let (_, y) = view.project(latitude, view.longitude);
+        html! {
+            <>
+                <line class="grid-line" x1="0" x2={WIDTH.to_string()}
+                    y1={y.to_string()} y2={y.to_string()} />
+                <text class="grid-label" x="4" y={(y - 4.0).to_string()}>
+                    {grid_label(latitude, step, 'N', 'S')}
+                </text>
+            </>
+        }
+    });
+
+    let meridians = lines(west, east).map(|longitude| {
+        let (x, _) = view.project(view.latitude, longitude);
+        let wrapped = (longitude + 180.0).rem_euclid(360.0) - 180.0;
+        html! {
+            <>
+                <line class="grid-line" y1="0" y2={HEIGHT.to_string()}
+                    x1={x.to_string()} x2={x.to_string()} />
+                <text class="grid-label" x={(x + 4.0).to_string()} y={(HEIGHT - 4.0).to_string()}>
+                    {grid_label(wrapped, step, 'E', 'W')}
+                </text>
+            </>
+        }
+    });
+
+    (step, html! { <g>{for parallels}{for meridians}</g> })
+}
+
+#[derive(Properties, PartialEq)]
+pub struct TacticalMapProps {
+    pub on_notify: Callback<Notification>,
+    pub display: DisplayConfig,
+}
+
+/// Plots every monitored entity at its dead-reckoned geodetic position, coloured by force
+/// with a vector along its heading.
+///
+/// Dragging pans the map, the mouse wheel zooms about the cursor and clicking an entity
+/// opens its details beside the map.
+#[function_component(TacticalMap)]
+pub fn tactical_map(props: &TacticalMapProps) -> Html {
+    let entities = use_state(Vec::<EntityRecord>::new);
+    let background = use_state(|| None::<Rc<MapBackground>>);
+    let view = use_reducer(View::default);
+    let fitted = use_state(|| false);
+    let selected = use_state(|| None::<EntityIdentifier>);
+    let drag = use_mut_ref(|| None::<(f64, f64)>);
+    let svg = use_node_ref();
+
+    {
+        let notify = props.on_notify.clone();
+        let entities = entities.clone();
+        let background = background.clone();
+
+        use_effect_with((), move |()| {
+            {
+                let entities = entities.clone();
+                subscribe(
+                    "entities",
+                    Callback::from(move |latest: Vec<EntityRecord>| entities.set(latest)),
+                );
+            }
+
+            spawn_local(async move {
+                let result = invoke("get_entities", JsValue::NULL)
+                    .await
+                    .map_err(|err| err.as_string().unwrap_or_else(|| "unknown error".into()))
+                    .and_then(|value| {
+                        serde_wasm_bindgen::from_value::<Vec<EntityRecord>>(value)
+                            .map_err(|e| format!("Unable to read entities: {e}"))
+                    });
+
+                match result {
+                    Ok(latest) => entities.set(latest),
+                    Err(message) => notify.emit(Notification {
+                        message,
+                        level: NotificationLevel::Error,
+                    }),
+                }
+
+                let result = invoke("get_map_background", JsValue::NULL)
+                    .await
+                    .map_err(|err| err.as_string().unwrap_or_else(|| "unknown error".into()))
+                    .and_then(|value| {
+                        serde_wasm_bindgen::from_value::<Option<MapBackground>>(value)
+                            .map_err(|e| format!("Unable to read map background: {e}"))
+                    });
+
+                match result {
+                    Ok(loaded) => background.set(loaded.map(Rc::new)),
+                    Err(message) => notify.emit(Notification {
+                        message,
+                        level: NotificationLevel::Warning,
+                    }),
+                }
+            });
+        });
+    }
+
+    let entity_bounds = Bounds::around(entities.iter().map(|e| {
+        (
+            e.placement.geodetic.latitude_deg,
+            e.placement.geodetic.longitude_deg,
+        )
+    }));
+
+    // Frame the exercise the first time entities are heard
+    {
+        let dispatcher = view.dispatcher();
+
+        use_effect_with(entity_bounds.is_some(), move |found| {
+            if *found && !*fitted {
+                if let Some(bounds) = entity_bounds {
+                    dispatcher.dispatch(ViewAction::Fit(bounds));
+                }
+                fitted.set(true);
+            }
+        });
+    }
+
+    // Registered directly so that the listener is not passive and can stop the page scrolling
+    {
+        let dispatcher = view.dispatcher();
+
+        use_effect_with(svg.clone(), move |svg| {
+            let target = svg.clone();
+            let listener = svg.cast::<Element>().map(|element| {
+                let on_wheel = Closure::<dyn Fn(WheelEvent)>::new(move |e: WheelEvent| {
+                    e.prevent_default();
+                    if let Some((x, y)) = to_drawing(&target, e.client_x(), e.client_y()) {
+                        let factor = if e.delta_y() < 0.0 {
+                            ZOOM_STEP
+                        } else {
+                            1.0 / ZOOM_STEP
+                        };
+                        dispatcher.dispatch(ViewAction::Zoom { x, y, factor });
+                    }
+                });
+
+                let options = AddEventListenerOptions::new();
+                options.set_passive(false);
+                let _ = element.add_event_listener_with_callback_and_add_event_listener_options(
+                    "wheel",
+                    on_wheel.as_ref().unchecked_ref(),
+                    &options,
+                );
+
+                (element, on_wheel)
+            });
+
+            move || {
+                if let Some((element, on_wheel)) = listener {
+                    let _ = element.remove_event_listener_with_callback(
+                        "wheel",
+                        on_wheel.as_ref().unchecked_ref(),
+                    );
+                }
+            }
+        });
+    }
+
+    let on_pointer_down = {
+        let drag = drag.clone();
+        let svg = svg.clone();
+        Callback::from(move |e: PointerEvent| {
+            *drag.borrow_mut() = to_drawing(&svg, e.client_x(), e.client_y());
+        })
+    };
+
+    let on_pointer_move = {
+        let drag = drag.clone();
+        let svg = svg.clone();
+        let dispatcher = view.dispatcher();
+        Callback::from(move |e: PointerEvent| {
+            let last = *drag.borrow();
+            if let (Some((x0, y0)), Some((x, y))) =
+                (last, to_drawing(&svg, e.client_x(), e.client_y()))
+            {
+                *drag.borrow_mut() = Some((x, y));
+                dispatcher.dispatch(ViewAction::Pan {
+                    dx: x - x0,
+                    dy: y - y0,
+                });
+            }
+        })
+    };
+
+    let on_pointer_up = { Callback::from(move |_: PointerEvent| *drag.borrow_mut() = None) };
+
+    let on_fit = {
+        let dispatcher = view.dispatcher();
+        let background = background.clone();
+        Callback::from(move |_| {
+            let bounds = entity_bounds.or_else(|| {
+                background.as_ref().and_then(|b| {
+                    Bounds::around(
+                        b.paths
+                            .iter()
+                            .flat_map(|path| path.points.iter())
+                            .chain(b.points.iter())
+                            .map(|[longitude, latitude]| (*latitude, *longitude)),
+                    )
+                })
+            });
+            if let Some(bounds) = bounds {
+                dispatcher.dispatch(ViewAction::Fit(bounds));
+            }
+        })
+    };
+
+    let on_load_background = {
+        let notify = props.on_notify.clone();
+        let background = background.clone();
+        Callback::from(move |_| {
+            let notify = notify.clone();
+            let background = background.clone();
+            spawn_local(async move {
+                let result = invoke("load_map_background", JsValue::NULL)
+                    .await
+                    .map_err(|err| err.as_string().unwrap_or_else(|| "unknown error".into()))
+                    .and_then(|value| {
+                        serde_wasm_bindgen::from_value::<MapBackground>(value)
+                            .map_err(|e| format!("Unable to read map background: {e}"))
+                    });
+
+                match result {
+                    Ok(loaded) => background.set(Some(Rc::new(loaded))),
+                    Err(message) => notify.emit(Notification {
+                        message,
+                        level: NotificationLevel::Error,
+                    }),
+                }
+            });
+        })
+    };
+
+    let on_clear_background = {
+        let notify = props.on_notify.clone();
+        let background = background.clone();
+        Callback::from(move |_| {
+            let notify = notify.clone();
+            let background = background.clone();
+            spawn_local(async move {
+                match invoke("clear_map_background", JsValue::NULL).await {
+                    Ok(_) => background.set(None),
+                    Err(err) => notify.emit(Notification {
+                        message: err.as_string().unwrap_or_else(|| "unknown error".into()),
+                        level: NotificationLevel::Error,
+                    }),
+                }
+            });
+        })
+    };
+
+    let background_data = use_memo((*background).clone(), |background| {
+        background.as_deref().map(background_path)
+    });
+
+    let current = *view;
+    let (grid_step, grid_lines) = grid(current);
+
+    // Places the background's degree coordinates under the current view
+    let background_transform = format!(
+        "translate({} {}) scale({} {})",
+        current.longitude.mul_add(-current.x_scale(), WIDTH / 2.0),
+        current.latitude.mul_add(current.scale, HEIGHT / 2.0),
+        current.x_scale(),
+        current.scale
+    );
+
+    let markers = entities.iter().filter_map(|entity| {
+        let geodetic = &entity.placement.geodetic;
+        let (x, y) = current.project(geodetic.latitude_deg, geodetic.longitude_deg);
+
+        if !(-CULL_MARGIN..=WIDTH + CULL_MARGIN).contains(&x)
+            || !(-CULL_MARGIN..=HEIGHT + CULL_MARGIN).contains(&y)
+        {
+            return None;
+        }
+
+        let (sin, cos) = entity.placement.attitude.heading.to_radians().sin_cos();
+        let id = entity.id;
+        let onclick = {
+            let selected = selected.clone();
+            Callback::from(move |e: MouseEvent| {
+                e.stop_propagation();
+                selected.set(Some(id));
+            })
+        };
+        let label = if entity.marking.is_empty() {
+            id.to_string()
+        } else {
+            entity.marking.clone()
+        };
+        let is_selected = *selected == Some(id);
+
+        Some(html! {
+            <g class={classes!(
+                "map-entity",
+                force_class(&entity.force),
+                entity.stale.then_some("stale")
+            )} {onclick}>
+                if is_selected {
+                    <circle class="map-selection" cx={x.to_string()} cy={y.to_string()} r="10" />
+                }
+                <line class="map-heading" x1={x.to_string()} y1={y.to_string()}
+                    x2={sin.mul_add(HEADING_LENGTH, x).to_string()}
+                    y2={cos.mul_add(-HEADING_LENGTH, y).to_string()} />
+                <circle cx={x.to_string()} cy={y.to_string()} r="5" />
+                <text x={(x + 8.0).to_string()} y={(y - 8.0).to_string()}>{label}</text>
+            </g>
+        })
+    });
+
+    let coordinates = props.display.coordinates;
+    let inspector = selected
+        .and_then(|id| entities.iter().find(|e| e.id == id))
+        .map(|entity| {
+            let on_close = {
+                let selected = selected.clone();
+                Callback::from(move |_| selected.set(None))
+            };
+            let error = entity.dead_reckoning_error.map_or_else(
+                || "-".to_string(),
+                |e| format!("{:.2} m, {:.1}°", e.position_m, e.orientation_deg),
+            );
+
+            html! {
+                <aside class="map-inspector">
+                    <h3>{entity.id.to_string()}</h3>
+                    <dl>
+                        <dt>{"Marking"}</dt><dd>{entity.marking.clone()}</dd>
+                        <dt>{"Force"}</dt><dd>{entity.force.clone()}</dd>
+                        <dt>{"Type"}</dt><dd>{entity.entity_type.to_string()}</dd>
+                        <dt>{"Location"}</dt><dd>{format_location(entity, coordinates)}</dd>
+                        <dt>{"Orientation"}</dt><dd>{format_orientation(entity, coordinates)}</dd>
+                        <dt>{"Speed"}</dt>
+                        <dd>{format!("{:.1} m/s", entity.kinematics.velocity.magnitude())}</dd>
+                        <dt>{"Dead reckoning"}</dt>
+                        <dd>{format!("{} ({error})", entity.kinematics.model)}</dd>
+                        <dt>{"Appearance"}</dt><dd>{format!("0x{:08X}", entity.appearance)}</dd>
+                        <dt>{"Last update"}</dt><dd>{format_time(entity.last_update_ms)}</dd>
+                    </dl>
+                    <button onclick={on_close}>{"Close"}</button>
+                </aside>
+            }
+        });
+
+    html! {
+        <section class="panel wide">
+            <h2>{"Map"}</h2>
+            <div class="controls">
+                <button onclick={on_fit}>{"Fit"}</button>
+                <button onclick={on_load_background}>{"Load background…"}</button>
+                if let Some(loaded) = background.as_ref() {
+                    <button onclick={on_clear_background}>
+                        {format!("Clear {}", loaded.name)}
+                    </button>
+                }
+                <span class="map-scale">{format!("Grid {grid_step}°")}</span>
+            </div>
+            <div class="map-layout">
+                <svg ref={svg} class="tactical-map"
+                    viewBox={format!("0 0 {WIDTH} {HEIGHT}")}
+                    onpointerdown={on_pointer_down}
+                    onpointermove={on_pointer_move}
+                    onpointerup={on_pointer_up.clone()}
+                    onpointerleave={on_pointer_up}>
+                    if let Some(data) = background_data.as_ref() {
+                        <g transform={background_transform}>
+                            <path class="map-background" d={data.clone()} />
+                        </g>
+                    }
+                    if let Some(loaded) = background.as_ref() {
+                        {for loaded.points.iter().map(|[longitude, latitude]| {
+                            let (x, y) = current.project(*latitude, *longitude);
+                            html! {
+                                <circle class="map-background-point"
+                                    cx={x.to_string()} cy={y.to_string()} r="2" />
+                            }
+                        })}
+                    }
+                    {grid_lines}
+                    {for markers}
+                </svg>
+                {inspector.unwrap_or_default()}
+            </div>
+        </section>
+    }
+}
@@ -0,0 +1,137 @@
+use web_sys::HtmlSelectElement;
+use yew::prelude::*;
+
+use crate::forms::on_u16_input;
+use crate::models::{ApplicationAddress, CommandTarget, EntityIdentifier};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum TargetKind {
+    All,
+    Site,
+    Application,
+    Entity,
+}
+
+impl TargetKind {
+    fn parse(value: &str) -> Self {
+        match value {
+            "site" => Self::Site,
+            "application" => Self::Application,
+            "entity" => Self::Entity,
+            _ => Self::All,
+        }
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct TargetSelectorProps {
+    pub on_change: Callback<CommandTarget>,
+    /// A target chosen elsewhere, such as the participant roster, to show in the selector
+    #[prop_or_default]
+    pub value: Option<CommandTarget>,
+}
+
+/// Chooses whether a SIMAN command addresses every simulation, one site, one application
+/// or one entity.
+#[function_component(TargetSelector)]
+pub fn target_selector(props: &TargetSelectorProps) -> Html {
+    let kind = use_state(|| TargetKind::All);
+    let site_id = use_state(|| 1u16);
+    let application_id = use_state(|| 1u16);
+    let entity_id = use_state(|| 1u16);
+
+    {
+        let kind = kind.clone();
+        let site_id = site_id.clone();
+        let application_id = application_id.clone();
+        let entity_id = entity_id.clone();
+
+        use_effect_with(props.value, move |value| match *value {
+            None => {}
+            Some(CommandTarget::All) => kind.set(TargetKind::All),
+            Some(CommandTarget::Site { site_id: site }) => {
+                kind.set(TargetKind::Site);
+                site_id.set(site);
+            }
+            Some(CommandTarget::Application(address)) => {
+                kind.set(TargetKind::Application);
+                site_id.set(address.site_id);
+                application_id.set(address.application_id);
+            }
+            Some(CommandTarget::Entity(id)) => {
+                kind.set(TargetKind::Entity);
+                site_id.set(id.site_id);
+                application_id.set(id.application_id);
+                entity_id.set(id.entity_id);
+            }
+        });
+    }
+
+    {
+        let on_change = props.on_change.clone();
+        let target = match *kind {
+            TargetKind::All => CommandTarget::All,
+            TargetKind::Site => CommandTarget::Site { site_id: *site_id },
+            TargetKind::Application => CommandTarget::Application(ApplicationAddress {
+                site_id: *site_id,
+                application_id: *application_id,
+            }),
+            TargetKind::Entity => CommandTarget::Entity(EntityIdentifier {
+                site_id: *site_id,
+                application_id: *application_id,
+                entity_id: *entity_id,
+            }),
+        };
+
+        use_effect_with(target, move |target| on_change.emit(*target));
+    }
+
+    let on_kind = {
+        let kind = kind.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            kind.set(TargetKind::parse(&select.value()));
+        })
+    };
+
+    let show_site = *kind != TargetKind::All;
+    let show_application = matches!(*kind, TargetKind::Application | TargetKind::Entity);
+    let show_entity = *kind == TargetKind::Entity;
+
+    html! {
+        <div class="form-grid">
+            <label>
+                {"Target"}
+                <select onchange={on_kind}>
+                    <option value="all" selected={*kind == TargetKind::All}>{"All"}</option>
+                    <option value="site" selected={*kind == TargetKind::Site}>{"Site"}</option>
+                    <option value="application" selected={*kind == TargetKind::Application}>
+                        {"Application"}
+                    </option>
+                    <option value="entity" selected={*kind == TargetKind::Entity}>{"Entity"}</option>
+                </select>
+            </label>
+            if show_site {
+                <label>
+                    {"Site"}
+                    <input type="number" min="1" max="65534" value={site_id.to_string()}
+                        oninput={on_u16_input(site_id.clone())} />
+                </label>
+            }
+            if show_application {
+                <label>
+                    {"Application"}
+                    <input type="number" min="1" max="65534" value={application_id.to_string()}
+                        oninput={on_u16_input(application_id.clone())} />
+                </label>
+            }
+            if show_entity {
+                <label>
+                    {"Entity"}
+                    <input type="number" min="1" max="65534" value={entity_id.to_string()}
+                        oninput={on_u16_input(entity_id.clone())} />
+                </label>
+            }
+        </div>
+    }
+}
@@ -0,0 +1,181 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use crate::dashboard::{Notification, NotificationLevel};
+use crate::events::subscribe;
+use crate::forms::format_time;
+use crate::models::{TrafficRow, TrafficSnapshot};
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "core"], catch)]
+    async fn invoke(cmd: &str, args: JsValue) -> Result<JsValue, JsValue>;
+}
+
+/// Formats a byte rate with a binary prefix, such as `12.3 KiB/s`.
+fn format_byte_rate(bytes_per_s: f64) -> String {
+    const UNITS: [&str; 3] = ["B/s", "KiB/s", "MiB/s"];
+
+    let mut value = bytes_per_s;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+fn traffic_table(
+    title: &'static str,
+    key: &'static str,
+    rows: &[TrafficRow],
+    class: Option<&'static str>,
+) -> Html {
+    if rows.is_empty() {
+        return html! {};
+    }
+
+    let rows = rows.iter().map(|row| {
+        let c = &row.counter;
+
+        html! {
+            <tr class={classes!(class)}>
+                <td>{row.direction.clone()}</td>
+                <td>{row.key.clone()}</td>
+                <td>{c.pdus}</td>
+                <td>{c.bytes}</td>
+                <td>{format!("{:.1}", c.pdu_rate)}</td>
+                <td>{format_byte_rate(c.byte_rate)}</td>
+                <td>{format!("{:.1}", c.average_pdu_rate)}</td>
+                <td>{format_byte_rate(c.average_byte_rate)}</td>
+            </tr>
+        }
+    });
+
+    html! {
+        <>
+            <h3>{title}</h3>
+            <table class="data-table">
+                <thead>
+                    <tr>
+                        <th>{"Direction"}</th>
+                        <th>{key}</th>
+                        <th>{"PDUs"}</th>
+                        <th>{"Bytes"}</th>
+                        <th>{"PDUs/s"}</th>
+                        <th>{"Rate"}</th>
+                        <th>{"Avg PDUs/s"}</th>
+                        <th>{"Avg rate"}</th>
+                    </tr>
+                </thead>
+                <tbody>{for rows}</tbody>
+            </table>
+        </>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct TrafficStatisticsProps {
+    pub on_notify: Callback<Notification>,
+}
+
+/// Shows the PDUs received and sent by type, application and exercise with their current
+/// and moving average rates, and the entities sending more than the scenario allows.
+#[function_component(TrafficStatistics)]
+pub fn traffic_statistics(props: &TrafficStatisticsProps) -> Html {
+    let traffic = use_state(TrafficSnapshot::default);
+
+    {
+        let notify = props.on_notify.clone();
+        let traffic = traffic.clone();
+
+        use_effect_with((), move |()| {
+            {
+                let traffic = traffic.clone();
+                subscribe(
+                    "traffic",
+                    Callback::from(move |latest: TrafficSnapshot| traffic.set(latest)),
+                );
+            }
+
+            spawn_local(async move {
+                let result = invoke("get_traffic_statistics", JsValue::NULL)
+                    .await
+                    .map_err(|err| err.as_string().unwrap_or_else(|| "unknown error".into()))
+                    .and_then(|value| {
+                        serde_wasm_bindgen::from_value::<TrafficSnapshot>(value)
+                            .map_err(|e| format!("Unable to read traffic statistics: {e}"))
+                    });
+
+                match result {
+                    Ok(latest) => traffic.set(latest),
+                    Err(message) => notify.emit(Notification {
+                        message,
+                        level: NotificationLevel::Error,
+                    }),
+                }
+            });
+        });
+    }
+
+    let on_reset = {
+        let notify = props.on_notify.clone();
+        let traffic = traffic.clone();
+        Callback::from(move |_| {
+            let notify = notify.clone();
+            let traffic = traffic.clone();
+            spawn_local(async move {
+                match invoke("reset_traffic_statistics", JsValue::NULL).await {
+                    Ok(_) => traffic.set(TrafficSnapshot::default()),
+                    Err(err) => notify.emit(Notification {
+                        message: err.as_string().unwrap_or_else(|| "unknown error".into()),
+                        level: NotificationLevel::Error,
+                    }),
+                }
+            });
+        })
+    };
+
+    let on_export = {
+        let notify = props.on_notify.clone();
+        Callback::from(move |_| {
+            let notify = notify.clone();
+            spawn_local(async move {
+                let notification = match invoke("export_traffic_statistics", JsValue::NULL).await {
+                    Ok(_) => Notification {
+                        message: "Traffic statistics exported".into(),
+                        level: NotificationLevel::Info,
+                    },
+                    Err(err) => Notification {
+                        message: err.as_string().unwrap_or_else(|| "unknown error".into()),
+                        level: NotificationLevel::Error,
+                    },
+                };
+                notify.emit(notification);
+            });
+        })
+    };
+
+    html! {
+        <section class="panel wide">
+            <h2>{"Traffic"}</h2>
+            <div class="controls">
+                <button onclick={on_reset}>{"Reset"}</button>
+                <button onclick={on_export}>{"Export CSV…"}</button>
+            </div>
+            if traffic.since_ms == 0 {
+                <p>{"No PDUs counted yet"}</p>
+            } else {
+                <p>{format!("Counting since {}", format_time(traffic.since_ms))}</p>
+                {traffic_table("Totals", "Scope", &traffic.totals, None)}
+                {traffic_table("Chatty entities", "Entity", &traffic.chatty_entities, Some("warning"))}
+                {traffic_table("By PDU type", "PDU type", &traffic.pdu_types, None)}
+                {traffic_table("By application", "Application", &traffic.applications, None)}
+                {traffic_table("By exercise", "Exercise", &traffic.exercises, None)}
+            }
+        </section>
+    }
+}